@@ -1,73 +1,14 @@
-use crate::channel::traits::ErrorChannel;
-use crate::code::error_vector::ErrorVector;
-use bitvec::prelude::*;
-use rand::distr::Bernoulli;
-use rand::prelude::*;
+use crate::channel::single_pauli::SinglePauliChannel;
 
-pub struct BitFlipChannel {
-    num_qubits: usize,
-    error_rate: f64,
-}
-
-impl BitFlipChannel {
-    pub fn new(num_qubits: usize, error_rate: f64) -> Self {
-        assert!(
-            error_rate >= 0.0 && error_rate <= 1.0,
-            "Error rate must be between 0 and 1"
-        );
-
-        Self {
-            num_qubits,
-            error_rate,
-        }
-    }
-
-    pub fn num_qubits(&self) -> usize {
-        self.num_qubits
-    }
-
-    pub fn error_rate(&self) -> f64 {
-        self.error_rate
-    }
-}
-
-impl ErrorChannel for BitFlipChannel {
-    fn sample(&self) -> ErrorVector {
-        let mut rng = rand::rng();
-        let mut x_part = bitvec![u64, Lsb0; 0; self.num_qubits];
-        let z_part = bitvec![u64, Lsb0; 0; self.num_qubits];
-
-        let dist = Bernoulli::new(self.error_rate).unwrap();
-
-        for qubit_idx in 0..self.num_qubits {
-            if dist.sample(&mut rng) {
-                x_part.set(qubit_idx, true);
-            }
-        }
-
-        ErrorVector::new(x_part, z_part)
-    }
-
-    fn sample_batch(&self, num_samples: usize) -> Vec<ErrorVector> {
-        (0..num_samples).map(|_| self.sample()).collect()
-    }
-
-    fn x_error_rate(&self) -> f64 {
-        self.error_rate
-    }
-
-    fn y_error_rate(&self) -> f64 {
-        0.0
-    }
-
-    fn z_error_rate(&self) -> f64 {
-        0.0
-    }
-}
+/// 各量子ビットに確率pでX誤りが起こるビット反転チャネル
+/// `SinglePauliChannel`の別名で、`BitFlipChannel::new(num_qubits, error_rate)`で構成する
+/// Z誤りやY誤りのチャネルは`SinglePauliChannel::phase_flip`、`SinglePauliChannel::bit_phase_flip`を使う
+pub type BitFlipChannel = SinglePauliChannel;
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::channel::traits::ErrorChannel;
 
     #[test]
     fn test_bit_flip_channel_sample() {
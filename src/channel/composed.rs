@@ -0,0 +1,123 @@
+use crate::Word;
+use crate::channel::traits::ErrorChannel;
+use crate::code::error_vector::ErrorVector;
+use bitvec::prelude::*;
+use rand::prelude::*;
+
+/// 複数のチャネルを順に作用させるチャネル
+/// 各チャネルから独立にサンプリングした誤りのXOR（Pauli演算子の積）を誤りとする
+/// 例えば脱分極チャネルと位相反転チャネルを重ねて、Z誤りに偏った雑音を作るのに使う
+pub struct ComposedChannel {
+    num_qubits: usize,
+    channels: Vec<Box<dyn ErrorChannel>>,
+}
+
+impl ComposedChannel {
+    pub fn new(channels: Vec<Box<dyn ErrorChannel>>) -> Self {
+        let num_qubits = channels
+            .first()
+            .expect("チャネルが1つもありません")
+            .num_qubits();
+        for channel in &channels {
+            assert_eq!(
+                channel.num_qubits(),
+                num_qubits,
+                "チャネルの量子ビット数({})が一致しません: {}",
+                channel.num_qubits(),
+                num_qubits
+            );
+        }
+
+        Self {
+            num_qubits,
+            channels,
+        }
+    }
+
+    pub fn num_qubits(&self) -> usize {
+        self.num_qubits
+    }
+
+    pub fn channels(&self) -> &[Box<dyn ErrorChannel>] {
+        &self.channels
+    }
+}
+
+impl ErrorChannel for ComposedChannel {
+    fn sample_with_rng(&self, rng: &mut dyn RngCore) -> ErrorVector {
+        let identity = ErrorVector::new(
+            bitvec![Word, Lsb0; 0; self.num_qubits],
+            bitvec![Word, Lsb0; 0; self.num_qubits],
+        );
+        self.channels.iter().fold(identity, |error, channel| {
+            error ^ channel.sample_with_rng(rng)
+        })
+    }
+
+    fn num_qubits(&self) -> usize {
+        self.num_qubits
+    }
+
+    /// 各チャネルの確率の和を返す
+    /// 複数のチャネルの誤りが打ち消し合う寄与を無視した、確率が小さいときの近似である
+    fn x_error_rate(&self) -> f64 {
+        self.channels.iter().map(|c| c.x_error_rate()).sum()
+    }
+
+    /// 各チャネルの確率の和を返す（`x_error_rate`と同じ近似）
+    fn y_error_rate(&self) -> f64 {
+        self.channels.iter().map(|c| c.y_error_rate()).sum()
+    }
+
+    /// 各チャネルの確率の和を返す（`x_error_rate`と同じ近似）
+    fn z_error_rate(&self) -> f64 {
+        self.channels.iter().map(|c| c.z_error_rate()).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::channel::depolarizing::DepolarizingChannel;
+    use crate::channel::single_pauli::SinglePauliChannel;
+    use rand::rngs::StdRng;
+
+    #[test]
+    fn test_composed_channel_with_zero_rate_channel() {
+        let composed = ComposedChannel::new(vec![
+            Box::new(DepolarizingChannel::new(10, 0.2)),
+            Box::new(SinglePauliChannel::phase_flip(10, 0.0)),
+        ]);
+        let depolarizing = DepolarizingChannel::new(10, 0.2);
+        let zero = SinglePauliChannel::phase_flip(10, 0.0);
+
+        // 同じ乱数列を使えば、誤り率0のチャネルを重ねても結果は変わらない
+        let mut composed_rng = StdRng::seed_from_u64(5);
+        let mut rng = StdRng::seed_from_u64(5);
+        for _ in 0..50 {
+            let expected = depolarizing.sample_with_rng(&mut rng);
+            assert_eq!(zero.sample_with_rng(&mut rng).num_errors(), 0);
+            assert_eq!(composed.sample_with_rng(&mut composed_rng), expected);
+        }
+    }
+
+    #[test]
+    fn test_composed_channel_error_rates() {
+        let composed = ComposedChannel::new(vec![
+            Box::new(DepolarizingChannel::new(4, 0.3)),
+            Box::new(SinglePauliChannel::phase_flip(4, 0.05)),
+        ]);
+        assert!((composed.x_error_rate() - 0.1).abs() < 1e-12);
+        assert!((composed.y_error_rate() - 0.1).abs() < 1e-12);
+        assert!((composed.z_error_rate() - 0.15).abs() < 1e-12);
+    }
+
+    #[test]
+    #[should_panic(expected = "チャネルの量子ビット数")]
+    fn test_composed_channel_mismatched_qubits() {
+        let _composed = ComposedChannel::new(vec![
+            Box::new(DepolarizingChannel::new(4, 0.1)),
+            Box::new(SinglePauliChannel::phase_flip(5, 0.1)),
+        ]);
+    }
+}
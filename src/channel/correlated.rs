@@ -0,0 +1,217 @@
+use crate::Word;
+use crate::channel::traits::ErrorChannel;
+use crate::code::error_vector::ErrorVector;
+use bitvec::prelude::*;
+use rand::distr::Bernoulli;
+use rand::distr::weighted::WeightedIndex;
+use rand::prelude::*;
+
+/// 量子ビット対に相関した誤りが起こるチャネル
+/// まず各量子ビットに独立な脱分極誤りを作用させ、
+/// 続いて指定された各量子ビット対に確率pで2量子ビット脱分極誤り
+/// （恒等演算子以外の15種類の2量子ビットPauliから一様に選ぶ）を作用させる
+/// クロストークのような非IIDな雑音に対する復号器の振る舞いを調べるために使う
+pub struct CorrelatedChannel {
+    num_qubits: usize,
+    single_rate: f64,
+    single_distribution: WeightedIndex<f64>,
+    pairs: Vec<(usize, usize, f64)>,
+}
+
+impl CorrelatedChannel {
+    pub fn new(num_qubits: usize, single_rate: f64, pairs: Vec<(usize, usize, f64)>) -> Self {
+        assert!(
+            (0.0..=1.0).contains(&single_rate),
+            "Error rate must be between 0 and 1"
+        );
+        for &(a, b, p) in &pairs {
+            assert!(
+                a < num_qubits && b < num_qubits,
+                "量子ビット対({}, {})がnum_qubits({})を超えています",
+                a,
+                b,
+                num_qubits
+            );
+            assert_ne!(a, b, "量子ビット対の2つの量子ビットが同じです: {}", a);
+            assert!(
+                (0.0..=1.0).contains(&p),
+                "Error rate must be between 0 and 1"
+            );
+        }
+
+        let weights = [
+            1.0 - single_rate,
+            single_rate / 3.0,
+            single_rate / 3.0,
+            single_rate / 3.0,
+        ];
+        let single_distribution = WeightedIndex::new(weights).unwrap();
+
+        Self {
+            num_qubits,
+            single_rate,
+            single_distribution,
+            pairs,
+        }
+    }
+
+    pub fn num_qubits(&self) -> usize {
+        self.num_qubits
+    }
+
+    pub fn single_rate(&self) -> f64 {
+        self.single_rate
+    }
+
+    pub fn pairs(&self) -> &[(usize, usize, f64)] {
+        &self.pairs
+    }
+}
+
+/// Pauliの種類 (0: I, 1: X, 2: Y, 3: Z) を量子ビットに掛け合わせる
+fn apply_pauli(
+    x_part: &mut BitVec<Word, Lsb0>,
+    z_part: &mut BitVec<Word, Lsb0>,
+    qubit_idx: usize,
+    pauli: usize,
+) {
+    let (x, z) = match pauli {
+        0 => (false, false),
+        1 => (true, false),
+        2 => (true, true),
+        3 => (false, true),
+        _ => unreachable!(),
+    };
+    let current_x = x_part[qubit_idx];
+    let current_z = z_part[qubit_idx];
+    x_part.set(qubit_idx, current_x ^ x);
+    z_part.set(qubit_idx, current_z ^ z);
+}
+
+impl ErrorChannel for CorrelatedChannel {
+    fn sample_with_rng(&self, rng: &mut dyn RngCore) -> ErrorVector {
+        let mut x_part = bitvec![Word, Lsb0; 0; self.num_qubits];
+        let mut z_part = bitvec![Word, Lsb0; 0; self.num_qubits];
+
+        for qubit_idx in 0..self.num_qubits {
+            let pauli = self.single_distribution.sample(rng);
+            apply_pauli(&mut x_part, &mut z_part, qubit_idx, pauli);
+        }
+
+        for &(a, b, p) in &self.pairs {
+            if Bernoulli::new(p).unwrap().sample(rng) {
+                // 1..16 を (qubit a の Pauli, qubit b の Pauli) に対応させる
+                let two_qubit_pauli = rng.random_range(1..16);
+                apply_pauli(&mut x_part, &mut z_part, a, two_qubit_pauli / 4);
+                apply_pauli(&mut x_part, &mut z_part, b, two_qubit_pauli % 4);
+            }
+        }
+
+        ErrorVector::new(x_part, z_part)
+    }
+
+    /// 1量子ビット誤りと量子ビット対の誤りを合成した、各量子ビットの周辺誤り確率を返す
+    fn marginal_probabilities(&self) -> Vec<f64> {
+        // 各量子ビットに作用するPauliが恒等演算子でない確率を、
+        // 独立な誤りを順に合成しながら追跡する
+        let mut probabilities = vec![self.single_rate; self.num_qubits];
+        for &(a, b, p) in &self.pairs {
+            // 15種類の2量子ビットPauliのうち、片方の量子ビットで恒等演算子でないものは12種類
+            let pair_rate = p * 12.0 / 15.0;
+            for qubit_idx in [a, b] {
+                let current = probabilities[qubit_idx];
+                // 非自明なPauli同士が打ち消し合うのは、同じPauliが選ばれたとき（確率1/3）だけ
+                probabilities[qubit_idx] = current * (1.0 - pair_rate)
+                    + pair_rate * (1.0 - current)
+                    + current * pair_rate * 2.0 / 3.0;
+            }
+        }
+        probabilities
+    }
+
+    // 以下の誤り率は独立な1量子ビット誤りの部分だけを表す
+    // 量子ビット対に由来する相関した誤りは含まない
+    fn num_qubits(&self) -> usize {
+        self.num_qubits
+    }
+
+    fn x_error_rate(&self) -> f64 {
+        self.single_rate / 3.0
+    }
+
+    fn y_error_rate(&self) -> f64 {
+        self.single_rate / 3.0
+    }
+
+    fn z_error_rate(&self) -> f64 {
+        self.single_rate / 3.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_correlated_channel_sample() {
+        let channel = CorrelatedChannel::new(5, 0.1, vec![(0, 1, 0.2)]);
+        let error_vector = channel.sample();
+        assert_eq!(error_vector.num_qubits(), 5);
+    }
+
+    #[test]
+    fn test_correlated_channel_pair_only() {
+        let channel = CorrelatedChannel::new(4, 0.0, vec![(0, 1, 1.0)]);
+        for error_vector in channel.sample_batch(100) {
+            let support = error_vector.x_part().clone() | error_vector.z_part();
+            assert!(support[0] || support[1]);
+            assert!(!support[2] && !support[3]);
+        }
+    }
+
+    #[test]
+    fn test_correlated_channel_unlisted_qubit_rate() {
+        let num_samples = 20000;
+        let single_rate = 0.05;
+        let channel = CorrelatedChannel::new(3, single_rate, vec![(0, 1, 0.5)]);
+        let samples = channel.sample_batch_with_seed(num_samples, 7);
+
+        let count_errors = |qubit_idx: usize| {
+            samples
+                .iter()
+                .filter(|ev| ev.x_part()[qubit_idx] || ev.z_part()[qubit_idx])
+                .count() as f64
+        };
+
+        // qubit 2 はどの対にも含まれないので、誤り率は single_rate になる
+        // 期待値1000に対して標準偏差は約31
+        let expected = num_samples as f64 * single_rate;
+        assert!((count_errors(2) - expected).abs() < expected * 0.15);
+
+        // 対に含まれる qubit 0 は相関した誤りの分だけ誤りが多くなる
+        assert!(count_errors(0) > expected * 2.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_correlated_channel_invalid_pair() {
+        CorrelatedChannel::new(3, 0.1, vec![(0, 3, 0.1)]);
+    }
+
+    #[test]
+    fn test_correlated_channel_marginal_probabilities() {
+        let num_samples = 20000;
+        let channel = CorrelatedChannel::new(3, 0.1, vec![(0, 1, 0.3)]);
+        let marginals = channel.marginal_probabilities();
+        assert!((marginals[2] - 0.1).abs() < 1e-12);
+        assert_eq!(marginals[0], marginals[1]);
+
+        let samples = channel.sample_batch_with_seed(num_samples, 11);
+        let count = samples
+            .iter()
+            .filter(|ev| ev.x_part()[0] || ev.z_part()[0])
+            .count() as f64;
+        let observed = count / num_samples as f64;
+        assert!((observed - marginals[0]).abs() < 0.02);
+    }
+}
@@ -1,19 +1,25 @@
+use crate::Word;
 use crate::channel::traits::ErrorChannel;
 use crate::code::error_vector::ErrorVector;
 use bitvec::prelude::*;
 use rand::distr::weighted::WeightedIndex;
 use rand::prelude::*;
+use rand::rngs::StdRng;
+use std::sync::Mutex;
 
 pub struct DepolarizingChannel {
     num_qubits: usize,
     error_rate: f64,
     distribution: WeightedIndex<f64>,
+    /// `seeded`で構成した場合の乱数生成器
+    /// `None`ならサンプリングのたびにスレッドローカルな乱数生成器を使う
+    rng: Option<Mutex<StdRng>>,
 }
 
 impl DepolarizingChannel {
     pub fn new(num_qubits: usize, error_rate: f64) -> Self {
         assert!(
-            error_rate >= 0.0 && error_rate <= 1.0,
+            (0.0..=1.0).contains(&error_rate),
             "Error rate must be between 0 and 1"
         );
         let weights = [
@@ -22,12 +28,23 @@ impl DepolarizingChannel {
             error_rate / 3.0,
             error_rate / 3.0,
         ];
-        let distribution = WeightedIndex::new(&weights).unwrap();
+        let distribution = WeightedIndex::new(weights).unwrap();
 
         Self {
             num_qubits,
             error_rate,
             distribution,
+            rng: None,
+        }
+    }
+
+    /// シードを固定した乱数生成器を内部に持つチャネルを構成する
+    /// `sample`や`sample_batch`はこの乱数生成器を順に進めて使うので、
+    /// 同じシードで構成したチャネルからは同じ順番で同じ誤りが得られる
+    pub fn seeded(num_qubits: usize, error_rate: f64, seed: u64) -> Self {
+        Self {
+            rng: Some(Mutex::new(StdRng::seed_from_u64(seed))),
+            ..Self::new(num_qubits, error_rate)
         }
     }
 
@@ -42,12 +59,18 @@ impl DepolarizingChannel {
 
 impl ErrorChannel for DepolarizingChannel {
     fn sample(&self) -> ErrorVector {
-        let mut rng = rand::rng();
-        let mut x_part = bitvec![u64, Lsb0; 0; self.num_qubits];
-        let mut z_part = bitvec![u64, Lsb0; 0; self.num_qubits];
+        match &self.rng {
+            Some(rng) => self.sample_with_rng(&mut *rng.lock().unwrap()),
+            None => self.sample_with_rng(&mut rand::rng()),
+        }
+    }
+
+    fn sample_with_rng(&self, rng: &mut dyn RngCore) -> ErrorVector {
+        let mut x_part = bitvec![Word, Lsb0; 0; self.num_qubits];
+        let mut z_part = bitvec![Word, Lsb0; 0; self.num_qubits];
 
         for qubit_idx in 0..self.num_qubits {
-            let error_type = self.distribution.sample(&mut rng);
+            let error_type = self.distribution.sample(rng);
             match error_type {
                 0 => {
                     // No error
@@ -73,7 +96,19 @@ impl ErrorChannel for DepolarizingChannel {
     }
 
     fn sample_batch(&self, num_samples: usize) -> Vec<ErrorVector> {
-        (0..num_samples).map(|_| self.sample()).collect()
+        match &self.rng {
+            Some(rng) => {
+                let mut rng = rng.lock().unwrap();
+                (0..num_samples)
+                    .map(|_| self.sample_with_rng(&mut *rng))
+                    .collect()
+            }
+            None => (0..num_samples).map(|_| self.sample()).collect(),
+        }
+    }
+
+    fn num_qubits(&self) -> usize {
+        self.num_qubits
     }
 
     fn x_error_rate(&self) -> f64 {
@@ -109,4 +144,65 @@ mod tests {
             assert_eq!(ev.num_qubits(), 5);
         }
     }
+
+    #[test]
+    fn test_depolarizing_channel_sample_batch_parallel() {
+        let num_qubits = 20;
+        let num_samples = 5000;
+        let error_rate = 0.1;
+        let channel = DepolarizingChannel::new(num_qubits, error_rate);
+
+        let parallel = channel.sample_batch_parallel_with_seed(num_samples, 42);
+        let sequential = channel.sample_batch_with_seed(num_samples, 42);
+        assert_eq!(parallel.len(), num_samples);
+        assert_eq!(sequential.len(), num_samples);
+
+        // 同じシードからは同じ結果が得られる
+        assert_eq!(
+            parallel,
+            channel.sample_batch_parallel_with_seed(num_samples, 42)
+        );
+
+        // 誤りの総数の期待値は num_qubits * num_samples * error_rate = 10000
+        // 標準偏差は約95なので、期待値から±5%以内に収まるはず
+        let expected = (num_qubits * num_samples) as f64 * error_rate;
+        for batch in [&parallel, &sequential] {
+            let total_weight: usize = batch.iter().map(|ev| ev.num_errors()).sum();
+            assert!((total_weight as f64 - expected).abs() < expected * 0.05);
+        }
+    }
+
+    #[test]
+    fn test_depolarizing_channel_expected_weight() {
+        let channel = DepolarizingChannel::new(10, 0.3);
+        let marginals = channel.marginal_probabilities();
+        assert_eq!(marginals.len(), 10);
+        assert!(marginals.iter().all(|&p| (p - 0.3).abs() < 1e-12));
+        assert!((channel.expected_weight() - 10.0 * 0.3).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_depolarizing_channel_error_rates() {
+        let channel = DepolarizingChannel::new(4, 0.3);
+        for rate in [
+            channel.x_error_rate(),
+            channel.y_error_rate(),
+            channel.z_error_rate(),
+        ] {
+            assert!((rate - 0.1).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_depolarizing_channel_seeded() {
+        let a = DepolarizingChannel::seeded(20, 0.3, 11);
+        let b = DepolarizingChannel::seeded(20, 0.3, 11);
+        assert_eq!(a.sample_batch(50), b.sample_batch(50));
+        // 内部の乱数生成器は呼び出しをまたいで進む
+        assert_eq!(a.sample(), b.sample());
+        assert_ne!(
+            a.sample_batch(50),
+            DepolarizingChannel::seeded(20, 0.3, 11).sample_batch(50)
+        );
+    }
 }
@@ -0,0 +1,105 @@
+use crate::math::sparse_matrix::BinarySparseMatrix;
+
+/// 検出器誤りモデル(DEM)を表す構造体
+/// 独立に起こる誤りのリストを持ち、各誤りは発生確率と、起こったときに反転する検出器の集合で表す
+/// 回路レベルの雑音を、検出器を行・誤りを列とする検査行列と列ごとの事前確率に落とし込むのに使う
+///
+/// # Examples
+/// ```rust
+/// use qldpc_sim::channel::detector_error_model::DetectorErrorModel;
+///
+/// let mut dem = DetectorErrorModel::new(2);
+/// dem.add_error(0.1, &[0]);
+/// dem.add_error(0.2, &[0, 1]);
+/// assert_eq!(dem.check_matrix().row_adj(), &[vec![0, 1], vec![1]]);
+/// assert_eq!(dem.probabilities(), vec![0.1, 0.2]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct DetectorErrorModel {
+    num_detectors: usize,
+    /// (発生確率, 反転する検出器)
+    errors: Vec<(f64, Vec<usize>)>,
+}
+
+impl DetectorErrorModel {
+    pub fn new(num_detectors: usize) -> Self {
+        Self {
+            num_detectors,
+            errors: Vec::new(),
+        }
+    }
+
+    /// 確率`probability`で起こり、検出器`detectors`を反転させる誤りを追加する
+    pub fn add_error(&mut self, probability: f64, detectors: &[usize]) {
+        assert!(
+            (0.0..=1.0).contains(&probability),
+            "Error rate must be between 0 and 1"
+        );
+        for &detector in detectors {
+            assert!(
+                detector < self.num_detectors,
+                "検出器のインデックス({})が検出器の数({})を超えています",
+                detector,
+                self.num_detectors
+            );
+        }
+        self.errors.push((probability, detectors.to_vec()));
+    }
+
+    pub fn num_detectors(&self) -> usize {
+        self.num_detectors
+    }
+
+    pub fn num_errors(&self) -> usize {
+        self.errors.len()
+    }
+
+    pub fn errors(&self) -> &[(f64, Vec<usize>)] {
+        &self.errors
+    }
+
+    /// 検出器を行、誤りを列とする検査行列を返す
+    /// j列目はj番目の誤りが反転させる検出器の集合になる
+    pub fn check_matrix(&self) -> BinarySparseMatrix {
+        let col_adj = self
+            .errors
+            .iter()
+            .map(|(_, detectors)| detectors.clone())
+            .collect();
+        BinarySparseMatrix::from_col_adj(self.num_detectors, self.errors.len(), col_adj)
+    }
+
+    /// 各誤りの発生確率を`check_matrix`の列の順に返す
+    pub fn probabilities(&self) -> Vec<f64> {
+        self.errors
+            .iter()
+            .map(|&(probability, _)| probability)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detector_error_model_check_matrix() {
+        let mut dem = DetectorErrorModel::new(3);
+        dem.add_error(0.01, &[0, 1]);
+        dem.add_error(0.02, &[1, 2]);
+        dem.add_error(0.03, &[2]);
+        assert_eq!(dem.num_errors(), 3);
+
+        let matrix = dem.check_matrix();
+        assert_eq!(matrix.shape(), (3, 3));
+        assert_eq!(matrix.row_adj(), &[vec![0], vec![0, 1], vec![1, 2]]);
+        assert_eq!(dem.probabilities(), vec![0.01, 0.02, 0.03]);
+    }
+
+    #[test]
+    #[should_panic(expected = "検出器のインデックス(2)が検出器の数(2)を超えています")]
+    fn test_detector_error_model_detector_out_of_range() {
+        let mut dem = DetectorErrorModel::new(2);
+        dem.add_error(0.1, &[2]);
+    }
+}
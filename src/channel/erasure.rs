@@ -0,0 +1,115 @@
+use crate::Word;
+use crate::channel::traits::{ErasureErrorChannel, ErrorChannel};
+use crate::code::error_vector::ErrorVector;
+use bitvec::prelude::*;
+use rand::distr::Bernoulli;
+use rand::prelude::*;
+
+/// 消失チャネルを表す構造体
+/// 各量子ビットが確率pで消失し、消失した量子ビットには一様ランダムなPauli(I, X, Y, Z)が作用する
+/// どの量子ビットが消失したかは既知の情報として復号器に渡せる
+pub struct ErasureChannel {
+    num_qubits: usize,
+    erasure_rate: f64,
+}
+
+impl ErasureChannel {
+    pub fn new(num_qubits: usize, erasure_rate: f64) -> Self {
+        assert!(
+            (0.0..=1.0).contains(&erasure_rate),
+            "Erasure rate must be between 0 and 1"
+        );
+
+        Self {
+            num_qubits,
+            erasure_rate,
+        }
+    }
+
+    pub fn num_qubits(&self) -> usize {
+        self.num_qubits
+    }
+
+    pub fn erasure_rate(&self) -> f64 {
+        self.erasure_rate
+    }
+
+    fn sample_with_erasure_rng(&self, rng: &mut dyn RngCore) -> (ErrorVector, Vec<bool>) {
+        let mut x_part = bitvec![Word, Lsb0; 0; self.num_qubits];
+        let mut z_part = bitvec![Word, Lsb0; 0; self.num_qubits];
+        let mut erasure_mask = vec![false; self.num_qubits];
+
+        let dist = Bernoulli::new(self.erasure_rate).unwrap();
+
+        for (qubit_idx, erased) in erasure_mask.iter_mut().enumerate() {
+            if dist.sample(rng) {
+                *erased = true;
+                // I, X, Y, Z を等確率で選ぶ
+                x_part.set(qubit_idx, rng.random_bool(0.5));
+                z_part.set(qubit_idx, rng.random_bool(0.5));
+            }
+        }
+
+        (ErrorVector::new(x_part, z_part), erasure_mask)
+    }
+}
+
+impl ErasureErrorChannel for ErasureChannel {
+    fn sample_with_erasure(&self) -> (ErrorVector, Vec<bool>) {
+        self.sample_with_erasure_rng(&mut rand::rng())
+    }
+}
+
+impl ErrorChannel for ErasureChannel {
+    fn sample_with_rng(&self, rng: &mut dyn RngCore) -> ErrorVector {
+        self.sample_with_erasure_rng(rng).0
+    }
+
+    fn num_qubits(&self) -> usize {
+        self.num_qubits
+    }
+
+    fn x_error_rate(&self) -> f64 {
+        self.erasure_rate / 4.0
+    }
+
+    fn y_error_rate(&self) -> f64 {
+        self.erasure_rate / 4.0
+    }
+
+    fn z_error_rate(&self) -> f64 {
+        self.erasure_rate / 4.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_erasure_channel_sample() {
+        let channel = ErasureChannel::new(5, 0.3);
+        let error_vector = channel.sample();
+        assert_eq!(error_vector.num_qubits(), 5);
+    }
+
+    #[test]
+    fn test_erasure_channel_support_within_mask() {
+        let channel = ErasureChannel::new(20, 0.3);
+        for (error_vector, erasure_mask) in channel.sample_batch_with_erasure(100) {
+            assert_eq!(erasure_mask.len(), 20);
+            let support = error_vector.x_part().clone() | error_vector.z_part();
+            for qubit_idx in support.iter_ones() {
+                assert!(erasure_mask[qubit_idx]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_erasure_channel_no_erasure() {
+        let channel = ErasureChannel::new(5, 0.0);
+        let (error_vector, erasure_mask) = channel.sample_with_erasure();
+        assert_eq!(error_vector.num_errors(), 0);
+        assert!(erasure_mask.iter().all(|&erased| !erased));
+    }
+}
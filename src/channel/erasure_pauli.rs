@@ -0,0 +1,136 @@
+use crate::Word;
+use crate::channel::traits::{ErasureErrorChannel, ErrorChannel};
+use crate::code::error_vector::ErrorVector;
+use bitvec::prelude::*;
+use rand::distr::Bernoulli;
+use rand::distr::weighted::WeightedIndex;
+use rand::prelude::*;
+
+/// 消失とPauli誤りを組み合わせたチャネルを表す構造体
+/// 各量子ビットは確率`erasure_rate`で消失し、消失した量子ビットには一様ランダムなPauli(I, X, Y, Z)が作用する
+/// 消失しなかった量子ビットには確率px, py, pzでそれぞれX, Y, Z誤りが起こる
+/// 消失した量子ビットの位置は既知の情報として復号器に渡せる
+pub struct ErasurePauliChannel {
+    num_qubits: usize,
+    erasure_rate: f64,
+    px: f64,
+    py: f64,
+    pz: f64,
+    distribution: WeightedIndex<f64>,
+}
+
+impl ErasurePauliChannel {
+    pub fn new(num_qubits: usize, erasure_rate: f64, px: f64, py: f64, pz: f64) -> Self {
+        assert!(
+            (0.0..=1.0).contains(&erasure_rate),
+            "Erasure rate must be between 0 and 1"
+        );
+        assert!(
+            px >= 0.0 && py >= 0.0 && pz >= 0.0 && px + py + pz <= 1.0,
+            "Pauli error rates must be non-negative and sum to at most 1"
+        );
+        let distribution = WeightedIndex::new([1.0 - px - py - pz, px, py, pz]).unwrap();
+
+        Self {
+            num_qubits,
+            erasure_rate,
+            px,
+            py,
+            pz,
+            distribution,
+        }
+    }
+
+    pub fn erasure_rate(&self) -> f64 {
+        self.erasure_rate
+    }
+
+    /// 消失しなかった量子ビットに起こる (X, Y, Z) 誤りの確率を返す
+    /// 消失位置を使う復号では、消失していない量子ビットの事前確率としてこちらを使う
+    pub fn pauli_error_rates(&self) -> (f64, f64, f64) {
+        (self.px, self.py, self.pz)
+    }
+
+    fn sample_with_erasure_rng(&self, rng: &mut dyn RngCore) -> (ErrorVector, Vec<bool>) {
+        let mut x_part = bitvec![Word, Lsb0; 0; self.num_qubits];
+        let mut z_part = bitvec![Word, Lsb0; 0; self.num_qubits];
+        let mut erasure_mask = vec![false; self.num_qubits];
+
+        let dist = Bernoulli::new(self.erasure_rate).unwrap();
+
+        for (qubit_idx, erased) in erasure_mask.iter_mut().enumerate() {
+            if dist.sample(rng) {
+                *erased = true;
+                // I, X, Y, Z を等確率で選ぶ
+                x_part.set(qubit_idx, rng.random_bool(0.5));
+                z_part.set(qubit_idx, rng.random_bool(0.5));
+            } else {
+                match self.distribution.sample(rng) {
+                    1 => x_part.set(qubit_idx, true),
+                    2 => {
+                        x_part.set(qubit_idx, true);
+                        z_part.set(qubit_idx, true);
+                    }
+                    3 => z_part.set(qubit_idx, true),
+                    _ => {}
+                }
+            }
+        }
+
+        (ErrorVector::new(x_part, z_part), erasure_mask)
+    }
+}
+
+impl ErasureErrorChannel for ErasurePauliChannel {
+    fn sample_with_erasure(&self) -> (ErrorVector, Vec<bool>) {
+        self.sample_with_erasure_rng(&mut rand::rng())
+    }
+}
+
+impl ErrorChannel for ErasurePauliChannel {
+    fn sample_with_rng(&self, rng: &mut dyn RngCore) -> ErrorVector {
+        self.sample_with_erasure_rng(rng).0
+    }
+
+    fn num_qubits(&self) -> usize {
+        self.num_qubits
+    }
+
+    fn x_error_rate(&self) -> f64 {
+        self.erasure_rate / 4.0 + (1.0 - self.erasure_rate) * self.px
+    }
+
+    fn y_error_rate(&self) -> f64 {
+        self.erasure_rate / 4.0 + (1.0 - self.erasure_rate) * self.py
+    }
+
+    fn z_error_rate(&self) -> f64 {
+        self.erasure_rate / 4.0 + (1.0 - self.erasure_rate) * self.pz
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_erasure_pauli_channel_rates() {
+        let channel = ErasurePauliChannel::new(5, 0.2, 0.01, 0.02, 0.03);
+        assert!((channel.x_error_rate() - (0.05 + 0.8 * 0.01)).abs() < 1e-12);
+        assert!((channel.y_error_rate() - (0.05 + 0.8 * 0.02)).abs() < 1e-12);
+        assert!((channel.z_error_rate() - (0.05 + 0.8 * 0.03)).abs() < 1e-12);
+        assert_eq!(channel.pauli_error_rates(), (0.01, 0.02, 0.03));
+    }
+
+    #[test]
+    fn test_erasure_pauli_channel_no_pauli_errors() {
+        // Pauli誤りがなければ、誤りは消失した量子ビットにしか起こらない
+        let channel = ErasurePauliChannel::new(20, 0.3, 0.0, 0.0, 0.0);
+        for (error_vector, erasure_mask) in channel.sample_batch_with_erasure(100) {
+            let support = error_vector.x_part().clone() | error_vector.z_part();
+            for qubit_idx in support.iter_ones() {
+                assert!(erasure_mask[qubit_idx]);
+            }
+        }
+    }
+}
@@ -1,3 +1,9 @@
+pub mod bit_flip;
+pub mod composed;
+pub mod correlated;
 pub mod depolarizing;
+pub mod detector_error_model;
+pub mod erasure;
+pub mod erasure_pauli;
+pub mod single_pauli;
 pub mod traits;
-pub mod bit_flip;
\ No newline at end of file
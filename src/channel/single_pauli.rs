@@ -0,0 +1,164 @@
+use crate::Word;
+use crate::channel::traits::ErrorChannel;
+use crate::code::error_vector::ErrorVector;
+use crate::code::paulis::PauliType;
+use bitvec::prelude::*;
+use rand::distr::Bernoulli;
+use rand::prelude::*;
+
+/// 各量子ビットに確率pで1種類のPauli誤りだけが起こるチャネル
+/// X誤りならビット反転、Z誤りなら位相反転、Y誤りならビット位相反転チャネルになる
+/// `new`はビット反転チャネルを構成し、`channel::bit_flip::BitFlipChannel`はこの型の別名になっている
+///
+/// # Examples
+/// ```rust
+/// use qldpc_sim::channel::single_pauli::SinglePauliChannel;
+/// use qldpc_sim::channel::traits::ErrorChannel;
+///
+/// let channel = SinglePauliChannel::phase_flip(5, 0.1);
+/// assert_eq!(channel.z_error_rate(), 0.1);
+/// assert_eq!(channel.x_error_rate(), 0.0);
+/// ```
+pub struct SinglePauliChannel {
+    num_qubits: usize,
+    error_rate: f64,
+    pauli: PauliType,
+}
+
+impl SinglePauliChannel {
+    /// 各量子ビットに確率pで`pauli`の誤りが起こるチャネル
+    pub fn with_pauli(num_qubits: usize, error_rate: f64, pauli: PauliType) -> Self {
+        assert!(
+            (0.0..=1.0).contains(&error_rate),
+            "Error rate must be between 0 and 1"
+        );
+
+        Self {
+            num_qubits,
+            error_rate,
+            pauli,
+        }
+    }
+
+    /// 各量子ビットに確率pでX誤りが起こるビット反転チャネル
+    pub fn new(num_qubits: usize, error_rate: f64) -> Self {
+        Self::with_pauli(num_qubits, error_rate, PauliType::X)
+    }
+
+    /// 各量子ビットに確率pでZ誤りが起こる位相反転チャネル
+    pub fn phase_flip(num_qubits: usize, error_rate: f64) -> Self {
+        Self::with_pauli(num_qubits, error_rate, PauliType::Z)
+    }
+
+    /// 各量子ビットに確率pでY誤りが起こるビット位相反転チャネル
+    pub fn bit_phase_flip(num_qubits: usize, error_rate: f64) -> Self {
+        Self::with_pauli(num_qubits, error_rate, PauliType::Y)
+    }
+
+    pub fn num_qubits(&self) -> usize {
+        self.num_qubits
+    }
+
+    pub fn error_rate(&self) -> f64 {
+        self.error_rate
+    }
+
+    pub fn pauli(&self) -> PauliType {
+        self.pauli
+    }
+
+    /// `pauli`の誤りが起こる確率を返す。それ以外の種類の誤りは起こらない
+    fn error_rate_of(&self, pauli: PauliType) -> f64 {
+        if self.pauli == pauli {
+            self.error_rate
+        } else {
+            0.0
+        }
+    }
+}
+
+impl ErrorChannel for SinglePauliChannel {
+    fn sample_with_rng(&self, rng: &mut dyn RngCore) -> ErrorVector {
+        let mut x_part = bitvec![Word, Lsb0; 0; self.num_qubits];
+        let mut z_part = bitvec![Word, Lsb0; 0; self.num_qubits];
+
+        let dist = Bernoulli::new(self.error_rate).unwrap();
+
+        for qubit_idx in 0..self.num_qubits {
+            if dist.sample(rng) {
+                x_part.set(qubit_idx, self.pauli.has_x());
+                z_part.set(qubit_idx, self.pauli.has_z());
+            }
+        }
+
+        ErrorVector::new(x_part, z_part)
+    }
+
+    fn num_qubits(&self) -> usize {
+        self.num_qubits
+    }
+
+    fn x_error_rate(&self) -> f64 {
+        self.error_rate_of(PauliType::X)
+    }
+
+    fn y_error_rate(&self) -> f64 {
+        self.error_rate_of(PauliType::Y)
+    }
+
+    fn z_error_rate(&self) -> f64 {
+        self.error_rate_of(PauliType::Z)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_single_pauli_channel_sample() {
+        let channel = SinglePauliChannel::new(5, 0.2);
+        let error_vector = channel.sample();
+        assert_eq!(error_vector.num_qubits(), 5);
+    }
+
+    #[test]
+    fn test_single_pauli_channel_sample_batch() {
+        let channel = SinglePauliChannel::new(5, 0.2);
+        let error_vectors = channel.sample_batch(10);
+        assert_eq!(error_vectors.len(), 10);
+    }
+
+    #[test]
+    fn test_single_pauli_channel_error_rates() {
+        let bit_flip = SinglePauliChannel::new(4, 0.2);
+        assert_eq!(bit_flip.x_error_rate(), 0.2);
+        assert_eq!(bit_flip.y_error_rate(), 0.0);
+        assert_eq!(bit_flip.z_error_rate(), 0.0);
+
+        let phase_flip = SinglePauliChannel::phase_flip(4, 0.2);
+        assert_eq!(phase_flip.x_error_rate(), 0.0);
+        assert_eq!(phase_flip.y_error_rate(), 0.0);
+        assert_eq!(phase_flip.z_error_rate(), 0.2);
+
+        let bit_phase_flip = SinglePauliChannel::bit_phase_flip(4, 0.2);
+        assert_eq!(bit_phase_flip.x_error_rate(), 0.0);
+        assert_eq!(bit_phase_flip.y_error_rate(), 0.2);
+        assert_eq!(bit_phase_flip.z_error_rate(), 0.0);
+    }
+
+    #[test]
+    fn test_single_pauli_channel_only_given_pauli() {
+        for error_vector in SinglePauliChannel::new(20, 0.3).sample_batch_with_seed(50, 1) {
+            assert!(error_vector.z_part().not_any());
+        }
+        for error_vector in SinglePauliChannel::phase_flip(20, 0.3).sample_batch_with_seed(50, 1) {
+            assert!(error_vector.x_part().not_any());
+        }
+        for error_vector in
+            SinglePauliChannel::bit_phase_flip(20, 0.3).sample_batch_with_seed(50, 1)
+        {
+            assert_eq!(error_vector.x_part(), error_vector.z_part());
+        }
+    }
+}
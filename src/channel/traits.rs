@@ -1,9 +1,91 @@
 use crate::code::error_vector::ErrorVector;
+use rand::prelude::*;
+use rand::rngs::StdRng;
+use rayon::prelude::*;
 
+/// 並列サンプリングで1つの乱数生成器が担当するサンプル数
+const PARALLEL_SAMPLE_CHUNK_SIZE: usize = 1024;
+
+/// 誤りをサンプリングするチャネル
+/// サンプリングで実装が必須なのは`sample_with_rng`だけで、`sample`と`sample_batch`は
+/// デフォルトではスレッドローカルな乱数生成器を使ってそれを呼び出す
 pub trait ErrorChannel {
-    fn sample(&self) -> ErrorVector;
-    fn sample_batch(&self, num_samples: usize) -> Vec<ErrorVector>;
+    /// 与えられた乱数生成器を使って誤りをサンプリングする
+    fn sample_with_rng(&self, rng: &mut dyn RngCore) -> ErrorVector;
+
+    fn sample(&self) -> ErrorVector {
+        self.sample_with_rng(&mut rand::rng())
+    }
+
+    fn sample_batch(&self, num_samples: usize) -> Vec<ErrorVector> {
+        (0..num_samples).map(|_| self.sample()).collect()
+    }
+
+    fn num_qubits(&self) -> usize;
     fn x_error_rate(&self) -> f64;
     fn y_error_rate(&self) -> f64;
     fn z_error_rate(&self) -> f64;
+
+    /// 各量子ビットに何らかの誤り(X, Y, Z)が起こる確率を返す
+    /// デフォルトでは全量子ビットで x_error_rate + y_error_rate + z_error_rate とする
+    fn marginal_probabilities(&self) -> Vec<f64> {
+        let p = self.x_error_rate() + self.y_error_rate() + self.z_error_rate();
+        vec![p; self.num_qubits()]
+    }
+
+    /// 誤りが起こる量子ビット数（誤りの重み）の期待値を返す
+    fn expected_weight(&self) -> f64 {
+        self.marginal_probabilities().iter().sum()
+    }
+
+    /// シードを固定した`StdRng`で逐次的にサンプリングする
+    fn sample_batch_with_seed(&self, num_samples: usize, seed: u64) -> Vec<ErrorVector> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        (0..num_samples)
+            .map(|_| self.sample_with_rng(&mut rng))
+            .collect()
+    }
+
+    /// rayonを使って並列にサンプリングする
+    /// ベースシードはスレッドローカルな乱数生成器から選ぶ
+    fn sample_batch_parallel(&self, num_samples: usize) -> Vec<ErrorVector>
+    where
+        Self: Sync,
+    {
+        let seed: u64 = rand::rng().random();
+        self.sample_batch_parallel_with_seed(num_samples, seed)
+    }
+
+    /// rayonを使って並列にサンプリングする
+    /// サンプルを一定数ごとのチャンクに分け、チャンクごとにベースシードから決まる`StdRng`を使うので、
+    /// スレッド数やスケジューリングによらず同じシードからは同じ結果が得られる
+    fn sample_batch_parallel_with_seed(&self, num_samples: usize, seed: u64) -> Vec<ErrorVector>
+    where
+        Self: Sync,
+    {
+        let num_chunks = num_samples.div_ceil(PARALLEL_SAMPLE_CHUNK_SIZE);
+        (0..num_chunks)
+            .into_par_iter()
+            .flat_map_iter(|chunk_idx| {
+                let chunk_seed = seed ^ (chunk_idx as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+                let mut rng = StdRng::seed_from_u64(chunk_seed);
+                let start = chunk_idx * PARALLEL_SAMPLE_CHUNK_SIZE;
+                let end = (start + PARALLEL_SAMPLE_CHUNK_SIZE).min(num_samples);
+                (start..end)
+                    .map(|_| self.sample_with_rng(&mut rng))
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+}
+
+/// 誤りと一緒にどの量子ビットが消失したか（消失マスク）を返すチャネル
+pub trait ErasureErrorChannel: ErrorChannel {
+    fn sample_with_erasure(&self) -> (ErrorVector, Vec<bool>);
+
+    fn sample_batch_with_erasure(&self, num_samples: usize) -> Vec<(ErrorVector, Vec<bool>)> {
+        (0..num_samples)
+            .map(|_| self.sample_with_erasure())
+            .collect()
+    }
 }
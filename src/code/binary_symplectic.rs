@@ -1,3 +1,4 @@
+use crate::Word;
 use crate::math::bit_linear_algebra::*;
 use bitvec::prelude::*;
 
@@ -11,20 +12,21 @@ use bitvec::prelude::*;
 /// ```rust
 /// use bitvec::prelude::*;
 /// use qldpc_sim::code::binary_symplectic::BinarySymplecticVector;
+/// use qldpc_sim::util::u8_to_bitvec;
 ///
 /// let bsv = BinarySymplecticVector::new(
-///    bitvec![u64, Lsb0; 1, 0, 1],
-///   bitvec![u64, Lsb0; 0, 1, 1],
+///    u8_to_bitvec(&[1, 0, 1]),
+///   u8_to_bitvec(&[0, 1, 1]),
 /// );
 /// ```
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct BinarySymplecticVector {
-    x_part: BitVec<u64, Lsb0>,
-    z_part: BitVec<u64, Lsb0>,
+    x_part: BitVec<Word, Lsb0>,
+    z_part: BitVec<Word, Lsb0>,
 }
 
 impl BinarySymplecticVector {
-    pub fn new(x_part: BitVec<u64, Lsb0>, z_part: BitVec<u64, Lsb0>) -> Self {
+    pub fn new(x_part: BitVec<Word, Lsb0>, z_part: BitVec<Word, Lsb0>) -> Self {
         assert_eq!(
             z_part.len(),
             x_part.len(),
@@ -35,11 +37,11 @@ impl BinarySymplecticVector {
         Self { z_part, x_part }
     }
 
-    pub fn x_part(&self) -> &BitVec<u64, Lsb0> {
+    pub fn x_part(&self) -> &BitVec<Word, Lsb0> {
         &self.x_part
     }
 
-    pub fn z_part(&self) -> &BitVec<u64, Lsb0> {
+    pub fn z_part(&self) -> &BitVec<Word, Lsb0> {
         &self.z_part
     }
 
@@ -53,14 +55,15 @@ impl BinarySymplecticVector {
     /// ```
     /// use bitvec::prelude::*;
     /// use qldpc_sim::code::binary_symplectic::BinarySymplecticVector;
+    /// use qldpc_sim::util::u8_to_bitvec;
     ///
     /// let v1 = BinarySymplecticVector::new(
-    ///     bitvec![u64, Lsb0; 1, 0, 1],
-    ///     bitvec![u64, Lsb0; 0, 1, 1],
+    ///     u8_to_bitvec(&[1, 0, 1]),
+    ///     u8_to_bitvec(&[0, 1, 1]),
     /// );
     /// let v2 = BinarySymplecticVector::new(
-    ///     bitvec![u64, Lsb0; 0, 1, 1],
-    ///     bitvec![u64, Lsb0; 1, 0, 1],
+    ///     u8_to_bitvec(&[0, 1, 1]),
+    ///     u8_to_bitvec(&[1, 0, 1]),
     /// );
     /// let result = v1.symplectic_product(&v2);
     /// assert_eq!(result, false);
@@ -86,17 +89,15 @@ impl BinarySymplecticVector {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::util::u8_to_bitvec;
 
     #[test]
     fn test_symplectic_product() {
-        let v1 =
-            BinarySymplecticVector::new(bitvec![u64, Lsb0; 1, 0, 1], bitvec![u64, Lsb0; 0, 1, 1]);
-        let v2 =
-            BinarySymplecticVector::new(bitvec![u64, Lsb0; 0, 1, 1], bitvec![u64, Lsb0; 1, 0, 1]);
+        let v1 = BinarySymplecticVector::new(u8_to_bitvec(&[1, 0, 1]), u8_to_bitvec(&[0, 1, 1]));
+        let v2 = BinarySymplecticVector::new(u8_to_bitvec(&[0, 1, 1]), u8_to_bitvec(&[1, 0, 1]));
         assert!(!v1.symplectic_product(&v2));
 
-        let v3 =
-            BinarySymplecticVector::new(bitvec![u64, Lsb0; 0, 1, 1], bitvec![u64, Lsb0; 1, 1, 0]);
+        let v3 = BinarySymplecticVector::new(u8_to_bitvec(&[0, 1, 1]), u8_to_bitvec(&[1, 1, 0]));
         assert!(v1.symplectic_product(&v3));
     }
 }
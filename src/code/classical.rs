@@ -0,0 +1,80 @@
+use crate::Word;
+use crate::math::sparse_matrix::BinarySparseMatrix;
+use bitvec::prelude::*;
+
+/// 古典線形符号を表す構造体
+/// パリティチェック行列を1つだけ持ち、古典LDPC符号の研究にも使えるようにする
+///
+/// # Examples
+/// ```rust
+/// use bitvec::prelude::*;
+/// use qldpc_sim::code::classical::ClassicalCode;
+/// use qldpc_sim::math::sparse_matrix::BinarySparseMatrix;
+/// use qldpc_sim::util::u8_to_bitvec;
+///
+/// let pcm = BinarySparseMatrix::from_row_adj(2, 3, vec![vec![0, 1], vec![1, 2]]);
+/// let repetition_code = ClassicalCode::new("Repetition", pcm);
+/// assert_eq!(repetition_code.n(), 3);
+/// assert_eq!(repetition_code.k(), 1);
+///
+/// let syndrome = repetition_code.syndrome(&u8_to_bitvec(&[1, 0, 0]));
+/// assert_eq!(syndrome, u8_to_bitvec(&[1, 0]));
+/// ```
+#[derive(Debug, Clone)]
+pub struct ClassicalCode {
+    code_name: String,
+    pcm: BinarySparseMatrix,
+}
+
+impl ClassicalCode {
+    pub fn new(code_name: &str, pcm: BinarySparseMatrix) -> Self {
+        Self {
+            code_name: code_name.to_string(),
+            pcm,
+        }
+    }
+
+    pub fn code_name(&self) -> &str {
+        &self.code_name
+    }
+
+    pub fn pcm(&self) -> &BinarySparseMatrix {
+        &self.pcm
+    }
+
+    pub fn n(&self) -> usize {
+        self.pcm.cols()
+    }
+
+    pub fn k(&self) -> usize {
+        self.n() - self.pcm.rank()
+    }
+
+    pub fn num_checks(&self) -> usize {
+        self.pcm.rows()
+    }
+
+    /// 誤りベクトルに対するシンドロームを計算する
+    pub fn syndrome(&self, error: &BitVec<Word, Lsb0>) -> BitVec<Word, Lsb0> {
+        &self.pcm * error
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classical_code_new() {
+        let pcm = BinarySparseMatrix::from_row_adj(
+            3,
+            7,
+            vec![vec![0, 1, 2, 4], vec![1, 2, 3, 5], vec![0, 1, 3, 6]],
+        );
+        let hamming_code = ClassicalCode::new("Hamming", pcm);
+        assert_eq!(hamming_code.n(), 7);
+        assert_eq!(hamming_code.k(), 4);
+        assert_eq!(hamming_code.num_checks(), 3);
+        assert_eq!(hamming_code.code_name(), "Hamming");
+    }
+}
@@ -0,0 +1,106 @@
+use crate::code::css_code::CssCode;
+use crate::math::sparse_matrix::BinarySparseMatrix;
+use std::collections::HashMap;
+
+/// 三角形の6.6.6カラー符号（蜂の巣格子上のカラー符号）を構成する
+/// 三角格子の点 (i, j) (i, j >= 0, i + j <= 3(d - 1) / 2) を (i + 2j + 1) mod 3 で3色に塗り分け、
+/// 色0の点を面（六角形）の中心、それ以外の点を量子ビットとする
+/// 各面は隣接する（最大6個の）量子ビットに作用し、H_XとH_Zは同じ台を持つ
+/// 符号のパラメータは [[(3d^2 + 1) / 4, 1, d]] となる
+///
+/// # Examples
+/// ```rust
+/// use qldpc_sim::code::color_code::color_code_666;
+/// use qldpc_sim::code::traits::QuantumCode;
+///
+/// // d = 3 はSteane符号と同じ[[7, 1, 3]]符号になる
+/// let code = color_code_666(3);
+/// assert_eq!(code.n(), 7);
+/// assert_eq!(code.k(), 1);
+/// ```
+pub fn color_code_666(distance: usize) -> CssCode {
+    assert!(
+        distance >= 3 && !distance.is_multiple_of(2),
+        "符号距離は3以上の奇数である必要があります: {}",
+        distance
+    );
+
+    let side = 3 * (distance - 1) / 2;
+    let is_face = |i: usize, j: usize| (i + 2 * j + 1).is_multiple_of(3);
+
+    let mut qubit_index = HashMap::new();
+    let mut face_centers = Vec::new();
+    for i in 0..=side {
+        for j in 0..=(side - i) {
+            if is_face(i, j) {
+                face_centers.push((i, j));
+            } else {
+                let idx = qubit_index.len();
+                qubit_index.insert((i, j), idx);
+            }
+        }
+    }
+
+    // 三角格子の6方向の隣接点
+    let neighbor_offsets: [(isize, isize); 6] =
+        [(1, 0), (0, 1), (-1, 1), (-1, 0), (0, -1), (1, -1)];
+    let row_adj: Vec<Vec<usize>> = face_centers
+        .iter()
+        .map(|&(i, j)| {
+            let mut support: Vec<usize> = neighbor_offsets
+                .iter()
+                .filter_map(|&(di, dj)| {
+                    let ni = i.checked_add_signed(di)?;
+                    let nj = j.checked_add_signed(dj)?;
+                    qubit_index.get(&(ni, nj)).copied()
+                })
+                .collect();
+            support.sort_unstable();
+            support
+        })
+        .collect();
+
+    let n = qubit_index.len();
+    let checks = BinarySparseMatrix::from_row_adj(row_adj.len(), n, row_adj);
+
+    let code_name = format!("ColorCode666(d={})", distance);
+    CssCode::from_parity_check_matrices(&code_name, checks.clone(), checks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::code::traits::QuantumCode;
+
+    #[test]
+    fn test_color_code_666_distance_3() {
+        let code = color_code_666(3);
+        assert_eq!(code.n(), 7);
+        assert_eq!(code.k(), 1);
+        assert_eq!(code.hx(), code.hz());
+        assert_eq!(code.hx().shape(), (3, 7));
+        assert!(code.hx().row_adj().iter().all(|face| face.len() == 4));
+        assert!(code.hx().is_orthogonal_to(code.hz()));
+    }
+
+    #[test]
+    fn test_color_code_666_larger_distances() {
+        for (distance, n) in [(5, 19), (7, 37)] {
+            let code = color_code_666(distance);
+            assert_eq!(code.n(), n);
+            assert_eq!(code.k(), 1);
+            assert!(
+                code.hx()
+                    .row_adj()
+                    .iter()
+                    .all(|face| face.len() == 4 || face.len() == 6)
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_color_code_666_even_distance() {
+        color_code_666(4);
+    }
+}
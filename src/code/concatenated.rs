@@ -0,0 +1,138 @@
+use crate::code::css_code::CssCode;
+use crate::code::traits::QuantumCode;
+use crate::math::sparse_matrix::BinarySparseMatrix;
+
+/// 2つのCSS符号を連接した符号を構成する
+/// 外符号の各量子ビットを内符号のブロック（論理量子ビット1個）で置き換える
+/// 連接符号の検査行列は次の2種類からなる
+/// - 各ブロックに作用する内符号のH_Z, H_X
+/// - 外符号のH_Z, H_Xの各行を、ブロックごとの論理演算子L_Z, L_Xに持ち上げたもの
+///
+/// # Examples
+/// ```rust
+/// use qldpc_sim::code::concatenated::concatenate;
+/// use qldpc_sim::code::css_code::CssCode;
+/// use qldpc_sim::code::traits::QuantumCode;
+/// use qldpc_sim::math::sparse_matrix::BinarySparseMatrix;
+///
+/// let checks = BinarySparseMatrix::from_row_adj(2, 3, vec![vec![0, 1], vec![1, 2]]);
+/// let empty = BinarySparseMatrix::from_row_adj(0, 3, vec![]);
+/// let phase_flip = CssCode::from_parity_check_matrices("PhaseFlip", empty.clone(), checks.clone());
+/// let bit_flip = CssCode::from_parity_check_matrices("BitFlip", checks, empty);
+///
+/// // 位相反転符号と反転符号の連接はShor符号になる
+/// let shor = concatenate(&phase_flip, &bit_flip);
+/// assert_eq!(shor.n(), 9);
+/// assert_eq!(shor.k(), 1);
+/// ```
+pub fn concatenate(outer: &CssCode, inner: &CssCode) -> CssCode {
+    assert_eq!(
+        inner.k(),
+        1,
+        "内符号の論理量子ビット数は1である必要があります"
+    );
+
+    let n_outer = outer.n();
+    let n_inner = inner.n();
+    let n = n_outer * n_inner;
+
+    let inner_lx = inner.lx();
+    let inner_lz = inner.lz();
+    let inner_lx_support = &inner_lx.row_adj()[0];
+    let inner_lz_support = &inner_lz.row_adj()[0];
+
+    let hz_row_adj =
+        concatenated_row_adj(inner.hz(), outer.hz(), inner_lz_support, n_outer, n_inner);
+    let hx_row_adj =
+        concatenated_row_adj(inner.hx(), outer.hx(), inner_lx_support, n_outer, n_inner);
+
+    let hz = BinarySparseMatrix::from_row_adj(hz_row_adj.len(), n, hz_row_adj);
+    let hx = BinarySparseMatrix::from_row_adj(hx_row_adj.len(), n, hx_row_adj);
+
+    let code_name = format!("Concatenated({}, {})", outer.code_name(), inner.code_name());
+    CssCode::from_parity_check_matrices(&code_name, hz, hx)
+}
+
+/// 内符号の検査行列を各ブロックに並べ、外符号の検査行列を論理演算子で持ち上げた行を返す
+fn concatenated_row_adj(
+    inner_checks: &BinarySparseMatrix,
+    outer_checks: &BinarySparseMatrix,
+    inner_logical_support: &[usize],
+    n_outer: usize,
+    n_inner: usize,
+) -> Vec<Vec<usize>> {
+    let mut row_adj = Vec::new();
+
+    for block_idx in 0..n_outer {
+        let offset = block_idx * n_inner;
+        for neighbors in inner_checks.row_adj() {
+            row_adj.push(neighbors.iter().map(|&q| offset + q).collect());
+        }
+    }
+
+    for neighbors in outer_checks.row_adj() {
+        let mut lifted: Vec<usize> = neighbors
+            .iter()
+            .flat_map(|&block_idx| {
+                inner_logical_support
+                    .iter()
+                    .map(move |&q| block_idx * n_inner + q)
+            })
+            .collect();
+        lifted.sort_unstable();
+        row_adj.push(lifted);
+    }
+
+    row_adj
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::code::named_codes::shor_code;
+
+    fn repetition_checks() -> BinarySparseMatrix {
+        BinarySparseMatrix::from_row_adj(2, 3, vec![vec![0, 1], vec![1, 2]])
+    }
+
+    fn empty_checks() -> BinarySparseMatrix {
+        BinarySparseMatrix::from_row_adj(0, 3, vec![])
+    }
+
+    #[test]
+    fn test_concatenate_shor_code() {
+        let phase_flip =
+            CssCode::from_parity_check_matrices("PhaseFlip", empty_checks(), repetition_checks());
+        let bit_flip =
+            CssCode::from_parity_check_matrices("BitFlip", repetition_checks(), empty_checks());
+
+        let shor = concatenate(&phase_flip, &bit_flip);
+        assert_eq!(shor.n(), 9);
+        assert_eq!(shor.k(), 1);
+        assert_eq!(shor.hz().shape(), (6, 9));
+        assert_eq!(shor.hx(), shor_code().hx());
+        assert!(shor.hz().row_space_eq(shor_code().hz()));
+    }
+
+    #[test]
+    fn test_concatenate_shor_with_itself() {
+        let phase_flip =
+            CssCode::from_parity_check_matrices("PhaseFlip", empty_checks(), repetition_checks());
+        let bit_flip =
+            CssCode::from_parity_check_matrices("BitFlip", repetition_checks(), empty_checks());
+        let shor = concatenate(&phase_flip, &bit_flip);
+
+        let code = concatenate(&shor, &shor);
+        assert_eq!(code.n(), 81);
+        assert_eq!(code.k(), 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_concatenate_inner_multiple_logicals() {
+        let outer =
+            CssCode::from_parity_check_matrices("PhaseFlip", empty_checks(), repetition_checks());
+        let inner = crate::code::generalized_bicycle::generalized_bicycle(5, &[0, 1], &[0, 1]);
+        concatenate(&outer, &inner);
+    }
+}
@@ -1,32 +1,127 @@
+use crate::Word;
 use crate::code::error_vector::{ErrorVector, Syndrome};
 use crate::code::traits::QuantumCode;
+use crate::math::bit_linear_algebra::{BinaryDenseMatrix, rank};
 use crate::math::sparse_matrix::BinarySparseMatrix;
+use bitvec::prelude::*;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
 
+/// CSS符号を表す構造体
+/// X型スタビライザーの検査行列H_XとZ型スタビライザーの検査行列H_Zを持つ
+///
+/// # Examples
+/// ```rust
+/// use qldpc_sim::prelude::*;
+///
+/// let hz = BinarySparseMatrix::from_row_adj(2, 3, vec![vec![0, 1], vec![1, 2]]);
+/// let hx = BinarySparseMatrix::zeros(0, 3);
+/// let code = CssCode::from_parity_check_matrices("BitFlip", hz, hx);
+/// assert_eq!(code.n(), 3);
+/// assert_eq!(code.k(), 1);
+/// ```
 pub struct CssCode {
     code_name: String,
     hz: BinarySparseMatrix,
     hx: BinarySparseMatrix,
+    /// M_X H_X = 0 を満たすメタチェック行列（Xシンドロームの整合性を検査する）
+    mx: Option<BinarySparseMatrix>,
+    /// M_Z H_Z = 0 を満たすメタチェック行列（Zシンドロームの整合性を検査する）
+    mz: Option<BinarySparseMatrix>,
+    /// 論理量子ビット数 k = n - rank(H_Z) - rank(H_X)
+    k: usize,
 }
 
 impl CssCode {
     pub fn new(code_name: String, hz: BinarySparseMatrix, hx: BinarySparseMatrix) -> Self {
-        Self { code_name, hz, hx }
+        if let Err(error) = check_same_qubit_count(&hz, &hx) {
+            panic!("{}", error);
+        }
+        let k = logical_qubit_count(&hz, &hx);
+        assert!(
+            k >= 0,
+            "H_ZとH_Xの階数の和が量子ビット数({})を超えています: k = {}",
+            hz.cols(),
+            k
+        );
+        Self {
+            code_name,
+            hz,
+            hx,
+            mx: None,
+            mz: None,
+            k: k as usize,
+        }
     }
 
+    /// 検査行列からCSS符号を構成する
+    /// 不正な検査行列ではパニックする。多数の符号を扱う場合は`try_from_parity_check_matrices`を使う
+    #[must_use]
     pub fn from_parity_check_matrices(
         code_name: &str,
         hz: BinarySparseMatrix,
         hx: BinarySparseMatrix,
     ) -> Self {
-        let val = &hx * &hz.transpose();
-        assert_eq!(
-            val,
-            BinarySparseMatrix::zeros(hx.rows(), hz.rows()),
-            "H_ZとH_Xが直交していません"
-        );
-        let k = hz.cols() - hz.rank() - hx.rank();
-        assert!(k > 0, "論理量子ビットが存在しません: k = {}", k);
-        CssCode::new(code_name.to_string(), hz, hx)
+        Self::try_from_parity_check_matrices(code_name, hz, hx)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// 検査行列からCSS符号を構成する
+    /// 列数の不一致、H_ZとH_Xが直交しない場合、論理量子ビットが存在しない場合はエラーを返す
+    ///
+    /// # Examples
+    /// ```rust
+    /// use qldpc_sim::prelude::*;
+    ///
+    /// let hz = BinarySparseMatrix::from_row_adj(1, 2, vec![vec![0, 1]]);
+    /// let hx = BinarySparseMatrix::from_row_adj(1, 2, vec![vec![0]]);
+    /// let result = CssCode::try_from_parity_check_matrices("Bad", hz, hx);
+    /// assert_eq!(result.err(), Some(CssCodeError::NonOrthogonal));
+    /// ```
+    pub fn try_from_parity_check_matrices(
+        code_name: &str,
+        hz: BinarySparseMatrix,
+        hx: BinarySparseMatrix,
+    ) -> Result<Self, CssCodeError> {
+        check_same_qubit_count(&hz, &hx)?;
+        if !hx.is_orthogonal_to(&hz) {
+            return Err(CssCodeError::NonOrthogonal);
+        }
+        if logical_qubit_count(&hz, &hx) <= 0 {
+            return Err(CssCodeError::NoLogicalQubits {
+                rank_sum: hz.rank() + hx.rank(),
+                num_qubits: hz.cols(),
+            });
+        }
+        Ok(CssCode::new(code_name.to_string(), hz, hx))
+    }
+
+    /// H_XとH_Zをalist形式で`dir/hx.alist`と`dir/hz.alist`に書き出す
+    /// Pythonの`ldpc`パッケージなどの外部ツールと符号をやり取りするのに使う
+    pub fn write_alist_pair(&self, dir: &Path) -> io::Result<()> {
+        fs::write(dir.join("hx.alist"), self.hx.to_alist())?;
+        fs::write(dir.join("hz.alist"), self.hz.to_alist())
+    }
+
+    /// `dir/hx.alist`と`dir/hz.alist`を読み込み、`try_from_parity_check_matrices`で符号を構成する
+    /// alistが不正な場合や、行列が符号を構成しない場合は`io::ErrorKind::InvalidData`を返す
+    pub fn read_alist_pair(code_name: &str, dir: &Path) -> io::Result<Self> {
+        let read_matrix = |file_name: &str| {
+            let alist = fs::read_to_string(dir.join(file_name))?;
+            BinarySparseMatrix::try_from_alist(&alist).map_err(|message| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("{}: {}", file_name, message),
+                )
+            })
+        };
+        let hx = read_matrix("hx.alist")?;
+        let hz = read_matrix("hz.alist")?;
+        CssCode::try_from_parity_check_matrices(code_name, hz, hx)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
     }
 
     pub fn hx(&self) -> &BinarySparseMatrix {
@@ -37,12 +132,76 @@ impl CssCode {
         &self.hz
     }
 
+    /// 冗長なスタビライザーの間の線形関係を表すメタチェック行列を設定する
+    /// M_X の列はH_Xの行に、M_Z の列はH_Zの行に対応し、M_X H_X = 0, M_Z H_Z = 0 を満たす必要がある
+    /// メタチェックを持たない側には行数0の行列を渡す
+    pub fn with_metachecks(mut self, mx: BinarySparseMatrix, mz: BinarySparseMatrix) -> Self {
+        assert_eq!(
+            mx.cols(),
+            self.hx.rows(),
+            "M_Xの列数({})がH_Xの行数({})と一致しません",
+            mx.cols(),
+            self.hx.rows()
+        );
+        assert_eq!(
+            mz.cols(),
+            self.hz.rows(),
+            "M_Zの列数({})がH_Zの行数({})と一致しません",
+            mz.cols(),
+            self.hz.rows()
+        );
+        assert!(
+            (&mx * &self.hx).row_adj().iter().all(|row| row.is_empty()),
+            "M_X H_X が0になりません"
+        );
+        assert!(
+            (&mz * &self.hz).row_adj().iter().all(|row| row.is_empty()),
+            "M_Z H_Z が0になりません"
+        );
+        self.mx = Some(mx);
+        self.mz = Some(mz);
+        self
+    }
+
+    pub fn mx(&self) -> Option<&BinarySparseMatrix> {
+        self.mx.as_ref()
+    }
+
+    pub fn mz(&self) -> Option<&BinarySparseMatrix> {
+        self.mz.as_ref()
+    }
+
+    /// X型の論理演算子を行に持つ行列を返す
+    /// i行目は`lz`のi行目とだけ反可換になるように選ばれる
     pub fn lx(&self) -> BinarySparseMatrix {
-        todo!("Implement L_X generation")
+        self.logical_operators().0
     }
 
+    /// Z型の論理演算子を行に持つ行列を返す
+    /// i行目は`lx`のi行目とだけ反可換になるように選ばれる
     pub fn lz(&self) -> BinarySparseMatrix {
-        todo!("Implement L_Z generation")
+        self.logical_operators().1
+    }
+
+    /// L_X L_Z^T = I を満たす論理演算子の組 (L_X, L_Z) を計算する
+    /// L_X は ker(H_Z) のうち H_X の行空間に含まれない部分から、
+    /// L_Z は ker(H_X) のうち H_Z の行空間に含まれない部分から選ぶ
+    fn logical_operators(&self) -> (BinarySparseMatrix, BinarySparseMatrix) {
+        let n = self.num_qubits();
+        let lx_candidates = complement_basis(&kernel_basis(&self.hz), &self.hx);
+        let lz_candidates = complement_basis(&kernel_basis(&self.hx), &self.hz);
+
+        // M = L_X' L_Z'^T は正則なので、L_X = M^{-1} L_X' とすれば L_X L_Z^T = I となる
+        let lx_dense = BinaryDenseMatrix::new(lx_candidates);
+        let lz_dense = BinaryDenseMatrix::new(lz_candidates);
+        let pairing = &lx_dense * &lz_dense.transpose();
+        let pairing_inverse = pairing.inverse().expect("論理演算子の対を構成できません");
+        let lx_dense = &pairing_inverse * &lx_dense;
+
+        (
+            bit_rows_to_sparse(lx_dense.get_data(), n),
+            bit_rows_to_sparse(lz_dense.get_data(), n),
+        )
     }
 
     pub fn num_stabilizers(&self) -> usize {
@@ -62,6 +221,74 @@ impl CssCode {
         let syndrome_x = &self.hx * z_part;
         Syndrome::new(syndrome_z, syndrome_x)
     }
+
+    /// 誤り`error`に復元操作`recovery`を施した結果が論理誤りになるかどうかを返す
+    /// 残差がスタビライザー群に含まれない（シンドロームが非自明か、論理演算子と反可換である）場合に`true`
+    /// 呼び出しのたびに論理演算子を計算し直すので、何度も判定する場合は`lx`と`lz`を先に求めて
+    /// `is_logical_error_with`を使う
+    pub fn is_logical_error(&self, error: &ErrorVector, recovery: &ErrorVector) -> bool {
+        self.is_logical_error_with(&self.lx(), &self.lz(), error, recovery)
+    }
+
+    /// `is_logical_error`と同じ判定を、事前に計算した論理演算子`lx`, `lz`を使って行う
+    pub fn is_logical_error_with(
+        &self,
+        lx: &BinarySparseMatrix,
+        lz: &BinarySparseMatrix,
+        error: &ErrorVector,
+        recovery: &ErrorVector,
+    ) -> bool {
+        self.logical_flips_with(lx, lz, error, recovery)
+            .is_none_or(|(x_logical, z_logical)| x_logical || z_logical)
+    }
+
+    /// 誤り`error`に復元操作`recovery`を施した残差が、論理X誤りと論理Z誤りを起こすかどうかを返す
+    /// 残差のシンドロームが自明でない場合は`None`、自明な場合は
+    /// (残差のX部分が`lz`と反可換か, 残差のZ部分が`lx`と反可換か) を返す
+    pub fn logical_flips_with(
+        &self,
+        lx: &BinarySparseMatrix,
+        lz: &BinarySparseMatrix,
+        error: &ErrorVector,
+        recovery: &ErrorVector,
+    ) -> Option<(bool, bool)> {
+        let residual = error ^ recovery;
+        if !self.syndrome(&residual).is_trivial() {
+            return None;
+        }
+        Some((
+            (lz * residual.x_part()).any(),
+            (lx * residual.z_part()).any(),
+        ))
+    }
+
+    /// 最小重みの論理演算子を含む論理コセットについて、重みごとの代表元の数を返す
+    /// X型の論理演算子は ker(H_Z) の元を、Z型は ker(H_X) の元を全て列挙し、
+    /// 論理演算子との反可換性で自明でないコセットに分類する
+    /// 戻り値のw番目は重みwの代表元の数で、長さは n + 1。同じ距離の符号を比べるのに使う
+    /// ker(H_Z) と ker(H_X) の全ての元を列挙するので、小さな符号専用
+    ///
+    /// # Examples
+    /// ```rust
+    /// use qldpc_sim::code::named_codes::steane_code;
+    ///
+    /// let enumerator = steane_code().logical_weight_enumerator();
+    /// assert_eq!(enumerator, vec![0, 0, 0, 7, 0, 0, 0, 1]);
+    /// ```
+    pub fn logical_weight_enumerator(&self) -> Vec<usize> {
+        let n = self.num_qubits();
+        let x_cosets = coset_weight_distributions(&kernel_basis(&self.hz), &self.lz(), n);
+        let z_cosets = coset_weight_distributions(&kernel_basis(&self.hx), &self.lx(), n);
+
+        // 最も軽い代表元を持つコセットを選ぶ。同じ重みならX型を優先する
+        let min_weight =
+            |distribution: &Vec<usize>| distribution.iter().position(|&count| count > 0);
+        x_cosets
+            .into_values()
+            .chain(z_cosets.into_values())
+            .min_by_key(|distribution| min_weight(distribution))
+            .unwrap_or_else(|| vec![0; n + 1])
+    }
 }
 
 impl QuantumCode for CssCode {
@@ -74,33 +301,162 @@ impl QuantumCode for CssCode {
     }
 
     fn k(&self) -> usize {
-        let n = self.n();
-        let rank_hz = self.hz.rank();
-        let rank_hx = self.hx.rank();
-        n - rank_hz - rank_hx
+        self.k
     }
 }
 
+/// 符号名とパラメータを "ShorCode [[9,1]]" の形式で表示する
+impl fmt::Display for CssCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} [[{},{}]]", self.code_name, self.n(), self.k())
+    }
+}
+
+/// 検査行列からCSS符号を構成できなかった理由
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CssCodeError {
+    /// H_XとH_Zの列数（作用する量子ビット数）が異なる
+    DimensionMismatch { hx_cols: usize, hz_cols: usize },
+    /// H_X H_Z^T が0にならない
+    NonOrthogonal,
+    /// rank(H_Z) + rank(H_X) が量子ビット数以上で、論理量子ビットが存在しない
+    NoLogicalQubits { rank_sum: usize, num_qubits: usize },
+}
+
+impl fmt::Display for CssCodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CssCodeError::DimensionMismatch { hx_cols, hz_cols } => write!(
+                f,
+                "X型とZ型のチェックが作用する量子ビット数が異なります: H_Xの列数({}) != H_Zの列数({})",
+                hx_cols, hz_cols
+            ),
+            CssCodeError::NonOrthogonal => write!(f, "H_ZとH_Xが直交していません"),
+            CssCodeError::NoLogicalQubits {
+                rank_sum,
+                num_qubits,
+            } => write!(
+                f,
+                "論理量子ビットが存在しません: rank(H_Z) + rank(H_X) = {} が量子ビット数 {} 以上です",
+                rank_sum, num_qubits
+            ),
+        }
+    }
+}
+
+impl std::error::Error for CssCodeError {}
+
+/// H_XとH_Zの列数（作用する量子ビット数）が一致することを確認する
+/// 行列積の次元不一致で落ちる前に、原因が分かるエラーを返す
+fn check_same_qubit_count(
+    hz: &BinarySparseMatrix,
+    hx: &BinarySparseMatrix,
+) -> Result<(), CssCodeError> {
+    if hx.cols() != hz.cols() {
+        return Err(CssCodeError::DimensionMismatch {
+            hx_cols: hx.cols(),
+            hz_cols: hz.cols(),
+        });
+    }
+    Ok(())
+}
+
+/// k = n - rank(H_Z) - rank(H_X) を符号付き整数で計算する
+/// 不正な検査行列では負になりうるため、呼び出し側で符号を確認する
+fn logical_qubit_count(hz: &BinarySparseMatrix, hx: &BinarySparseMatrix) -> i64 {
+    hz.cols() as i64 - hz.rank() as i64 - hx.rank() as i64
+}
+
+fn sparse_to_bit_rows(matrix: &BinarySparseMatrix) -> Vec<BitVec<Word, Lsb0>> {
+    matrix
+        .row_adj()
+        .iter()
+        .map(|neighbors| {
+            let mut row = bitvec![Word, Lsb0; 0; matrix.cols()];
+            for &col_idx in neighbors {
+                row.set(col_idx, true);
+            }
+            row
+        })
+        .collect()
+}
+
+fn bit_rows_to_sparse(rows: &[BitVec<Word, Lsb0>], n_cols: usize) -> BinarySparseMatrix {
+    let row_adj = rows.iter().map(|row| row.iter_ones().collect()).collect();
+    BinarySparseMatrix::from_row_adj(rows.len(), n_cols, row_adj)
+}
+
+/// 論理コセットの重み分布を計算するときに列挙できる ker(H) の次元の上限
+const MAX_ENUMERATED_KERNEL_DIMENSION: usize = 24;
+
+/// 基底の張る空間の元を全て列挙し、論理演算子`logicals`との反可換性のパターンごとに重み分布を数える
+/// 全ての論理演算子と可換な元（スタビライザー）は除く
+fn coset_weight_distributions(
+    basis: &[BitVec<Word, Lsb0>],
+    logicals: &BinarySparseMatrix,
+    n: usize,
+) -> BTreeMap<Vec<bool>, Vec<usize>> {
+    assert!(
+        basis.len() <= MAX_ENUMERATED_KERNEL_DIMENSION,
+        "列挙する空間の次元({})が大きすぎます: 上限は{}",
+        basis.len(),
+        MAX_ENUMERATED_KERNEL_DIMENSION
+    );
+    let mut distributions = BTreeMap::new();
+    let mut element = bitvec![Word, Lsb0; 0; n];
+    // グレイコードの順に、1つの基底ベクトルを足すだけで次の元に進む
+    for i in 1..(1usize << basis.len()) {
+        element ^= &basis[i.trailing_zeros() as usize];
+        let label = logicals * &element;
+        if label.not_any() {
+            continue;
+        }
+        let key: Vec<bool> = label.iter().map(|bit| *bit).collect();
+        distributions.entry(key).or_insert_with(|| vec![0; n + 1])[element.count_ones()] += 1;
+    }
+    distributions
+}
+
+/// H x = 0 を満たすベクトル全体の基底を返す
+fn kernel_basis(matrix: &BinarySparseMatrix) -> Vec<BitVec<Word, Lsb0>> {
+    BinaryDenseMatrix::from_rows(sparse_to_bit_rows(matrix), matrix.cols())
+        .nullspace()
+        .get_data()
+        .to_vec()
+}
+
+/// candidatesのうち、stabilizersの行空間と合わせて線形独立になるものを選ぶ
+fn complement_basis(
+    candidates: &[BitVec<Word, Lsb0>],
+    stabilizers: &BinarySparseMatrix,
+) -> Vec<BitVec<Word, Lsb0>> {
+    let mut span = sparse_to_bit_rows(stabilizers);
+    let mut current_rank = rank(&span);
+    let mut basis = Vec::new();
+
+    for candidate in candidates {
+        span.push(candidate.clone());
+        let new_rank = rank(&span);
+        if new_rank > current_rank {
+            current_rank = new_rank;
+            basis.push(candidate.clone());
+        } else {
+            span.pop();
+        }
+    }
+
+    basis
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::math::sparse_matrix::BinarySparseMatrix;
-    use bitvec::prelude::*;
+    use crate::code::named_codes::shor_code;
+    use crate::util::u8_to_bitvec;
 
     #[test]
     fn test_css_code_new() {
-        let hz_row_adj = vec![
-            vec![0, 1],
-            vec![1, 2],
-            vec![3, 4],
-            vec![4, 5],
-            vec![6, 7],
-            vec![7, 8],
-        ];
-        let hx_row_adj = vec![vec![0, 1, 2, 3, 4, 5], vec![3, 4, 5, 6, 7, 8]];
-        let hz = BinarySparseMatrix::from_row_adj(6, 9, hz_row_adj);
-        let hx = BinarySparseMatrix::from_row_adj(2, 9, hx_row_adj);
-        let css_code = CssCode::from_parity_check_matrices("TestCSS", hz, hx);
+        let css_code = shor_code();
         assert_eq!(css_code.num_qubits(), 9);
         assert_eq!(css_code.k(), 1);
         assert_eq!(css_code.num_stabilizers(), 8);
@@ -116,6 +472,49 @@ mod tests {
         let _css_code = CssCode::from_parity_check_matrices("NonOrthogonalCSS", hz, hx);
     }
 
+    #[test]
+    #[should_panic(expected = "X型とZ型のチェックが作用する量子ビット数が異なります")]
+    fn test_css_code_mismatched_columns() {
+        let hz = BinarySparseMatrix::from_row_adj(2, 3, vec![vec![0, 1], vec![1, 2]]);
+        let hx = BinarySparseMatrix::from_row_adj(1, 4, vec![vec![0, 1, 2, 3]]);
+        let _css_code = CssCode::from_parity_check_matrices("MismatchedCSS", hz, hx);
+    }
+
+    #[test]
+    fn test_css_code_try_from_errors() {
+        let hz = BinarySparseMatrix::from_row_adj(2, 3, vec![vec![0, 1], vec![1, 2]]);
+        let hx = BinarySparseMatrix::from_row_adj(1, 4, vec![vec![0, 1, 2, 3]]);
+        let result = CssCode::try_from_parity_check_matrices("MismatchedCSS", hz, hx);
+        assert_eq!(
+            result.err(),
+            Some(CssCodeError::DimensionMismatch {
+                hx_cols: 4,
+                hz_cols: 3
+            })
+        );
+
+        let hz = BinarySparseMatrix::from_row_adj(2, 4, vec![vec![0, 1], vec![1, 2]]);
+        let hx = BinarySparseMatrix::from_row_adj(2, 4, vec![vec![1, 2], vec![2, 3]]);
+        let result = CssCode::try_from_parity_check_matrices("NonOrthogonalCSS", hz, hx);
+        assert_eq!(result.err(), Some(CssCodeError::NonOrthogonal));
+
+        let hz = BinarySparseMatrix::from_row_adj(3, 4, vec![vec![0, 1], vec![1, 2], vec![2, 3]]);
+        let hx = BinarySparseMatrix::from_row_adj(1, 4, vec![vec![0, 1, 2, 3]]);
+        let result = CssCode::try_from_parity_check_matrices("NoLogicalQubitsCSS", hz, hx);
+        assert_eq!(
+            result.err(),
+            Some(CssCodeError::NoLogicalQubits {
+                rank_sum: 4,
+                num_qubits: 4
+            })
+        );
+
+        let hz = BinarySparseMatrix::from_row_adj(2, 3, vec![vec![0, 1], vec![1, 2]]);
+        let hx = BinarySparseMatrix::zeros(0, 3);
+        let code = CssCode::try_from_parity_check_matrices("BitFlip", hz, hx).unwrap();
+        assert_eq!(code.k(), 1);
+    }
+
     #[test]
     #[should_panic(expected = "論理量子ビットが存在しません")]
     fn test_css_code_no_logical_qubits() {
@@ -128,25 +527,179 @@ mod tests {
 
     #[test]
     fn test_css_code_syndrome() {
-        let hz_row_adj = vec![
-            vec![0, 1],
-            vec![1, 2],
-            vec![3, 4],
-            vec![4, 5],
-            vec![6, 7],
-            vec![7, 8],
-        ];
-        let hx_row_adj = vec![vec![0, 1, 2, 3, 4, 5], vec![3, 4, 5, 6, 7, 8]];
-        let hz = BinarySparseMatrix::from_row_adj(6, 9, hz_row_adj);
-        let hx = BinarySparseMatrix::from_row_adj(2, 9, hx_row_adj);
-        let css_code = CssCode::from_parity_check_matrices("TestCSS", hz, hx);
-        let error_z = bitvec![u64, Lsb0; 1, 0, 0, 0, 0, 0, 0, 0, 0];
-        let error_x = bitvec![u64, Lsb0; 0, 1, 0, 0, 0, 0, 0, 0, 0];
+        let css_code = shor_code();
+        let error_z = u8_to_bitvec(&[1, 0, 0, 0, 0, 0, 0, 0, 0]);
+        let error_x = u8_to_bitvec(&[0, 1, 0, 0, 0, 0, 0, 0, 0]);
         let error_vector = ErrorVector::new(error_x, error_z);
         let syndrome = css_code.syndrome(&error_vector);
-        let expected_z_syndrome = bitvec![u64, Lsb0; 1, 1, 0, 0, 0, 0];
-        let expected_x_syndrome = bitvec![u64, Lsb0; 1, 0];
+        let expected_z_syndrome = u8_to_bitvec(&[1, 1, 0, 0, 0, 0]);
+        let expected_x_syndrome = u8_to_bitvec(&[1, 0]);
         assert_eq!(syndrome.z_syndrome(), &expected_z_syndrome);
         assert_eq!(syndrome.x_syndrome(), &expected_x_syndrome);
     }
+
+    #[test]
+    fn test_css_code_logical_operators() {
+        let css_code = shor_code();
+
+        let lx = css_code.lx();
+        let lz = css_code.lz();
+        assert_eq!(lx.shape(), (css_code.k(), 9));
+        assert_eq!(lz.shape(), (css_code.k(), 9));
+
+        // 論理演算子はスタビライザーと可換
+        assert!(lx.is_orthogonal_to(css_code.hz()));
+        assert!(lz.is_orthogonal_to(css_code.hx()));
+        // L_X L_Z^T = I
+        assert_eq!(
+            &lx * &lz.transpose(),
+            BinarySparseMatrix::identity(css_code.k())
+        );
+    }
+
+    #[test]
+    fn test_css_code_display() {
+        let css_code = shor_code();
+
+        assert_eq!(css_code.code_name(), "ShorCode");
+        assert_eq!(css_code.to_string(), "ShorCode [[9,1]]");
+    }
+
+    #[test]
+    fn test_css_code_with_metachecks() {
+        // 4ビットの繰り返し符号に全ての2ビット検査を入れ、三角形の検査の和が0になることをメタチェックとする
+        let hz = BinarySparseMatrix::from_row_adj(
+            6,
+            4,
+            vec![
+                vec![0, 1],
+                vec![0, 2],
+                vec![0, 3],
+                vec![1, 2],
+                vec![1, 3],
+                vec![2, 3],
+            ],
+        );
+        let hx = BinarySparseMatrix::zeros(0, 4);
+        let mz = BinarySparseMatrix::from_row_adj(
+            4,
+            6,
+            vec![vec![0, 1, 3], vec![0, 2, 4], vec![1, 2, 5], vec![3, 4, 5]],
+        );
+        let mx = BinarySparseMatrix::zeros(0, 0);
+        let code = CssCode::from_parity_check_matrices("K4", hz, hx).with_metachecks(mx, mz);
+
+        assert_eq!(code.mz().unwrap().rows(), 4);
+        assert_eq!(code.mx().unwrap().rows(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "M_Z H_Z が0になりません")]
+    fn test_css_code_with_invalid_metachecks() {
+        let hz = BinarySparseMatrix::from_row_adj(2, 3, vec![vec![0, 1], vec![1, 2]]);
+        let hx = BinarySparseMatrix::zeros(0, 3);
+        let mz = BinarySparseMatrix::from_row_adj(1, 2, vec![vec![0]]);
+        let mx = BinarySparseMatrix::zeros(0, 0);
+        let _ = CssCode::from_parity_check_matrices("BitFlip", hz, hx).with_metachecks(mx, mz);
+    }
+
+    #[test]
+    #[should_panic(expected = "論理量子ビットが存在しません")]
+    fn test_css_code_overdetermined_checks() {
+        // H_Zだけで全ての量子ビットを固定してしまう
+        let hz_row_adj = vec![vec![0], vec![1], vec![2], vec![0, 1, 2]];
+        let hz = BinarySparseMatrix::from_row_adj(4, 3, hz_row_adj);
+        let hx = BinarySparseMatrix::zeros(0, 3);
+        let _css_code = CssCode::from_parity_check_matrices("OverdeterminedCSS", hz, hx);
+    }
+
+    #[test]
+    #[should_panic(expected = "H_ZとH_Xの階数の和が量子ビット数(2)を超えています")]
+    fn test_css_code_new_negative_k() {
+        let hz = BinarySparseMatrix::from_row_adj(2, 2, vec![vec![0], vec![1]]);
+        let hx = BinarySparseMatrix::from_row_adj(1, 2, vec![vec![0]]);
+        let _css_code = CssCode::new("NegativeK".to_string(), hz, hx);
+    }
+
+    #[test]
+    fn test_css_code_alist_pair_round_trip() {
+        let code = shor_code();
+
+        let dir = std::env::temp_dir().join(format!("qldpc_sim_alist_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        code.write_alist_pair(&dir).unwrap();
+        let restored = CssCode::read_alist_pair("ShorCode", &dir).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(restored.hx(), code.hx());
+        assert_eq!(restored.hz(), code.hz());
+        assert_eq!(restored.to_string(), "ShorCode [[9,1]]");
+    }
+
+    #[test]
+    fn test_css_code_read_alist_pair_invalid() {
+        let code = shor_code();
+        let dir =
+            std::env::temp_dir().join(format!("qldpc_sim_alist_invalid_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        code.write_alist_pair(&dir).unwrap();
+
+        // 途中で切れたalistはパニックせずエラーになる
+        fs::write(dir.join("hx.alist"), "9 2\n3 6\n1 1").unwrap();
+        let truncated = CssCode::read_alist_pair("ShorCode", &dir).err();
+
+        // 読めても直交しない行列の組はエラーになる
+        fs::write(dir.join("hx.alist"), code.hz().to_alist()).unwrap();
+        let non_orthogonal = CssCode::read_alist_pair("ShorCode", &dir).err();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(
+            truncated.map(|error| error.kind()),
+            Some(io::ErrorKind::InvalidData)
+        );
+        assert_eq!(
+            non_orthogonal.map(|error| error.kind()),
+            Some(io::ErrorKind::InvalidData)
+        );
+    }
+
+    #[test]
+    fn test_css_code_is_logical_error() {
+        let code = shor_code();
+
+        let error = ErrorVector::from_string("ZIIIIIIII");
+        // Z0 と Z1 はスタビライザー Z0 Z1 の分だけ異なるので等価
+        assert!(!code.is_logical_error(&error, &error));
+        assert!(!code.is_logical_error(&error, &ErrorVector::from_string("IZIIIIIII")));
+        // 残差 Z0 Z3 Z6 はシンドロームを持たないが論理演算子である
+        assert!(code.is_logical_error(&error, &ErrorVector::from_string("IIIZIIZII")));
+        // 何もしなければシンドロームが残る
+        assert!(code.is_logical_error(&error, &ErrorVector::from_string("IIIIIIIII")));
+
+        let (lx, lz) = (code.lx(), code.lz());
+        for recovery in ErrorVector::enumerate_up_to_weight(9, 2) {
+            assert_eq!(
+                code.is_logical_error_with(&lx, &lz, &error, &recovery),
+                code.is_logical_error(&error, &recovery)
+            );
+        }
+    }
+
+    #[test]
+    fn test_logical_weight_enumerator_steane() {
+        let code = crate::code::named_codes::steane_code();
+        // ハミング符号 1 + 7x^3 + 7x^4 + x^7 から双対のシンプレックス符号 1 + 7x^4 を除いたもの
+        let enumerator = code.logical_weight_enumerator();
+        assert_eq!(enumerator, vec![0, 0, 0, 7, 0, 0, 0, 1]);
+        assert_eq!(enumerator.iter().sum::<usize>(), 8);
+    }
+
+    #[test]
+    fn test_logical_weight_enumerator_shor() {
+        let code = shor_code();
+        // X型とZ型の最小重みはどちらも3なのでX型のコセットが選ばれる
+        // ker(H_Z) は各ブロックの XXX で張られ、奇数個のブロックの積が論理演算子になる
+        let enumerator = code.logical_weight_enumerator();
+        assert_eq!(enumerator, vec![0, 0, 0, 3, 0, 0, 0, 0, 0, 1]);
+    }
 }
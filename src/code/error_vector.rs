@@ -1,14 +1,22 @@
+use crate::Word;
 use crate::code::paulis::{Paulis, Phase};
+use crate::math::bit_linear_algebra::inner_product;
+use crate::util::{bitvec_to_u8, u8_to_bitvec};
 use bitvec::prelude::*;
+use std::fmt;
+use std::ops::BitXor;
 
+/// 誤りベクトルを表す構造体
+/// X部分とZ部分のビットベクトルのみを持ち、位相の情報は持たない
+/// 誤りの位相は復号や論理誤りの判定に影響しないため、常に+1であるとみなす
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ErrorVector {
-    x_part: BitVec<u64, Lsb0>,
-    z_part: BitVec<u64, Lsb0>,
+    x_part: BitVec<Word, Lsb0>,
+    z_part: BitVec<Word, Lsb0>,
 }
 
 impl ErrorVector {
-    pub fn new(x_part: BitVec<u64, Lsb0>, z_part: BitVec<u64, Lsb0>) -> Self {
+    pub fn new(x_part: BitVec<Word, Lsb0>, z_part: BitVec<Word, Lsb0>) -> Self {
         assert_eq!(
             x_part.len(),
             z_part.len(),
@@ -34,26 +42,14 @@ impl ErrorVector {
             "XエラーとZエラーの長さが一致しません"
         );
 
-        let mut x_part = bitvec![u64, Lsb0; 0; num_qubits];
-        let mut z_part = bitvec![u64, Lsb0; 0; num_qubits];
-
-        for i in 0..num_qubits {
-            if x_errors[i] != 0 {
-                x_part.set(i, true);
-            }
-            if z_errors[i] != 0 {
-                z_part.set(i, true);
-            }
-        }
-
-        Self::new(x_part, z_part)
+        Self::new(u8_to_bitvec(&x_errors), u8_to_bitvec(&z_errors))
     }
 
-    pub fn x_part(&self) -> &BitVec<u64, Lsb0> {
+    pub fn x_part(&self) -> &BitVec<Word, Lsb0> {
         &self.x_part
     }
 
-    pub fn z_part(&self) -> &BitVec<u64, Lsb0> {
+    pub fn z_part(&self) -> &BitVec<Word, Lsb0> {
         &self.z_part
     }
 
@@ -66,6 +62,47 @@ impl ErrorVector {
         error_vec.count_ones()
     }
 
+    /// 重みがmax_weight以下のすべてのPauli誤りを、重みの小さい順に列挙する
+    /// 重み0（恒等演算子）も含み、重みwの誤りは C(n, w) * 3^w 個ある
+    ///
+    /// # Examples
+    /// ```rust
+    /// use qldpc_sim::code::error_vector::ErrorVector;
+    ///
+    /// let errors: Vec<ErrorVector> = ErrorVector::enumerate_up_to_weight(2, 1).collect();
+    /// assert_eq!(errors.len(), 1 + 3 * 2);
+    /// assert_eq!(errors[0].num_errors(), 0);
+    /// ```
+    pub fn enumerate_up_to_weight(
+        num_qubits: usize,
+        max_weight: usize,
+    ) -> impl Iterator<Item = ErrorVector> {
+        (0..=max_weight.min(num_qubits))
+            .flat_map(move |weight| WeightedErrorIterator::new(num_qubits, weight))
+    }
+
+    /// 各量子ビットの誤りを 'I', 'X', 'Y', 'Z' のいずれかの文字として順に返す
+    ///
+    /// # Examples
+    /// ```rust
+    /// use qldpc_sim::code::error_vector::ErrorVector;
+    ///
+    /// let error = ErrorVector::from_string("IXYZ");
+    /// assert_eq!(error.paulis().collect::<String>(), "IXYZ");
+    /// ```
+    pub fn paulis(&self) -> impl Iterator<Item = char> + '_ {
+        self.x_part
+            .iter()
+            .zip(self.z_part.iter())
+            .map(|(x, z)| match (*x, *z) {
+                (false, false) => 'I',
+                (true, false) => 'X',
+                (true, true) => 'Y',
+                (false, true) => 'Z',
+            })
+    }
+
+    /// 位相+1のPauli演算子に変換する
     pub fn to_paulis(&self) -> Paulis {
         Paulis::new(
             self.num_qubits(),
@@ -74,36 +111,344 @@ impl ErrorVector {
             self.z_part.clone(),
         )
     }
+
+    /// Pauli演算子と可換かどうかをシンプレクティック積で判定する
+    /// 論理演算子やスタビライザーとの可換性を直接調べるときに使う
+    ///
+    /// # Examples
+    /// ```rust
+    /// use qldpc_sim::code::error_vector::ErrorVector;
+    /// use qldpc_sim::code::paulis::Paulis;
+    ///
+    /// let error = ErrorVector::from_string("XII");
+    /// assert!(!error.commutes_with(&Paulis::from_string("ZZI")));
+    /// assert!(error.commutes_with(&Paulis::from_string("IZZ")));
+    /// ```
+    pub fn commutes_with(&self, paulis: &Paulis) -> bool {
+        assert_eq!(
+            self.num_qubits(),
+            paulis.num_qubits(),
+            "誤りベクトルとPauli演算子の量子ビット数が一致しません"
+        );
+        let symplectic_product = inner_product(&self.x_part, paulis.z_part())
+            ^ inner_product(&self.z_part, paulis.x_part());
+        !symplectic_product
+    }
+}
+
+/// 重みがちょうどweightのPauli誤りを列挙するイテレータ
+/// 量子ビットの組み合わせを辞書順に進め、各組み合わせについてX, Y, Zの割り当てを列挙する
+struct WeightedErrorIterator {
+    num_qubits: usize,
+    /// 誤りが起こる量子ビットの組み合わせ（昇順）
+    support: Vec<usize>,
+    /// 各量子ビットのPauli (0: X, 1: Y, 2: Z)
+    paulis: Vec<u8>,
+    finished: bool,
+}
+
+impl WeightedErrorIterator {
+    fn new(num_qubits: usize, weight: usize) -> Self {
+        Self {
+            num_qubits,
+            support: (0..weight).collect(),
+            paulis: vec![0; weight],
+            finished: weight > num_qubits,
+        }
+    }
+
+    /// 次のPauliの割り当て、それが尽きたら次の量子ビットの組み合わせに進む
+    fn advance(&mut self) {
+        for pauli in self.paulis.iter_mut().rev() {
+            if *pauli < 2 {
+                *pauli += 1;
+                return;
+            }
+            *pauli = 0;
+        }
+
+        let weight = self.support.len();
+        for i in (0..weight).rev() {
+            if self.support[i] < self.num_qubits - weight + i {
+                self.support[i] += 1;
+                for j in (i + 1)..weight {
+                    self.support[j] = self.support[j - 1] + 1;
+                }
+                return;
+            }
+        }
+        self.finished = true;
+    }
+}
+
+impl Iterator for WeightedErrorIterator {
+    type Item = ErrorVector;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        let mut x_part = bitvec![Word, Lsb0; 0; self.num_qubits];
+        let mut z_part = bitvec![Word, Lsb0; 0; self.num_qubits];
+        for (&qubit_idx, &pauli) in self.support.iter().zip(&self.paulis) {
+            x_part.set(qubit_idx, pauli <= 1);
+            z_part.set(qubit_idx, pauli >= 1);
+        }
+
+        self.advance();
+        Some(ErrorVector::new(x_part, z_part))
+    }
+}
+
+/// 誤りベクトル同士の合成（X部分とZ部分それぞれのXOR）を計算する
+/// 復号後に残った誤り error ^ recovery を求めるときに使う
+impl BitXor<&ErrorVector> for &ErrorVector {
+    type Output = ErrorVector;
+
+    fn bitxor(self, rhs: &ErrorVector) -> Self::Output {
+        assert_eq!(
+            self.num_qubits(),
+            rhs.num_qubits(),
+            "誤りベクトルの量子ビット数が一致しません: {} != {}",
+            self.num_qubits(),
+            rhs.num_qubits()
+        );
+        ErrorVector::new(
+            self.x_part.clone() ^ &rhs.x_part,
+            self.z_part.clone() ^ &rhs.z_part,
+        )
+    }
+}
+
+impl BitXor<ErrorVector> for ErrorVector {
+    type Output = ErrorVector;
+
+    fn bitxor(self, rhs: ErrorVector) -> Self::Output {
+        &self ^ &rhs
+    }
+}
+
+impl BitXor<&ErrorVector> for ErrorVector {
+    type Output = ErrorVector;
+
+    fn bitxor(self, rhs: &ErrorVector) -> Self::Output {
+        &self ^ rhs
+    }
+}
+
+impl BitXor<ErrorVector> for &ErrorVector {
+    type Output = ErrorVector;
+
+    fn bitxor(self, rhs: ErrorVector) -> Self::Output {
+        self ^ &rhs
+    }
+}
+
+/// 位相を付けずに "IXZYI" のようなPauli文字列として表示する
+impl fmt::Display for ErrorVector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for pauli in self.paulis() {
+            write!(f, "{}", pauli)?;
+        }
+        Ok(())
+    }
 }
 
 /// シンドロームを表す構造体
 /// 現状はCSS符号のみをサポートしているため、ZシンドロームとXシンドロームを別々に持つ
 pub struct Syndrome {
-    z_syndrome: BitVec<u64, Lsb0>,
-    x_syndrome: BitVec<u64, Lsb0>,
+    z_syndrome: BitVec<Word, Lsb0>,
+    x_syndrome: BitVec<Word, Lsb0>,
 }
 
 impl Syndrome {
-    pub fn new(z_syndrome: BitVec<u64, Lsb0>, x_syndrome: BitVec<u64, Lsb0>) -> Self {
+    pub fn new(z_syndrome: BitVec<Word, Lsb0>, x_syndrome: BitVec<Word, Lsb0>) -> Self {
         Self {
             z_syndrome,
             x_syndrome,
         }
     }
 
+    pub fn from_u8vec(z_syndrome: Vec<u8>, x_syndrome: Vec<u8>) -> Self {
+        Self::new(u8_to_bitvec(&z_syndrome), u8_to_bitvec(&x_syndrome))
+    }
+
     pub fn len(&self) -> usize {
         self.z_syndrome.len() + self.x_syndrome.len()
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
     pub fn num_stabilizers(&self) -> usize {
         self.z_syndrome.len() + self.x_syndrome.len()
     }
 
-    pub fn z_syndrome(&self) -> &BitVec<u64, Lsb0> {
+    pub fn z_syndrome(&self) -> &BitVec<Word, Lsb0> {
         &self.z_syndrome
     }
 
-    pub fn x_syndrome(&self) -> &BitVec<u64, Lsb0> {
+    pub fn x_syndrome(&self) -> &BitVec<Word, Lsb0> {
         &self.x_syndrome
     }
+
+    /// Vec<u8>で動作する復号器向けにZシンドロームを変換する
+    pub fn z_syndrome_u8(&self) -> Vec<u8> {
+        bitvec_to_u8(&self.z_syndrome)
+    }
+
+    /// Vec<u8>で動作する復号器向けにXシンドロームを変換する
+    pub fn x_syndrome_u8(&self) -> Vec<u8> {
+        bitvec_to_u8(&self.x_syndrome)
+    }
+
+    /// すべてのスタビライザーの測定値が+1（シンドロームが全て0）かどうかを判定する
+    pub fn is_trivial(&self) -> bool {
+        self.z_syndrome.not_any() && self.x_syndrome.not_any()
+    }
+
+    /// 反転したX型スタビライザーのインデックスを返す
+    pub fn flipped_x(&self) -> Vec<usize> {
+        self.x_syndrome.iter_ones().collect()
+    }
+
+    /// 反転したZ型スタビライザーのインデックスを返す
+    pub fn flipped_z(&self) -> Vec<usize> {
+        self.z_syndrome.iter_ones().collect()
+    }
+
+    /// Zシンドロームの後ろにXシンドロームを連結した1本のビット列 [z_syndrome ; x_syndrome] を返す
+    pub fn to_bitvec(&self) -> BitVec<Word, Lsb0> {
+        let mut flat = self.z_syndrome.clone();
+        flat.extend_from_bitslice(&self.x_syndrome);
+        flat
+    }
+
+    /// `to_bitvec`で連結したビット列を、先頭z_len個をZシンドロームとして分割する
+    pub fn from_bitvec(flat: &BitVec<Word, Lsb0>, z_len: usize) -> Self {
+        assert!(
+            z_len <= flat.len(),
+            "z_len({})がビット列の長さ({})を超えています",
+            z_len,
+            flat.len()
+        );
+        let (z_syndrome, x_syndrome) = flat.split_at(z_len);
+        Self::new(z_syndrome.to_bitvec(), x_syndrome.to_bitvec())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::code::named_codes::shor_code;
+    use crate::util::u8_to_bitvec;
+
+    #[test]
+    fn test_syndrome_flipped_checks() {
+        let shor_code = shor_code();
+
+        let syndrome = shor_code.syndrome(&ErrorVector::from_string("IIIIXIIII"));
+        assert!(!syndrome.is_trivial());
+        assert_eq!(syndrome.flipped_z(), vec![2, 3]);
+        assert!(syndrome.flipped_x().is_empty());
+
+        let trivial = shor_code.syndrome(&ErrorVector::from_string("IIIIIIIII"));
+        assert!(trivial.is_trivial());
+    }
+
+    #[test]
+    fn test_error_vector_xor() {
+        let error = ErrorVector::from_string("XYZIX");
+        let zero = &error ^ &error;
+        assert_eq!(zero, ErrorVector::from_string("IIIII"));
+        assert_eq!(zero.num_errors(), 0);
+
+        let recovery = ErrorVector::from_string("XXIIZ");
+        let residual = error ^ recovery;
+        assert_eq!(residual, ErrorVector::from_string("IZZIY"));
+    }
+
+    #[test]
+    fn test_error_vector_commutes_with() {
+        let x_error = ErrorVector::from_string("IXIII");
+        assert!(!x_error.commutes_with(&Paulis::from_string("ZZIII")));
+        assert!(x_error.commutes_with(&Paulis::from_string("IIZZI")));
+        assert!(x_error.commutes_with(&Paulis::from_string("XXXXX")));
+    }
+
+    #[test]
+    fn test_paulis_error_vector_round_trip() {
+        let paulis = Paulis::from_string("-iXYZI");
+        let error = paulis.to_error_vector();
+        assert_eq!(error, ErrorVector::from_string("XYZI"));
+        // 位相は失われる
+        assert_eq!(error.to_paulis(), Paulis::from_string("XYZI"));
+    }
+
+    #[test]
+    fn test_syndrome_u8_round_trip() {
+        let z_syndrome = vec![1, 0, 0, 1, 1];
+        let x_syndrome = vec![0, 1];
+        let syndrome = Syndrome::from_u8vec(z_syndrome.clone(), x_syndrome.clone());
+        assert_eq!(syndrome.z_syndrome(), &u8_to_bitvec(&[1, 0, 0, 1, 1]));
+        assert_eq!(syndrome.x_syndrome(), &u8_to_bitvec(&[0, 1]));
+        assert_eq!(syndrome.z_syndrome_u8(), z_syndrome);
+        assert_eq!(syndrome.x_syndrome_u8(), x_syndrome);
+    }
+
+    #[test]
+    fn test_syndrome_bitvec_round_trip() {
+        let syndrome = Syndrome::from_u8vec(vec![1, 0, 1], vec![0, 1]);
+        let flat = syndrome.to_bitvec();
+        assert_eq!(flat, u8_to_bitvec(&[1, 0, 1, 0, 1]));
+
+        let restored = Syndrome::from_bitvec(&flat, 3);
+        assert_eq!(restored.z_syndrome(), syndrome.z_syndrome());
+        assert_eq!(restored.x_syndrome(), syndrome.x_syndrome());
+    }
+
+    #[test]
+    fn test_enumerate_up_to_weight() {
+        let n = 5;
+        let weight_one = ErrorVector::enumerate_up_to_weight(n, 1)
+            .filter(|error| error.num_errors() == 1)
+            .count();
+        assert_eq!(weight_one, 3 * n);
+
+        // 1 + 3 * C(5, 1) + 9 * C(5, 2) = 1 + 15 + 90
+        let errors: Vec<ErrorVector> = ErrorVector::enumerate_up_to_weight(n, 2).collect();
+        assert_eq!(errors.len(), 106);
+        assert!(errors.iter().all(|error| error.num_errors() <= 2));
+        for i in 0..errors.len() {
+            for j in (i + 1)..errors.len() {
+                assert_ne!(errors[i], errors[j]);
+            }
+        }
+
+        // 量子ビット数を超える重みは量子ビット数で打ち切られる
+        assert_eq!(ErrorVector::enumerate_up_to_weight(1, 3).count(), 4);
+    }
+
+    #[test]
+    fn test_error_vector_display() {
+        let error = ErrorVector::from_u8vec(vec![0, 0, 1, 0, 0], vec![0; 5]);
+        assert_eq!(error.to_string(), "IIXII");
+
+        let error = ErrorVector::from_string("IXZYI");
+        assert_eq!(format!("{}", error), "IXZYI");
+    }
+
+    #[test]
+    fn test_error_vector_paulis() {
+        let mut x_part = bitvec![Word, Lsb0; 0; 5];
+        let mut z_part = bitvec![Word, Lsb0; 0; 5];
+        x_part.set(3, true);
+        z_part.set(3, true);
+        let error = ErrorVector::new(x_part, z_part);
+
+        let paulis: Vec<char> = error.paulis().collect();
+        assert_eq!(paulis, vec!['I', 'I', 'I', 'Y', 'I']);
+    }
 }
@@ -0,0 +1,52 @@
+use crate::code::css_code::CssCode;
+use crate::math::sparse_matrix::BinarySparseMatrix;
+
+/// 一般化自転車符号(Generalized Bicycle code)を構成する
+/// `a`, `b`は巡回群`Z_l`上の多項式a(x), b(x)の非零項の指数を表す
+/// 巡回行列A = a(P), B = b(P)（Pはl×lの巡回シフト行列）から
+/// H_X = [A | B], H_Z = [B^T | A^T] として符号を構成する
+/// 巡回行列同士は可換なので H_X H_Z^T = AB + BA = 0 となる
+///
+/// # Examples
+/// ```rust
+/// use qldpc_sim::code::generalized_bicycle::generalized_bicycle;
+/// use qldpc_sim::code::traits::QuantumCode;
+///
+/// // Lin & Pryadko による[[48, 6, 8]]一般化自転車符号
+/// let code = generalized_bicycle(24, &[0, 2, 8, 15], &[0, 2, 12, 17]);
+/// assert_eq!(code.n(), 48);
+/// assert_eq!(code.k(), 6);
+/// ```
+pub fn generalized_bicycle(l: usize, a: &[usize], b: &[usize]) -> CssCode {
+    let a_matrix = BinarySparseMatrix::circulant(l, a);
+    let b_matrix = BinarySparseMatrix::circulant(l, b);
+
+    let hx = a_matrix.augment_right(&b_matrix);
+    let hz = b_matrix.transpose().augment_right(&a_matrix.transpose());
+
+    let code_name = format!("GeneralizedBicycle(l={}, a={:?}, b={:?})", l, a, b);
+    CssCode::from_parity_check_matrices(&code_name, hz, hx)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::code::traits::QuantumCode;
+
+    #[test]
+    fn test_generalized_bicycle_48_6() {
+        let code = generalized_bicycle(24, &[0, 2, 8, 15], &[0, 2, 12, 17]);
+        assert_eq!(code.n(), 48);
+        assert_eq!(code.k(), 6);
+        assert_eq!(code.hx().shape(), (24, 48));
+        assert_eq!(code.hz().shape(), (24, 48));
+    }
+
+    #[test]
+    fn test_generalized_bicycle_toric_like() {
+        // a(x) = b(x) = 1 + x のとき gcd(a, b, x^l - 1) = 1 + x なので k = 2
+        let code = generalized_bicycle(5, &[0, 1], &[0, 1]);
+        assert_eq!(code.n(), 10);
+        assert_eq!(code.k(), 2);
+    }
+}
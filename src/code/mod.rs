@@ -1,7 +1,15 @@
 mod binary_symplectic;
+pub mod classical;
+pub mod color_code;
+pub mod concatenated;
 pub mod css_code;
 pub mod error_vector;
+pub mod generalized_bicycle;
+pub mod lifted_product;
+pub mod named_codes;
 pub mod paulis;
+pub mod random_ldpc;
 pub mod traits;
 pub mod stabilizer;
 pub mod stabilizer_code;
+pub mod surface_code;
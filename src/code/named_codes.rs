@@ -0,0 +1,111 @@
+use crate::code::css_code::CssCode;
+use crate::code::paulis::Paulis;
+use crate::code::stabilizer_code::StabilizerCode;
+use crate::math::sparse_matrix::BinarySparseMatrix;
+
+/// [[7, 1, 3]] Steane符号を返す
+/// H_X, H_Z はともに[7, 4, 3]ハミング符号の検査行列
+///
+/// # Examples
+/// ```rust
+/// use qldpc_sim::code::named_codes::steane_code;
+/// use qldpc_sim::code::traits::QuantumCode;
+///
+/// let code = steane_code();
+/// assert_eq!(code.n(), 7);
+/// assert_eq!(code.k(), 1);
+/// ```
+pub fn steane_code() -> CssCode {
+    let hamming_row_adj = vec![vec![3, 4, 5, 6], vec![1, 2, 5, 6], vec![0, 2, 4, 6]];
+    let hamming = BinarySparseMatrix::from_row_adj(3, 7, hamming_row_adj);
+    CssCode::from_parity_check_matrices("Steane", hamming.clone(), hamming)
+}
+
+/// [[9, 1, 3]] Shor符号を返す
+/// 3量子ビットのビット反転符号を位相反転符号で連結したもので、H_Z は各ブロック内の隣り合う2量子ビット、
+/// H_X は隣り合う2ブロックの6量子ビットに作用する
+///
+/// # Examples
+/// ```rust
+/// use qldpc_sim::code::named_codes::shor_code;
+/// use qldpc_sim::code::traits::QuantumCode;
+///
+/// let code = shor_code();
+/// assert_eq!(code.n(), 9);
+/// assert_eq!(code.k(), 1);
+/// ```
+pub fn shor_code() -> CssCode {
+    let hz_row_adj = vec![
+        vec![0, 1],
+        vec![1, 2],
+        vec![3, 4],
+        vec![4, 5],
+        vec![6, 7],
+        vec![7, 8],
+    ];
+    let hx_row_adj = vec![vec![0, 1, 2, 3, 4, 5], vec![3, 4, 5, 6, 7, 8]];
+    let hz = BinarySparseMatrix::from_row_adj(6, 9, hz_row_adj);
+    let hx = BinarySparseMatrix::from_row_adj(2, 9, hx_row_adj);
+    CssCode::from_parity_check_matrices("ShorCode", hz, hx)
+}
+
+/// [[5, 1, 3]] 5量子ビット符号（完全符号）を返す
+/// 生成子は XZZXI の巡回シフト
+///
+/// # Examples
+/// ```rust
+/// use qldpc_sim::code::named_codes::five_qubit_code;
+/// use qldpc_sim::code::traits::QuantumCode;
+///
+/// let code = five_qubit_code();
+/// assert_eq!(code.n(), 5);
+/// assert_eq!(code.k(), 1);
+/// ```
+pub fn five_qubit_code() -> StabilizerCode {
+    let generators = vec![
+        Paulis::from_string("XZZXI"),
+        Paulis::from_string("IXZZX"),
+        Paulis::from_string("XIXZZ"),
+        Paulis::from_string("ZXIXZ"),
+    ];
+    StabilizerCode::from_generators("FiveQubit", generators)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::code::traits::QuantumCode;
+
+    #[test]
+    fn test_steane_code() {
+        let code = steane_code();
+        assert_eq!(code.n(), 7);
+        assert_eq!(code.k(), 1);
+        assert_eq!(code.num_stabilizers(), 6);
+        assert!(code.hx().is_orthogonal_to(code.hz()));
+    }
+
+    #[test]
+    fn test_shor_code() {
+        let code = shor_code();
+        assert_eq!(code.n(), 9);
+        assert_eq!(code.k(), 1);
+        assert_eq!(code.hz().shape(), (6, 9));
+        assert_eq!(code.hx().shape(), (2, 9));
+        assert!(code.hx().is_orthogonal_to(code.hz()));
+    }
+
+    #[test]
+    fn test_five_qubit_code() {
+        let code = five_qubit_code();
+        assert_eq!(code.n(), 5);
+        assert_eq!(code.k(), 1);
+
+        let generators = code.stabilizer_group().generators();
+        for a in generators {
+            for b in generators {
+                assert!(a.commutes(b));
+            }
+        }
+    }
+}
@@ -1,4 +1,6 @@
+use crate::Word;
 use crate::code::binary_symplectic::BinarySymplecticVector;
+use crate::code::error_vector::ErrorVector;
 use bitvec::prelude::*;
 use std::ops::Mul;
 
@@ -22,6 +24,53 @@ pub enum Phase {
     MinusI,
 }
 
+impl Phase {
+    /// 逆元を返す（+i と -i が入れ替わり、±1 はそのまま）
+    ///
+    /// # Examples
+    /// ```rust
+    /// use qldpc_sim::code::paulis::Phase;
+    ///
+    /// assert_eq!(Phase::I.inverse(), Phase::MinusI);
+    /// assert_eq!(Phase::I * Phase::I.inverse(), Phase::One);
+    /// ```
+    pub fn inverse(self) -> Phase {
+        self.pow(-1)
+    }
+
+    /// n乗を返す。nが負の場合は逆元の|n|乗になる
+    ///
+    /// # Examples
+    /// ```rust
+    /// use qldpc_sim::code::paulis::Phase;
+    ///
+    /// assert_eq!(Phase::I.pow(2), Phase::MinusOne);
+    /// assert_eq!(Phase::MinusI.pow(-3), Phase::MinusI);
+    /// ```
+    pub fn pow(self, n: i64) -> Phase {
+        Phase::from_exponent(self.exponent() * n.rem_euclid(4))
+    }
+
+    /// 位相を i^k と表したときの指数 k (0..4)
+    fn exponent(self) -> i64 {
+        match self {
+            Phase::One => 0,
+            Phase::I => 1,
+            Phase::MinusOne => 2,
+            Phase::MinusI => 3,
+        }
+    }
+
+    fn from_exponent(k: i64) -> Phase {
+        match k.rem_euclid(4) {
+            0 => Phase::One,
+            1 => Phase::I,
+            2 => Phase::MinusOne,
+            _ => Phase::MinusI,
+        }
+    }
+}
+
 impl Mul for Phase {
     type Output = Self;
 
@@ -41,6 +90,35 @@ impl Mul for Phase {
     }
 }
 
+/// 恒等演算子以外の単一量子ビットPauli演算子の種類を表す列挙型
+/// Y = iXZ なので、Yは X成分と Z成分の両方を持つ
+///
+/// # Examples
+/// ```rust
+/// use qldpc_sim::code::paulis::PauliType;
+///
+/// assert!(PauliType::Y.has_x() && PauliType::Y.has_z());
+/// assert!(!PauliType::Z.has_x());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PauliType {
+    X,
+    Y,
+    Z,
+}
+
+impl PauliType {
+    /// X成分を持つ(XまたはY)かどうか
+    pub fn has_x(self) -> bool {
+        matches!(self, PauliType::X | PauliType::Y)
+    }
+
+    /// Z成分を持つ(ZまたはY)かどうか
+    pub fn has_z(self) -> bool {
+        matches!(self, PauliType::Z | PauliType::Y)
+    }
+}
+
 /// Pauli演算子(Paulis)を表す構造体
 /// 量子ビット数、位相、Z部分とX部分のビットベクトルを持つ
 /// 位相の情報も持っているという点でBinarySymplecticVectorよりも強力
@@ -64,8 +142,8 @@ impl Paulis {
     pub fn new(
         num_qubits: usize,
         phase: Phase,
-        x_part: BitVec<u64, Lsb0>,
-        z_part: BitVec<u64, Lsb0>,
+        x_part: BitVec<Word, Lsb0>,
+        z_part: BitVec<Word, Lsb0>,
     ) -> Self {
         assert_eq!(
             num_qubits,
@@ -124,9 +202,9 @@ impl Paulis {
         }
     }
 
-    fn parse_paulis(s: &str) -> (BitVec<u64, Lsb0>, BitVec<u64, Lsb0>) {
-        let mut z_part = BitVec::<u64, Lsb0>::new();
-        let mut x_part = BitVec::<u64, Lsb0>::new();
+    fn parse_paulis(s: &str) -> (BitVec<Word, Lsb0>, BitVec<Word, Lsb0>) {
+        let mut z_part = BitVec::<Word, Lsb0>::new();
+        let mut x_part = BitVec::<Word, Lsb0>::new();
 
         for c in s.chars() {
             match c {
@@ -155,8 +233,8 @@ impl Paulis {
     }
 
     pub fn identity(num_qubits: usize) -> Self {
-        let z_part = bitvec![u64, Lsb0; 0; num_qubits];
-        let x_part = bitvec![u64, Lsb0; 0; num_qubits];
+        let z_part = bitvec![Word, Lsb0; 0; num_qubits];
+        let x_part = bitvec![Word, Lsb0; 0; num_qubits];
 
         Self::new(num_qubits, Phase::One, z_part, x_part)
     }
@@ -173,14 +251,69 @@ impl Paulis {
         &self.binary_symplectic_vector
     }
 
-    pub fn z_part(&self) -> &BitVec<u64, Lsb0> {
+    pub fn z_part(&self) -> &BitVec<Word, Lsb0> {
         self.binary_symplectic_vector.z_part()
     }
 
-    pub fn x_part(&self) -> &BitVec<u64, Lsb0> {
+    pub fn x_part(&self) -> &BitVec<Word, Lsb0> {
         self.binary_symplectic_vector.x_part()
     }
 
+    /// 誤りベクトルに変換する
+    /// 誤りベクトルは位相を持たないので、この変換では位相の情報が失われる
+    pub fn to_error_vector(&self) -> ErrorVector {
+        ErrorVector::from_paulis(self)
+    }
+
+    /// 恒等演算子でない量子ビットの数（ウェイト）を返す
+    pub fn weight(&self) -> usize {
+        (self.z_part().clone() | self.x_part()).count_ones()
+    }
+
+    /// 恒等演算子でない量子ビットのインデックスを昇順で返す
+    ///
+    /// # Examples
+    /// ```rust
+    /// use qldpc_sim::code::paulis::Paulis;
+    ///
+    /// let pauli = Paulis::from_string("IXYZI");
+    /// assert_eq!(pauli.weight(), 3);
+    /// assert_eq!(pauli.support(), vec![1, 2, 3]);
+    /// ```
+    pub fn support(&self) -> Vec<usize> {
+        (self.z_part().clone() | self.x_part())
+            .iter_ones()
+            .collect()
+    }
+
+    /// エルミート演算子かどうか（位相が±1かどうか）を返す
+    /// 各量子ビットのPauli演算子はエルミートなので、位相が実数であればよい
+    /// スタビライザーの生成子はエルミートでなければならない
+    ///
+    /// # Examples
+    /// ```rust
+    /// use qldpc_sim::code::paulis::Paulis;
+    ///
+    /// assert!(Paulis::from_string("-XZ").is_hermitian());
+    /// assert!(!Paulis::from_string("iX").is_hermitian());
+    /// ```
+    pub fn is_hermitian(&self) -> bool {
+        matches!(self.phase, Phase::One | Phase::MinusOne)
+    }
+
+    /// self^n が恒等演算子（位相+1）になる最小の正の整数nを返す
+    /// 恒等演算子なら1、その他のエルミートな演算子なら2、位相が±iなら4になる
+    pub fn order(&self) -> usize {
+        let identity = Paulis::identity(self.num_qubits);
+        let mut power = self.clone();
+        let mut n = 1;
+        while power != identity {
+            power = &power * self;
+            n += 1;
+        }
+        n
+    }
+
     pub fn commutes(&self, other: &Paulis) -> bool {
         assert_eq!(
             self.num_qubits, other.num_qubits,
@@ -190,6 +323,131 @@ impl Paulis {
             .binary_symplectic_vector
             .symplectic_product(&other.binary_symplectic_vector)
     }
+
+    /// テンソル積 self ⊗ other を計算する
+    /// Z部分とX部分をそれぞれ連結し、位相は掛け合わせる
+    ///
+    /// # Examples
+    /// ```rust
+    /// use qldpc_sim::code::paulis::Paulis;
+    ///
+    /// let a = Paulis::from_string("iXZ");
+    /// let b = Paulis::from_string("-Y");
+    /// assert_eq!(a.tensor(&b), Paulis::from_string("-iXZY"));
+    /// ```
+    pub fn tensor(&self, other: &Paulis) -> Paulis {
+        let mut x_part = self.x_part().clone();
+        x_part.extend_from_bitslice(other.x_part());
+        let mut z_part = self.z_part().clone();
+        z_part.extend_from_bitslice(other.z_part());
+
+        Paulis::new(
+            self.num_qubits + other.num_qubits,
+            self.phase * other.phase,
+            x_part,
+            z_part,
+        )
+    }
+
+    /// total_qubits量子ビットの恒等演算子のうち、supportで指定した位置にこの演算子を埋め込む
+    /// self の i 番目の量子ビットは support[i] 番目の量子ビットに配置される
+    ///
+    /// # Examples
+    /// ```rust
+    /// use qldpc_sim::code::paulis::Paulis;
+    ///
+    /// let xz = Paulis::from_string("XZ");
+    /// assert_eq!(xz.embed(5, &[1, 3]), Paulis::from_string("IXIZI"));
+    /// ```
+    pub fn embed(&self, total_qubits: usize, support: &[usize]) -> Paulis {
+        assert_eq!(
+            self.num_qubits,
+            support.len(),
+            "num_qubits({})とsupportの長さ({})が一致しません",
+            self.num_qubits,
+            support.len()
+        );
+
+        let mut x_part = bitvec![Word, Lsb0; 0; total_qubits];
+        let mut z_part = bitvec![Word, Lsb0; 0; total_qubits];
+        let mut used = bitvec![Word, Lsb0; 0; total_qubits];
+
+        for (i, &qubit_idx) in support.iter().enumerate() {
+            assert!(
+                qubit_idx < total_qubits,
+                "supportの要素がtotal_qubits({})を超えています: {}",
+                total_qubits,
+                qubit_idx
+            );
+            assert!(!used[qubit_idx], "supportに重複があります: {}", qubit_idx);
+            used.set(qubit_idx, true);
+            x_part.set(qubit_idx, self.x_part()[i]);
+            z_part.set(qubit_idx, self.z_part()[i]);
+        }
+
+        Paulis::new(total_qubits, self.phase, x_part, z_part)
+    }
+
+    /// アダマールゲートHで共役を取る (P -> H P H)
+    /// X -> Z, Z -> X, Y -> -Y
+    pub fn conjugate_h(&mut self, qubit: usize) {
+        self.assert_qubit_index(qubit);
+        let mut x_part = self.x_part().clone();
+        let mut z_part = self.z_part().clone();
+        let (x, z) = (x_part[qubit], z_part[qubit]);
+        if x && z {
+            self.phase = self.phase * Phase::MinusOne;
+        }
+        x_part.set(qubit, z);
+        z_part.set(qubit, x);
+        self.binary_symplectic_vector = BinarySymplecticVector::new(x_part, z_part);
+    }
+
+    /// 位相ゲートSで共役を取る (P -> S P S†)
+    /// X -> Y, Y -> -X, Z -> Z
+    pub fn conjugate_s(&mut self, qubit: usize) {
+        self.assert_qubit_index(qubit);
+        let mut z_part = self.z_part().clone();
+        let (x, z) = (self.x_part()[qubit], z_part[qubit]);
+        if x && z {
+            self.phase = self.phase * Phase::MinusOne;
+        }
+        z_part.set(qubit, z ^ x);
+        self.binary_symplectic_vector = BinarySymplecticVector::new(self.x_part().clone(), z_part);
+    }
+
+    /// CNOTゲートで共役を取る (P -> CNOT P CNOT)
+    /// XI -> XX, IX -> IX, ZI -> ZI, IZ -> ZZ
+    /// 位相はAaronson-Gottesmanの規則 r ^= x_c z_t (x_t ^ z_c ^ 1) に従って更新する
+    pub fn conjugate_cnot(&mut self, control: usize, target: usize) {
+        self.assert_qubit_index(control);
+        self.assert_qubit_index(target);
+        assert_ne!(
+            control, target,
+            "制御ビットと標的ビットが同じです: {}",
+            control
+        );
+
+        let mut x_part = self.x_part().clone();
+        let mut z_part = self.z_part().clone();
+        let (x_c, z_c) = (x_part[control], z_part[control]);
+        let (x_t, z_t) = (x_part[target], z_part[target]);
+        if x_c && z_t && !(x_t ^ z_c) {
+            self.phase = self.phase * Phase::MinusOne;
+        }
+        x_part.set(target, x_t ^ x_c);
+        z_part.set(control, z_c ^ z_t);
+        self.binary_symplectic_vector = BinarySymplecticVector::new(x_part, z_part);
+    }
+
+    fn assert_qubit_index(&self, qubit: usize) {
+        assert!(
+            qubit < self.num_qubits,
+            "量子ビットのインデックス({})がnum_qubits({})を超えています",
+            qubit,
+            self.num_qubits
+        );
+    }
 }
 
 impl Mul<&Paulis> for &Paulis {
@@ -201,8 +459,8 @@ impl Mul<&Paulis> for &Paulis {
             "乗算するPauli文字列の量子ビット数が一致しません"
         );
         let mut phase = self.phase * rhs.phase;
-        let mut z_part = BitVec::<u64, Lsb0>::with_capacity(self.num_qubits);
-        let mut x_part = BitVec::<u64, Lsb0>::with_capacity(self.num_qubits);
+        let mut z_part = BitVec::<Word, Lsb0>::with_capacity(self.num_qubits);
+        let mut x_part = BitVec::<Word, Lsb0>::with_capacity(self.num_qubits);
 
         for i in 0..self.num_qubits {
             let a_z = self.binary_symplectic_vector.z_part()[i];
@@ -262,6 +520,7 @@ impl Mul<Paulis> for &Paulis {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::util::u8_to_bitvec;
 
     #[test]
     fn test_paulis_from_string() {
@@ -270,11 +529,11 @@ mod tests {
         assert_eq!(pauli_str.phase, Phase::One);
         assert_eq!(
             pauli_str.binary_symplectic_vector.z_part().clone(),
-            bitvec!(u64, Lsb0; 0, 1, 0, 1)
+            u8_to_bitvec(&[0, 1, 0, 1])
         );
         assert_eq!(
             pauli_str.binary_symplectic_vector.x_part().clone(),
-            bitvec!(u64, Lsb0; 1, 0, 0, 1)
+            u8_to_bitvec(&[1, 0, 0, 1])
         );
 
         let pauli_str = Paulis::from_string("-iYZXI");
@@ -282,11 +541,11 @@ mod tests {
         assert_eq!(pauli_str.phase, Phase::MinusI);
         assert_eq!(
             pauli_str.binary_symplectic_vector.z_part().clone(),
-            bitvec!(u64, Lsb0; 1, 1, 0, 0)
+            u8_to_bitvec(&[1, 1, 0, 0])
         );
         assert_eq!(
             pauli_str.binary_symplectic_vector.x_part().clone(),
-            bitvec!(u64, Lsb0; 1, 0, 1, 0)
+            u8_to_bitvec(&[1, 0, 1, 0])
         );
 
         let pauli_str = Paulis::from_string("IIXI");
@@ -294,11 +553,11 @@ mod tests {
         assert_eq!(pauli_str.phase, Phase::One);
         assert_eq!(
             pauli_str.binary_symplectic_vector.z_part().clone(),
-            bitvec!(u64, Lsb0; 0, 0, 0, 0)
+            u8_to_bitvec(&[0, 0, 0, 0])
         );
         assert_eq!(
             pauli_str.binary_symplectic_vector.x_part().clone(),
-            bitvec!(u64, Lsb0; 0, 0, 1, 0)
+            u8_to_bitvec(&[0, 0, 1, 0])
         );
     }
 
@@ -325,4 +584,172 @@ mod tests {
         let pauli_str3 = Paulis::from_string("+IZII");
         assert!(pauli_str1.commutes(&pauli_str3));
     }
+
+    #[test]
+    fn test_paulis_tensor() {
+        let x = Paulis::from_string("X");
+        let z = Paulis::from_string("-Z");
+        let result = x.tensor(&z);
+        assert_eq!(result, Paulis::from_string("-XZ"));
+        assert_eq!(result.num_qubits(), 2);
+
+        let phase_i = Paulis::from_string("iIY");
+        assert_eq!(phase_i.tensor(&phase_i), Paulis::from_string("-IYIY"));
+    }
+
+    #[test]
+    fn test_paulis_embed() {
+        let xz = Paulis::from_string("X").tensor(&Paulis::from_string("Z"));
+        let embedded = xz.embed(5, &[1, 3]);
+        assert_eq!(embedded, Paulis::from_string("IXIZI"));
+
+        // supportの順番は保持される
+        let embedded_reversed = xz.embed(5, &[3, 1]);
+        assert_eq!(embedded_reversed, Paulis::from_string("IZIXI"));
+    }
+
+    #[test]
+    #[should_panic(expected = "supportに重複があります")]
+    fn test_paulis_embed_duplicate_support() {
+        let xz = Paulis::from_string("XZ");
+        let _embedded = xz.embed(5, &[1, 1]);
+    }
+
+    #[test]
+    fn test_paulis_weight_and_support() {
+        let pauli = Paulis::from_string("IXYZI");
+        assert_eq!(pauli.weight(), 3);
+        assert_eq!(pauli.support(), vec![1, 2, 3]);
+
+        let identity = Paulis::identity(4);
+        assert_eq!(identity.weight(), 0);
+        assert!(identity.support().is_empty());
+    }
+
+    #[test]
+    fn test_paulis_conjugate_h() {
+        let mut paulis = Paulis::from_string("XZI");
+        paulis.conjugate_h(0);
+        assert_eq!(paulis, Paulis::from_string("ZZI"));
+        paulis.conjugate_h(1);
+        assert_eq!(paulis, Paulis::from_string("ZXI"));
+
+        let mut y = Paulis::from_string("Y");
+        y.conjugate_h(0);
+        assert_eq!(y, Paulis::from_string("-Y"));
+    }
+
+    #[test]
+    fn test_paulis_conjugate_s() {
+        let mut x = Paulis::from_string("X");
+        x.conjugate_s(0);
+        assert_eq!(x, Paulis::from_string("Y"));
+        x.conjugate_s(0);
+        assert_eq!(x, Paulis::from_string("-X"));
+
+        let mut z = Paulis::from_string("Z");
+        z.conjugate_s(0);
+        assert_eq!(z, Paulis::from_string("Z"));
+    }
+
+    #[test]
+    fn test_paulis_conjugate_cnot() {
+        let cases = [
+            ("XI", "XX"),
+            ("IX", "IX"),
+            ("ZI", "ZI"),
+            ("IZ", "ZZ"),
+            ("YI", "YX"),
+            ("IY", "ZY"),
+            ("YY", "-XZ"),
+            ("XZ", "-YY"),
+        ];
+        for (before, after) in cases {
+            let mut paulis = Paulis::from_string(before);
+            paulis.conjugate_cnot(0, 1);
+            assert_eq!(paulis, Paulis::from_string(after), "{}", before);
+        }
+    }
+
+    #[test]
+    fn test_paulis_conjugation_preserves_products() {
+        let a = Paulis::from_string("XYZI");
+        let b = Paulis::from_string("-iYYXZ");
+        let conjugate_all = |paulis: &Paulis| {
+            let mut result = paulis.clone();
+            result.conjugate_h(0);
+            result.conjugate_s(1);
+            result.conjugate_cnot(1, 2);
+            result.conjugate_cnot(3, 0);
+            result
+        };
+        assert_eq!(
+            conjugate_all(&(&a * &b)),
+            &conjugate_all(&a) * &conjugate_all(&b)
+        );
+    }
+
+    #[test]
+    fn test_phase_inverse() {
+        for phase in [Phase::One, Phase::I, Phase::MinusOne, Phase::MinusI] {
+            assert_eq!(phase * phase.inverse(), Phase::One);
+        }
+        assert_eq!(Phase::One.inverse(), Phase::One);
+        assert_eq!(Phase::I.inverse(), Phase::MinusI);
+        assert_eq!(Phase::MinusOne.inverse(), Phase::MinusOne);
+        assert_eq!(Phase::MinusI.inverse(), Phase::I);
+    }
+
+    #[test]
+    fn test_phase_pow() {
+        for phase in [Phase::One, Phase::I, Phase::MinusOne, Phase::MinusI] {
+            assert_eq!(phase.pow(0), Phase::One);
+            assert_eq!(phase.pow(1), phase);
+            assert_eq!(phase.pow(4), Phase::One);
+            assert_eq!(phase.pow(-1), phase.inverse());
+            let mut expected = Phase::One;
+            for n in 0..8 {
+                assert_eq!(phase.pow(n), expected);
+                expected = expected * phase;
+            }
+        }
+        assert_eq!(Phase::I.pow(2), Phase::MinusOne);
+        assert_eq!(Phase::I.pow(3), Phase::MinusI);
+        assert_eq!(Phase::MinusOne.pow(-5), Phase::MinusOne);
+    }
+
+    #[test]
+    fn test_paulis_is_hermitian() {
+        assert!(Paulis::from_string("XZ").is_hermitian());
+        assert!(Paulis::from_string("-XZ").is_hermitian());
+        assert!(!Paulis::from_string("iX").is_hermitian());
+        assert!(!Paulis::from_string("-iXY").is_hermitian());
+        // X Z = -iY はエルミートでない
+        let xz = Paulis::from_string("X") * Paulis::from_string("Z");
+        assert!(!xz.is_hermitian());
+    }
+
+    #[test]
+    fn test_paulis_order() {
+        assert_eq!(Paulis::identity(3).order(), 1);
+        assert_eq!(Paulis::from_string("-III").order(), 2);
+        assert_eq!(Paulis::from_string("XZ").order(), 2);
+        assert_eq!(Paulis::from_string("-YI").order(), 2);
+        assert_eq!(Paulis::from_string("iX").order(), 4);
+        assert_eq!(Paulis::from_string("-iII").order(), 4);
+    }
+
+    #[test]
+    fn test_paulis_word_type_round_trip() {
+        let expected_bits = if cfg!(feature = "bits32") { 32 } else { 64 };
+        assert_eq!(Word::BITS, expected_bits);
+
+        let pauli = Paulis::from_string("-iXYZI");
+        let x_part: &BitVec<Word, Lsb0> = pauli.x_part();
+        let z_part: &BitVec<Word, Lsb0> = pauli.z_part();
+        assert_eq!(*x_part, u8_to_bitvec(&[1, 1, 0, 0]));
+        assert_eq!(*z_part, u8_to_bitvec(&[0, 1, 1, 0]));
+        let rebuilt = Paulis::new(4, pauli.phase(), x_part.clone(), z_part.clone());
+        assert_eq!(rebuilt, pauli);
+    }
 }
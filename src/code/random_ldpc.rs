@@ -0,0 +1,141 @@
+use crate::math::sparse_matrix::BinarySparseMatrix;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use rand::seq::IndexedRandom;
+use std::collections::VecDeque;
+
+/// Progressive Edge-Growth (PEG) 法でランダムなLDPC検査行列を生成する
+/// 列を1つずつ処理し、各列に`col_weight`本のエッジを、その列から遠いチェックノードへ順に張る
+/// 新しいエッジで長さ`min_girth`未満のサイクルができないチェックのうち、次数が最小のものを
+/// 乱数で選ぶので、行の重みはほぼ均一になる
+/// 条件を満たすチェックがなくなった場合は最も遠いチェックを選ぶため、
+/// 行列が小さすぎると内周が`min_girth`を下回ることがある
+/// 同じ`seed`からは常に同じ行列が得られる
+///
+/// # Examples
+/// ```rust
+/// use qldpc_sim::code::random_ldpc::random_ldpc_pcm;
+///
+/// let pcm = random_ldpc_pcm(20, 30, 3, 6, 42);
+/// assert_eq!(pcm.shape(), (20, 30));
+/// assert!(pcm.girth().is_none_or(|g| g >= 6));
+/// ```
+pub fn random_ldpc_pcm(
+    rows: usize,
+    cols: usize,
+    col_weight: usize,
+    min_girth: usize,
+    seed: u64,
+) -> BinarySparseMatrix {
+    assert!(
+        col_weight <= rows,
+        "列の重み({})が行数({})を超えています",
+        col_weight,
+        rows
+    );
+
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut row_adj: Vec<Vec<usize>> = vec![Vec::new(); rows];
+    let mut col_adj: Vec<Vec<usize>> = vec![Vec::new(); cols];
+
+    for col in 0..cols {
+        for _ in 0..col_weight {
+            let distances = check_distances(&row_adj, &col_adj, col);
+
+            // 新しいエッジ (col, check) が作るサイクルの長さは distance + 1
+            let allowed: Vec<usize> = (0..rows)
+                .filter(|&check| distances[check].is_none_or(|d| d != 1 && d + 1 >= min_girth))
+                .collect();
+            let candidates = if allowed.is_empty() {
+                let farthest = distances
+                    .iter()
+                    .filter_map(|&d| d.filter(|&d| d != 1))
+                    .max()
+                    .expect("エッジを張れるチェックがありません");
+                (0..rows)
+                    .filter(|&check| distances[check] == Some(farthest))
+                    .collect()
+            } else {
+                allowed
+            };
+
+            let min_degree = candidates
+                .iter()
+                .map(|&check| row_adj[check].len())
+                .min()
+                .unwrap();
+            let lightest: Vec<usize> = candidates
+                .into_iter()
+                .filter(|&check| row_adj[check].len() == min_degree)
+                .collect();
+            let &check = lightest.choose(&mut rng).unwrap();
+
+            row_adj[check].push(col);
+            col_adj[col].push(check);
+        }
+    }
+
+    BinarySparseMatrix::new(rows, cols, row_adj, col_adj)
+}
+
+/// タナーグラフ上でビット`col`から各チェックノードまでの距離（エッジ数）を幅優先探索で求める
+/// 到達できないチェックは`None`
+fn check_distances(
+    row_adj: &[Vec<usize>],
+    col_adj: &[Vec<usize>],
+    col: usize,
+) -> Vec<Option<usize>> {
+    let mut check_distance = vec![None; row_adj.len()];
+    let mut bit_distance = vec![None; col_adj.len()];
+    bit_distance[col] = Some(0);
+
+    let mut queue = VecDeque::new();
+    queue.push_back(col);
+    while let Some(bit) = queue.pop_front() {
+        let d = bit_distance[bit].unwrap();
+        for &check in &col_adj[bit] {
+            if check_distance[check].is_some() {
+                continue;
+            }
+            check_distance[check] = Some(d + 1);
+            for &next_bit in &row_adj[check] {
+                if bit_distance[next_bit].is_none() {
+                    bit_distance[next_bit] = Some(d + 2);
+                    queue.push_back(next_bit);
+                }
+            }
+        }
+    }
+
+    check_distance
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_random_ldpc_pcm_column_weight_and_girth() {
+        for (rows, cols, col_weight, min_girth) in [(20, 30, 3, 6), (60, 90, 3, 8)] {
+            let pcm = random_ldpc_pcm(rows, cols, col_weight, min_girth, 7);
+            assert_eq!(pcm.shape(), (rows, cols));
+            for col in 0..cols {
+                assert_eq!(pcm.nonzero_rows(col).len(), col_weight);
+            }
+            assert!(pcm.girth().is_none_or(|g| g >= min_girth));
+
+            // 行の重みはほぼ均一になる
+            let row_weights: Vec<usize> = pcm.row_adj().iter().map(|row| row.len()).collect();
+            let max_weight = *row_weights.iter().max().unwrap();
+            let min_weight = *row_weights.iter().min().unwrap();
+            assert!(max_weight - min_weight <= 1);
+        }
+    }
+
+    #[test]
+    fn test_random_ldpc_pcm_reproducible() {
+        let a = random_ldpc_pcm(10, 20, 3, 6, 1);
+        let b = random_ldpc_pcm(10, 20, 3, 6, 1);
+        assert_eq!(a, b);
+    }
+}
@@ -1,11 +1,16 @@
-use crate::code::paulis::Paulis;
-use crate::math::bit_linear_algebra::is_linearly_independent;
+use crate::Word;
+use crate::code::error_vector::ErrorVector;
+use crate::code::paulis::{Paulis, Phase};
+use crate::math::bit_linear_algebra::{inner_product, is_linearly_independent, rank};
+use crate::math::sparse_matrix::BinarySparseMatrix;
 
 use bitvec::prelude::*;
+use std::sync::OnceLock;
 
 /// スタビライザー群を表す構造体
 /// 量子ビット数と生成子のベクトルを持つ
 /// 生成子は互いに可換で独立である必要があり、コンストラクタでチェックする
+/// ランクや可換性の計算に使うbinary symplectic表現は構築時に一度だけ計算して保持する
 ///
 /// # Examples
 /// ```rust
@@ -26,21 +31,30 @@ use bitvec::prelude::*;
 #[derive(Debug, Clone)]
 pub struct StabilizerGroup {
     generators: Vec<Paulis>,
+    /// 生成子をbinary symplectic表現 (x | z) の行ベクトルとして並べたもの
+    symplectic_rows: Vec<BitVec<Word, Lsb0>>,
+    /// 生成子を標準形に簡約した独立な生成子。群の要素の列挙に使う
+    canonical_generators: Vec<Paulis>,
+    /// 標準形の生成子のbinary symplectic表現 (x | z) と、その先頭の1の位置（ピボット）の組
+    /// 簡約行階段形になっているので、`contains`で照会ベクトルを簡約するのに使う
+    /// 最初に必要になったときに一度だけ計算する
+    reduced_rows: OnceLock<Vec<(usize, BitVec<Word, Lsb0>)>>,
 }
 
 impl StabilizerGroup {
     pub fn new(generators: Vec<Paulis>) -> Self {
-        let mut z_part_vecs = Vec::<BitVec<u64, Lsb0>>::new();
-        let mut x_part_vecs = Vec::<BitVec<u64, Lsb0>>::new();
-        for generator in &generators {
-            z_part_vecs.push(generator.z_part().clone());
-            x_part_vecs.push(generator.x_part().clone());
-        }
+        let stabilizer_group = Self::from_redundant_generators(generators);
         assert!(
-            is_linearly_independent(&z_part_vecs) && is_linearly_independent(&x_part_vecs),
+            stabilizer_group.is_independent(),
             "演算子が独立ではありません"
         );
+        stabilizer_group
+    }
 
+    /// 生成子の独立性をチェックせずにスタビライザー群を構成する
+    /// 冗長な生成子を含む場合は`to_canonical`で独立な生成子に簡約できる
+    /// 生成子の積が-Iになる場合はスタビライザー群にならないのでパニックする
+    pub fn from_redundant_generators(generators: Vec<Paulis>) -> Self {
         for i in 0..generators.len() {
             for j in (i + 1)..generators.len() {
                 assert!(
@@ -54,7 +68,82 @@ impl StabilizerGroup {
             }
         }
 
-        Self { generators }
+        let symplectic_rows = generators
+            .iter()
+            .map(|generator| {
+                let mut row = generator.x_part().clone();
+                row.extend_from_bitslice(generator.z_part());
+                row
+            })
+            .collect();
+        let canonical_generators = reduce_generators(&generators);
+        Self {
+            generators,
+            symplectic_rows,
+            canonical_generators,
+            reduced_rows: OnceLock::new(),
+        }
+    }
+
+    /// 生成子のZ部分とX部分をそれぞれ行として並べた疎行列の組 (Z部分, X部分) を返す
+    /// i行目はi番目の生成子に対応し、どちらも (生成子の数) × (量子ビット数) の行列になる
+    ///
+    /// # Examples
+    /// ```rust
+    /// use qldpc_sim::code::paulis::Paulis;
+    /// use qldpc_sim::code::stabilizer::StabilizerGroup;
+    ///
+    /// let group = StabilizerGroup::new(vec![Paulis::from_string("XXI"), Paulis::from_string("ZZI")]);
+    /// let (z_matrix, x_matrix) = group.to_symplectic_matrix();
+    /// assert_eq!(z_matrix.row_adj(), &[vec![], vec![0, 1]]);
+    /// assert_eq!(x_matrix.row_adj(), &[vec![0, 1], vec![]]);
+    /// ```
+    pub fn to_symplectic_matrix(&self) -> (BinarySparseMatrix, BinarySparseMatrix) {
+        let num_qubits = self.num_qubits();
+        let num_generators = self.generators.len();
+        let z_row_adj = self
+            .generators
+            .iter()
+            .map(|generator| generator.z_part().iter_ones().collect())
+            .collect();
+        let x_row_adj = self
+            .generators
+            .iter()
+            .map(|generator| generator.x_part().iter_ones().collect())
+            .collect();
+        (
+            BinarySparseMatrix::from_row_adj(num_generators, num_qubits, z_row_adj),
+            BinarySparseMatrix::from_row_adj(num_generators, num_qubits, x_row_adj),
+        )
+    }
+
+    /// 生成子のシンプレクティック空間上でのランク（独立な生成子の数）を返す
+    pub fn rank(&self) -> usize {
+        rank(&self.symplectic_rows)
+    }
+
+    /// 生成子がシンプレクティック空間上で線形独立かどうかを判定する
+    pub fn is_independent(&self) -> bool {
+        is_linearly_independent(&self.symplectic_rows)
+    }
+
+    /// シンプレクティック表現 (x | z) 上のガウスの消去法で生成子を標準形に簡約する
+    /// 生成子同士の積で消去するので位相も正しく追跡され、冗長な生成子は取り除かれる
+    ///
+    /// # Examples
+    /// ```rust
+    /// use qldpc_sim::code::paulis::Paulis;
+    /// use qldpc_sim::code::stabilizer::StabilizerGroup;
+    ///
+    /// let s1 = Paulis::from_string("XXI");
+    /// let s2 = Paulis::from_string("IXX");
+    /// let s3 = Paulis::from_string("XIX"); // s1 * s2
+    /// let group = StabilizerGroup::from_redundant_generators(vec![s1, s2, s3]);
+    /// assert!(!group.is_independent());
+    /// assert_eq!(group.to_canonical().num_generators(), 2);
+    /// ```
+    pub fn to_canonical(&self) -> StabilizerGroup {
+        StabilizerGroup::new(self.canonical_generators.clone())
     }
 
     pub fn num_qubits(&self) -> usize {
@@ -69,37 +158,163 @@ impl StabilizerGroup {
         self.generators.len()
     }
 
+    /// 生成子のbinary symplectic表現 (x | z) を行ベクトルとして並べたものを返す
+    pub(crate) fn symplectic_rows(&self) -> &[BitVec<Word, Lsb0>] {
+        &self.symplectic_rows
+    }
+
+    /// 誤りに対するシンドロームを計算する
+    /// i番目の要素はi番目の生成子と誤りのシンプレクティック積で、trueは反可換であることを表す
+    ///
+    /// # Examples
+    /// ```rust
+    /// use qldpc_sim::code::error_vector::ErrorVector;
+    /// use qldpc_sim::code::paulis::Paulis;
+    /// use qldpc_sim::code::stabilizer::StabilizerGroup;
+    ///
+    /// let group = StabilizerGroup::new(vec![Paulis::from_string("ZZI"), Paulis::from_string("IZZ")]);
+    /// let error = ErrorVector::from_string("IXI");
+    /// assert_eq!(group.syndrome(&error), vec![true, true]);
+    /// ```
+    pub fn syndrome(&self, error: &ErrorVector) -> Vec<bool> {
+        assert_eq!(
+            error.num_qubits(),
+            self.num_qubits(),
+            "誤りの量子ビット数({})がスタビライザー群の量子ビット数({})と一致しません",
+            error.num_qubits(),
+            self.num_qubits()
+        );
+        // (x | z) と (x' | z') のシンプレクティック積は (x | z) と (z' | x') の内積
+        let mut swapped = error.z_part().clone();
+        swapped.extend_from_bitslice(error.x_part());
+        self.symplectic_rows
+            .iter()
+            .map(|row| inner_product(row, &swapped))
+            .collect()
+    }
+
+    /// 群の位数を返す。冗長な生成子は位数に寄与しない
     pub fn order(&self) -> usize {
-        1 << self.generators.len()
+        1 << self.rank()
     }
 
+    /// 群の要素を重複なく列挙する。冗長な生成子があっても、標準形の生成子の積だけを数える
     pub fn iter(&self) -> StabilizerGroupIterator {
         StabilizerGroupIterator {
-            stabilizer_group: self.clone(),
+            num_qubits: self.num_qubits(),
+            generators: self.canonical_generators.clone(),
             index: 0,
             total: self.order(),
         }
     }
 
-    pub fn include(&self, paulis: &Paulis) -> bool {
-        let mut z_part_vecs = Vec::<BitVec<u64, Lsb0>>::new();
-        z_part_vecs.push(paulis.z_part().clone());
-        let mut x_part_vecs = Vec::<BitVec<u64, Lsb0>>::new();
-        x_part_vecs.push(paulis.x_part().clone());
-        for generator in &self.generators {
-            z_part_vecs.push(generator.z_part().clone());
-            x_part_vecs.push(generator.x_part().clone());
+    /// Pauli演算子が（位相を除いて）スタビライザー群に含まれるかどうかを判定する
+    /// シンプレクティック表現 (x | z) が生成子の張るGF(2)上の空間に含まれるかを、
+    /// 標準形の生成子の簡約行階段形で照会ベクトルを簡約し、0になるかどうかで調べる
+    /// 誤りと復元操作の残差が純粋なスタビライザーか（訂正に成功したか）を確かめるのに使う
+    ///
+    /// # Examples
+    /// ```rust
+    /// use qldpc_sim::code::paulis::Paulis;
+    /// use qldpc_sim::code::stabilizer::StabilizerGroup;
+    ///
+    /// let group = StabilizerGroup::new(vec![Paulis::from_string("XXI"), Paulis::from_string("ZZI")]);
+    /// assert!(group.contains(&Paulis::from_string("-YYI")));
+    /// assert!(!group.contains(&Paulis::from_string("XII")));
+    /// ```
+    pub fn contains(&self, paulis: &Paulis) -> bool {
+        assert_eq!(
+            paulis.num_qubits(),
+            self.num_qubits(),
+            "Pauli演算子の量子ビット数({})がスタビライザー群の量子ビット数({})と一致しません",
+            paulis.num_qubits(),
+            self.num_qubits()
+        );
+        let mut vector = paulis.x_part().clone();
+        vector.extend_from_bitslice(paulis.z_part());
+        for (pivot, row) in self.reduced_rows() {
+            if vector[*pivot] {
+                vector ^= row;
+            }
         }
-        !is_linearly_independent(&z_part_vecs) && !is_linearly_independent(&x_part_vecs)
+        vector.not_any()
+    }
+
+    /// 標準形の生成子のbinary symplectic表現とピボットの組を返す
+    fn reduced_rows(&self) -> &[(usize, BitVec<Word, Lsb0>)] {
+        self.reduced_rows.get_or_init(|| {
+            self.canonical_generators
+                .iter()
+                .map(|generator| {
+                    let mut row = generator.x_part().clone();
+                    row.extend_from_bitslice(generator.z_part());
+                    let pivot = row
+                        .first_one()
+                        .expect("標準形の生成子が恒等演算子になっています");
+                    (pivot, row)
+                })
+                .collect()
+        })
+    }
+
+    /// `contains`と同じ判定を行う
+    pub fn include(&self, paulis: &Paulis) -> bool {
+        self.contains(paulis)
     }
 }
 
 pub struct StabilizerGroupIterator {
-    stabilizer_group: StabilizerGroup,
+    num_qubits: usize,
+    generators: Vec<Paulis>,
     index: usize,
     total: usize,
 }
 
+/// シンプレクティック表現 (x | z) 上のガウスの消去法で生成子を標準形に簡約する
+/// 生成子同士の積で消去するので位相も正しく追跡され、冗長な生成子は取り除かれる
+/// 冗長な生成子が-Iに簡約される場合はパニックする
+fn reduce_generators(generators: &[Paulis]) -> Vec<Paulis> {
+    let num_qubits = generators
+        .first()
+        .map_or(0, |generator| generator.num_qubits());
+    let mut rows = generators.to_vec();
+    let mut pivot_count = 0;
+
+    for col in 0..(2 * num_qubits) {
+        let bit = |paulis: &Paulis| {
+            if col < num_qubits {
+                paulis.x_part()[col]
+            } else {
+                paulis.z_part()[col - num_qubits]
+            }
+        };
+
+        let pivot_row = (pivot_count..rows.len()).find(|&row| bit(&rows[row]));
+
+        if let Some(pivot) = pivot_row {
+            rows.swap(pivot_count, pivot);
+
+            for row in 0..rows.len() {
+                if row != pivot_count && bit(&rows[row]) {
+                    rows[row] = &rows[row] * &rows[pivot_count];
+                }
+            }
+
+            pivot_count += 1;
+        }
+    }
+
+    // 残りの行は恒等演算子になる
+    for row in &rows[pivot_count..] {
+        assert!(
+            row.phase() == Phase::One,
+            "スタビライザー群が-Iを含んでいます"
+        );
+    }
+    rows.truncate(pivot_count);
+    rows
+}
+
 impl Iterator for StabilizerGroupIterator {
     type Item = Paulis;
 
@@ -108,9 +323,9 @@ impl Iterator for StabilizerGroupIterator {
             return None;
         }
 
-        let mut result = Paulis::identity(self.stabilizer_group.num_qubits());
+        let mut result = Paulis::identity(self.num_qubits);
 
-        for (gen_idx, generator) in self.stabilizer_group.generators().iter().enumerate() {
+        for (gen_idx, generator) in self.generators.iter().enumerate() {
             if (self.index >> gen_idx) & 1 == 1 {
                 result = &result * generator;
             }
@@ -134,6 +349,7 @@ impl ExactSizeIterator for StabilizerGroupIterator {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::code::named_codes::steane_code;
 
     #[test]
     fn test_stabilizer_new() {
@@ -165,6 +381,83 @@ mod tests {
         let _stabilizer_group = StabilizerGroup::new(vec![s1, s3, s4]);
     }
 
+    #[test]
+    fn test_stabilizer_new_css() {
+        // X型とZ型の生成子が混在していても、シンプレクティック表現上で独立なら構成できる
+        let s1 = Paulis::from_string("ZZI");
+        let s2 = Paulis::from_string("IZZ");
+        let s3 = Paulis::from_string("XXX");
+        let stabilizer_group = StabilizerGroup::new(vec![s1, s2, s3]);
+        assert!(stabilizer_group.is_independent());
+        assert_eq!(stabilizer_group.rank(), 3);
+    }
+
+    #[test]
+    fn test_stabilizer_to_canonical() {
+        let s1 = Paulis::from_string("XZZXI");
+        let s2 = Paulis::from_string("IXZZX");
+        let s3 = Paulis::from_string("XIXZZ");
+        let s4 = Paulis::from_string("ZXIXZ");
+        let redundant = &s1 * &s2;
+        let stabilizer_group =
+            StabilizerGroup::from_redundant_generators(vec![s1, s2, s3, s4, redundant]);
+        assert!(!stabilizer_group.is_independent());
+        assert_eq!(stabilizer_group.rank(), 4);
+
+        let n = stabilizer_group.num_qubits();
+        // 生成子の数から計算すると k = 0 になってしまう
+        assert_eq!(n - stabilizer_group.num_generators(), 0);
+
+        let canonical = stabilizer_group.to_canonical();
+        assert!(canonical.is_independent());
+        assert_eq!(canonical.num_generators(), 4);
+        assert_eq!(n - canonical.num_generators(), 1);
+        for generator in stabilizer_group.generators() {
+            assert!(canonical.include(generator));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "スタビライザー群が-Iを含んでいます")]
+    fn test_stabilizer_to_canonical_minus_identity() {
+        let s1 = Paulis::from_string("ZZ");
+        let s2 = Paulis::from_string("-ZZ");
+        let _canonical = StabilizerGroup::from_redundant_generators(vec![s1, s2]).to_canonical();
+    }
+
+    #[test]
+    #[should_panic(expected = "スタビライザー群が-Iを含んでいます")]
+    fn test_stabilizer_redundant_minus_identity() {
+        // XX * ZZ = -YY なので、3つの積は-Iになる
+        let generators = vec![
+            Paulis::from_string("XX"),
+            Paulis::from_string("ZZ"),
+            Paulis::from_string("YY"),
+        ];
+        let _stabilizer_group = StabilizerGroup::from_redundant_generators(generators);
+    }
+
+    #[test]
+    fn test_stabilizer_redundant_order_and_iterator() {
+        let s1 = Paulis::from_string("XZZXI");
+        let s2 = Paulis::from_string("IXZZX");
+        let s3 = Paulis::from_string("XIXZZ");
+        let s4 = Paulis::from_string("ZXIXZ");
+        let redundant = &s1 * &s2;
+        let stabilizer_group =
+            StabilizerGroup::from_redundant_generators(vec![s1, s2, s3, s4, redundant]);
+        assert_eq!(stabilizer_group.order(), 16);
+
+        let mut elements: Vec<Paulis> = Vec::new();
+        for paulis in stabilizer_group.iter() {
+            if !elements.contains(&paulis) {
+                elements.push(paulis);
+            }
+        }
+        assert_eq!(elements.len(), 16);
+        assert_eq!(stabilizer_group.iter().len(), 16);
+    }
+
     #[test]
     fn test_stabilizer_size() {
         let s1 = Paulis::from_string("XZZXI");
@@ -202,4 +495,108 @@ mod tests {
         assert!(stabilizer_group.include(&included_pauli));
         assert!(!stabilizer_group.include(&not_included_pauli));
     }
+
+    #[test]
+    fn test_stabilizer_contains() {
+        let s1 = Paulis::from_string("XZZXI");
+        let s2 = Paulis::from_string("IXZZX");
+        let s3 = Paulis::from_string("XIXZZ");
+        let s4 = Paulis::from_string("ZXIXZ");
+        let stabilizer_group = StabilizerGroup::new(vec![s1.clone(), s2.clone(), s3, s4]);
+
+        // 生成子の積は位相によらず含まれる
+        let product = &s1 * &s2;
+        assert!(stabilizer_group.contains(&product));
+        assert!(stabilizer_group.contains(&Paulis::from_string("-iXZZXI")));
+        assert!(stabilizer_group.contains(&Paulis::identity(5)));
+
+        // 5量子ビット符号の論理演算子 XXXXX は生成子と可換だが含まれない
+        let logical_x = Paulis::from_string("XXXXX");
+        assert!(
+            stabilizer_group
+                .generators()
+                .iter()
+                .all(|g| g.commutes(&logical_x))
+        );
+        assert!(!stabilizer_group.contains(&logical_x));
+    }
+
+    #[test]
+    fn test_stabilizer_contains_mixed_parts() {
+        let s1 = Paulis::from_string("XZZXI");
+        let s2 = Paulis::from_string("IXZZX");
+        let s3 = Paulis::from_string("XIXZZ");
+        let s4 = Paulis::from_string("ZXIXZ");
+        let stabilizer_group = StabilizerGroup::new(vec![s1, s2, s3, s4]);
+
+        // X部分は s1 の、Z部分は s2 のものだが、どの生成子の積とも一致しない
+        let mixed = Paulis::from_string("XIZYI");
+        assert!(!stabilizer_group.contains(&mixed));
+        assert!(!stabilizer_group.include(&mixed));
+    }
+
+    #[test]
+    fn test_stabilizer_contains_matches_iteration() {
+        // 冗長な生成子を含む群でも、全Pauli演算子について列挙した要素との一致で判定した結果と等しい
+        let group = StabilizerGroup::from_redundant_generators(vec![
+            Paulis::from_string("XXI"),
+            Paulis::from_string("IXX"),
+            Paulis::from_string("XIX"),
+            Paulis::from_string("ZZZ"),
+        ]);
+        let elements: Vec<Paulis> = group.iter().collect();
+        for index in 0..64 {
+            let label: String = (0..3)
+                .map(|q| ['I', 'X', 'Y', 'Z'][(index >> (2 * q)) & 3])
+                .collect();
+            let paulis = Paulis::from_string(&label);
+            let expected = elements
+                .iter()
+                .any(|e| e.x_part() == paulis.x_part() && e.z_part() == paulis.z_part());
+            assert_eq!(group.contains(&paulis), expected, "{}", label);
+        }
+    }
+
+    /// Steane符号のX型生成子3つとZ型生成子3つからなるスタビライザー群
+    fn steane_group() -> StabilizerGroup {
+        let steane = steane_code();
+        let zeros = bitvec![Word, Lsb0; 0; 7];
+        let x_generators = (0..steane.hx().rows())
+            .map(|i| Paulis::new(7, Phase::One, steane.hx().row(i), zeros.clone()));
+        let z_generators = (0..steane.hz().rows())
+            .map(|i| Paulis::new(7, Phase::One, zeros.clone(), steane.hz().row(i)));
+        StabilizerGroup::new(x_generators.chain(z_generators).collect())
+    }
+
+    #[test]
+    fn test_stabilizer_to_symplectic_matrix_steane() {
+        let steane = steane_code();
+        let group = steane_group();
+
+        let (z_matrix, x_matrix) = group.to_symplectic_matrix();
+        assert_eq!(z_matrix.shape(), (6, 7));
+        assert_eq!(x_matrix.shape(), (6, 7));
+        assert_eq!(&x_matrix.row_adj()[..3], steane.hx().row_adj());
+        assert_eq!(&z_matrix.row_adj()[3..], steane.hz().row_adj());
+        assert!(x_matrix.row_adj()[3..].iter().all(|row| row.is_empty()));
+        assert!(z_matrix.row_adj()[..3].iter().all(|row| row.is_empty()));
+    }
+
+    #[test]
+    fn test_stabilizer_syndrome_matches_css_code() {
+        let steane = steane_code();
+        let group = steane_group();
+
+        // X型の生成子はZ誤りを、Z型の生成子はX誤りを検出する
+        for error in ErrorVector::enumerate_up_to_weight(7, 2) {
+            let css_syndrome = steane.syndrome(&error);
+            let expected: Vec<bool> = css_syndrome
+                .x_syndrome()
+                .iter()
+                .chain(css_syndrome.z_syndrome().iter())
+                .map(|bit| *bit)
+                .collect();
+            assert_eq!(group.syndrome(&error), expected);
+        }
+    }
 }
@@ -1,6 +1,11 @@
-use crate::code::paulis::Paulis;
+use crate::Word;
+use crate::code::error_vector::ErrorVector;
+use crate::code::paulis::{Paulis, Phase};
 use crate::code::stabilizer::StabilizerGroup;
 use crate::code::traits::QuantumCode;
+use crate::math::bit_linear_algebra::{BinaryDenseMatrix, inner_product, rank};
+use bitvec::prelude::*;
+use std::sync::OnceLock;
 
 /// スタビライザー符号を表す構造体
 /// スタビライザー群を持ち、符号のパラメータ(n, k)を計算するメソッドを提供する
@@ -26,6 +31,9 @@ use crate::code::traits::QuantumCode;
 pub struct StabilizerCode {
     code_name: String,
     stabilizer_group: StabilizerGroup,
+    /// 初回の呼び出しで計算した論理演算子の組 (L_X, L_Z)
+    /// スタビライザー群は構築後に変更されないので、一度計算した値をそのまま使える
+    logicals: OnceLock<(Vec<Paulis>, Vec<Paulis>)>,
 }
 
 impl StabilizerCode {
@@ -33,11 +41,14 @@ impl StabilizerCode {
         Self {
             code_name,
             stabilizer_group,
+            logicals: OnceLock::new(),
         }
     }
 
+    /// 生成子のリストからスタビライザー符号を構成する
+    /// 生成子は冗長（線形従属）でもよく、kはシンプレクティックランクから計算される
     pub fn from_generators(code_name: &str, generators: Vec<Paulis>) -> Self {
-        let stabilizer_group = StabilizerGroup::new(generators);
+        let stabilizer_group = StabilizerGroup::from_redundant_generators(generators);
         Self::new(code_name.to_string(), stabilizer_group)
     }
 
@@ -48,6 +59,133 @@ impl StabilizerCode {
     pub fn stabilizer_group(&self) -> &StabilizerGroup {
         &self.stabilizer_group
     }
+
+    /// 誤りに対するシンドロームを計算する
+    /// i番目の要素はi番目の生成子と誤りのシンプレクティック積で、trueは反可換であることを表す
+    pub fn syndrome(&self, error: &ErrorVector) -> Vec<bool> {
+        self.stabilizer_group.syndrome(error)
+    }
+
+    /// 論理X演算子のリストを返す
+    /// i番目は`lz`のi番目とだけ反可換になるように選ばれる
+    pub fn lx(&self) -> Vec<Paulis> {
+        self.logical_operators().0.clone()
+    }
+
+    /// 論理Z演算子のリストを返す
+    /// i番目は`lx`のi番目とだけ反可換になるように選ばれる
+    pub fn lz(&self) -> Vec<Paulis> {
+        self.logical_operators().1.clone()
+    }
+
+    /// 誤りを作用させた後に論理演算子を測定したとき、測定値が反転するかどうかを返す
+    /// 結果は`lx`の各演算子に対する結果に続けて`lz`の各演算子に対する結果を並べた長さ2kのベクトル
+    /// trueはその論理演算子と誤りが反可換であることを表す
+    ///
+    /// # Examples
+    /// ```rust
+    /// use qldpc_sim::code::error_vector::ErrorVector;
+    /// use qldpc_sim::code::paulis::Paulis;
+    /// use qldpc_sim::code::stabilizer_code::StabilizerCode;
+    ///
+    /// let generators = vec![
+    ///     Paulis::from_string("XZZXI"),
+    ///     Paulis::from_string("IXZZX"),
+    ///     Paulis::from_string("XIXZZ"),
+    ///     Paulis::from_string("ZXIXZ"),
+    /// ];
+    /// let code = StabilizerCode::from_generators("FiveQubit", generators);
+    /// let error = ErrorVector::from_string("XZZXI");
+    /// assert_eq!(code.measure_logicals(&error), vec![false, false]);
+    /// ```
+    pub fn measure_logicals(&self, error: &ErrorVector) -> Vec<bool> {
+        let (lx, lz) = self.logical_operators();
+        lx.iter()
+            .chain(lz.iter())
+            .map(|logical| !error.commutes_with(logical))
+            .collect()
+    }
+
+    /// 論理演算子の組 (L_X, L_Z) を返す。初回の呼び出しで計算し、以降はキャッシュした値を返す
+    fn logical_operators(&self) -> &(Vec<Paulis>, Vec<Paulis>) {
+        self.logicals
+            .get_or_init(|| self.compute_logical_operators())
+    }
+
+    /// シンプレクティック表現 (x | z) 上で論理演算子の組 (L_X, L_Z) を計算する
+    /// スタビライザー群の正規化群からスタビライザーに含まれない基底を取り出し、
+    /// シンプレクティック・グラム・シュミット法で反可換な組に分ける
+    fn compute_logical_operators(&self) -> (Vec<Paulis>, Vec<Paulis>) {
+        let n = self.n();
+        let symplectic_rows = self.stabilizer_group.symplectic_rows();
+
+        // v = (x | z) が生成子 s と可換 ⇔ x・s_z + z・s_x = 0
+        let commutation_rows: Vec<BitVec<Word, Lsb0>> = symplectic_rows
+            .iter()
+            .map(|row| {
+                let (x_part, z_part) = row.split_at(n);
+                let mut swapped = z_part.to_bitvec();
+                swapped.extend_from_bitslice(x_part);
+                swapped
+            })
+            .collect();
+        let normalizer = BinaryDenseMatrix::new(commutation_rows).nullspace();
+
+        // 正規化群の基底のうち、スタビライザーと合わせて線形独立なものを候補とする
+        let mut span = symplectic_rows.to_vec();
+        let mut current_rank = rank(&span);
+        let mut candidates = Vec::new();
+        for vector in normalizer.get_data() {
+            span.push(vector.clone());
+            let new_rank = rank(&span);
+            if new_rank > current_rank {
+                current_rank = new_rank;
+                candidates.push(vector.clone());
+            } else {
+                span.pop();
+            }
+        }
+
+        let mut lx = Vec::new();
+        let mut lz = Vec::new();
+        while let Some(x_vector) = candidates.pop() {
+            let partner_idx = candidates
+                .iter()
+                .position(|vector| symplectic_product(&x_vector, vector, n))
+                .expect("反可換な論理演算子の組が見つかりません");
+            let z_vector = candidates.swap_remove(partner_idx);
+
+            // 残りの候補を x_vector, z_vector の両方と可換になるように修正する
+            for vector in candidates.iter_mut() {
+                let with_x = symplectic_product(vector, &x_vector, n);
+                let with_z = symplectic_product(vector, &z_vector, n);
+                if with_z {
+                    *vector ^= &x_vector;
+                }
+                if with_x {
+                    *vector ^= &z_vector;
+                }
+            }
+
+            lx.push(symplectic_to_paulis(&x_vector, n));
+            lz.push(symplectic_to_paulis(&z_vector, n));
+        }
+
+        (lx, lz)
+    }
+}
+
+/// シンプレクティック表現 (x | z) 同士のシンプレクティック積を計算する
+fn symplectic_product(a: &BitVec<Word, Lsb0>, b: &BitVec<Word, Lsb0>, n: usize) -> bool {
+    let (a_x, a_z) = a.split_at(n);
+    let (b_x, b_z) = b.split_at(n);
+    inner_product(&a_x.to_bitvec(), &b_z.to_bitvec())
+        ^ inner_product(&a_z.to_bitvec(), &b_x.to_bitvec())
+}
+
+fn symplectic_to_paulis(vector: &BitVec<Word, Lsb0>, n: usize) -> Paulis {
+    let (x_part, z_part) = vector.split_at(n);
+    Paulis::new(n, Phase::One, x_part.to_bitvec(), z_part.to_bitvec())
 }
 
 impl QuantumCode for StabilizerCode {
@@ -61,7 +199,7 @@ impl QuantumCode for StabilizerCode {
 
     fn k(&self) -> usize {
         let n = self.n();
-        let r = self.num_stabilizers();
+        let r = self.stabilizer_group.rank();
         n - r
     }
 }
@@ -69,6 +207,7 @@ impl QuantumCode for StabilizerCode {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::code::named_codes::five_qubit_code;
 
     #[test]
     fn test_stabilizer_code_new() {
@@ -83,4 +222,112 @@ mod tests {
         assert_eq!(stabilizer_code.k(), 1);
         assert_eq!(stabilizer_code.num_stabilizers(), 4);
     }
+
+    #[test]
+    fn test_stabilizer_code_redundant_generators() {
+        let s1 = Paulis::from_string("XZZXI");
+        let s2 = Paulis::from_string("IXZZX");
+        let s3 = Paulis::from_string("XIXZZ");
+        let s4 = Paulis::from_string("ZXIXZ");
+        let product = &(&(&s1 * &s2) * &s3) * &s4;
+        let generators = vec![s1, s2, s3, s4, product];
+        let stabilizer_code = StabilizerCode::from_generators("RedundantCode", generators);
+        assert_eq!(stabilizer_code.n(), 5);
+        assert_eq!(stabilizer_code.num_stabilizers(), 5);
+        assert_eq!(stabilizer_code.k(), 1);
+
+        // 冗長な生成子は群の位数や要素の列挙に影響しない
+        let group = stabilizer_code.stabilizer_group();
+        assert_eq!(group.order(), 16);
+        assert_eq!(group.iter().count(), 16);
+    }
+
+    #[test]
+    fn test_stabilizer_code_logical_operators() {
+        let code = five_qubit_code();
+        let lx = code.lx();
+        let lz = code.lz();
+        assert_eq!(lx.len(), 1);
+        assert_eq!(lz.len(), 1);
+
+        for generator in code.stabilizer_group().generators() {
+            assert!(lx[0].commutes(generator));
+            assert!(lz[0].commutes(generator));
+        }
+        assert!(!lx[0].commutes(&lz[0]));
+
+        // 2回目以降はキャッシュした同じ演算子を返す
+        assert_eq!(code.lx(), lx);
+        assert_eq!(code.clone().lz(), lz);
+    }
+
+    #[test]
+    fn test_stabilizer_code_measure_logicals() {
+        let code = five_qubit_code();
+        let lx = code.lx();
+        let lz = code.lz();
+
+        // 論理演算子そのものを誤りとして作用させると、対になる論理演算子だけが反転する
+        assert_eq!(
+            code.measure_logicals(&lx[0].to_error_vector()),
+            vec![false, true]
+        );
+        assert_eq!(
+            code.measure_logicals(&lz[0].to_error_vector()),
+            vec![true, false]
+        );
+
+        // ZZZZZは非自明な論理演算子なので、いずれかの論理測定を反転させる
+        let logical_error = ErrorVector::from_string("ZZZZZ");
+        assert_ne!(code.measure_logicals(&logical_error), vec![false, false]);
+
+        // 5量子ビット符号の論理演算子は、スタビライザーを除いて ZZZZZ と XXXXX に等しい
+        let equivalent_lx = Paulis::from_string("ZZZZZ");
+        let equivalent_lz = Paulis::from_string("XXXXX");
+        assert!(code.stabilizer_group().contains(&(&lx[0] * &equivalent_lx)));
+        assert!(code.stabilizer_group().contains(&(&lz[0] * &equivalent_lz)));
+
+        // ZIIII は ZZZZZ と可換、XXXXX と反可換なので、2番目の論理測定だけが反転する
+        // 測定結果は、スタビライザーを掛けても変わらない
+        let single_error = Paulis::from_string("ZIIII");
+        let expected = vec![
+            !single_error.commutes(&equivalent_lx),
+            !single_error.commutes(&equivalent_lz),
+        ];
+        assert_eq!(expected, vec![false, true]);
+        assert_eq!(
+            code.measure_logicals(&single_error.to_error_vector()),
+            expected
+        );
+        for generator in code.stabilizer_group().generators() {
+            let shifted = &single_error * generator;
+            assert_eq!(code.measure_logicals(&shifted.to_error_vector()), expected);
+        }
+    }
+
+    #[test]
+    fn test_five_qubit_code_syndrome() {
+        let code = five_qubit_code();
+        assert_eq!(
+            code.syndrome(&ErrorVector::from_string("XIIII")),
+            vec![false, false, false, true]
+        );
+        assert_eq!(
+            code.syndrome(&ErrorVector::from_string("IIZII")),
+            vec![false, false, true, false]
+        );
+
+        // 完全符号なので、単一量子ビット誤り15個のシンドロームは全て異なり、自明でない
+        let syndromes: Vec<Vec<bool>> = ErrorVector::enumerate_up_to_weight(5, 1)
+            .map(|error| code.syndrome(&error))
+            .collect();
+        assert_eq!(syndromes.len(), 16);
+        assert!(syndromes[0].iter().all(|&bit| !bit));
+        for i in 1..16 {
+            assert!(syndromes[i].iter().any(|&bit| bit));
+            for j in (i + 1)..16 {
+                assert_ne!(syndromes[i], syndromes[j]);
+            }
+        }
+    }
 }
@@ -0,0 +1,117 @@
+use crate::code::css_code::CssCode;
+use crate::math::sparse_matrix::BinarySparseMatrix;
+
+/// 回転表面符号を構成する
+/// d×d の格子点 (r, c) に量子ビット r * d + c を置き、格子の面（と境界の辺）にスタビライザーを置く
+/// 面の左上の角を (i, j) (0 <= i, j <= d) で表し、i + j が偶数ならX型、奇数ならZ型とする
+/// 内部の面は重み4、境界では上下の辺にX型、左右の辺にZ型の重み2のスタビライザーだけを残す
+/// 符号のパラメータは [[d^2, 1, d]] で、同じ距離の（回転していない）表面符号より量子ビットが少ない
+///
+/// # Examples
+/// ```rust
+/// use qldpc_sim::code::surface_code::rotated_surface_code;
+/// use qldpc_sim::code::traits::QuantumCode;
+///
+/// let code = rotated_surface_code(3);
+/// assert_eq!(code.n(), 9);
+/// assert_eq!(code.k(), 1);
+/// ```
+pub fn rotated_surface_code(distance: usize) -> CssCode {
+    assert!(
+        distance >= 2,
+        "符号距離は2以上である必要があります: {}",
+        distance
+    );
+    let d = distance;
+
+    let mut x_checks = Vec::new();
+    let mut z_checks = Vec::new();
+    for i in 0..=d {
+        for j in 0..=d {
+            let is_x_type = (i + j).is_multiple_of(2);
+            let on_top_or_bottom = i == 0 || i == d;
+            let on_left_or_right = j == 0 || j == d;
+            // 角の面は1量子ビットにしか作用しないので置かない
+            if on_top_or_bottom && on_left_or_right {
+                continue;
+            }
+            if (on_top_or_bottom && !is_x_type) || (on_left_or_right && is_x_type) {
+                continue;
+            }
+
+            // 面 (i, j) は量子ビット (i - 1, j - 1), (i - 1, j), (i, j - 1), (i, j) のうち格子内のものに作用する
+            let support: Vec<usize> = [(i, j), (i, j + 1), (i + 1, j), (i + 1, j + 1)]
+                .iter()
+                .filter(|&&(r, c)| (1..=d).contains(&r) && (1..=d).contains(&c))
+                .map(|&(r, c)| (r - 1) * d + (c - 1))
+                .collect();
+            if is_x_type {
+                x_checks.push(support);
+            } else {
+                z_checks.push(support);
+            }
+        }
+    }
+
+    let n = d * d;
+    let hx = BinarySparseMatrix::from_row_adj(x_checks.len(), n, x_checks);
+    let hz = BinarySparseMatrix::from_row_adj(z_checks.len(), n, z_checks);
+
+    let code_name = format!("RotatedSurfaceCode(d={})", distance);
+    CssCode::from_parity_check_matrices(&code_name, hz, hx)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::code::traits::QuantumCode;
+
+    #[test]
+    fn test_rotated_surface_code_parameters() {
+        for distance in [3, 5] {
+            let code = rotated_surface_code(distance);
+            assert_eq!(code.n(), distance * distance);
+            assert_eq!(code.k(), 1);
+            assert!(code.hx().is_orthogonal_to(code.hz()));
+            assert_eq!(code.hx().rows(), (distance * distance - 1) / 2);
+            assert_eq!(code.hz().rows(), (distance * distance - 1) / 2);
+
+            // 内部の面は重み4、境界は重み2
+            let weights = code
+                .hx()
+                .row_adj()
+                .iter()
+                .chain(code.hz().row_adj())
+                .map(|row| row.len());
+            let num_boundary = weights.clone().filter(|&w| w == 2).count();
+            assert!(weights.clone().all(|w| w == 2 || w == 4));
+            assert_eq!(num_boundary, 2 * (distance - 1));
+
+            // 最小重みの論理演算子の重みが符号距離になる
+            let enumerator = code.logical_weight_enumerator();
+            let min_weight = enumerator.iter().position(|&count| count > 0);
+            assert_eq!(min_weight, Some(distance));
+        }
+    }
+
+    #[test]
+    fn test_rotated_surface_code_d3_checks() {
+        let code = rotated_surface_code(3);
+        assert_eq!(
+            code.hx().row_adj(),
+            &[vec![1, 2], vec![0, 1, 3, 4], vec![4, 5, 7, 8], vec![6, 7]]
+        );
+        assert_eq!(
+            code.hz().row_adj(),
+            &[vec![0, 3], vec![1, 2, 4, 5], vec![3, 4, 6, 7], vec![5, 8]]
+        );
+    }
+
+    #[test]
+    fn test_rotated_surface_code_even_distance() {
+        let code = rotated_surface_code(4);
+        assert_eq!(code.n(), 16);
+        assert_eq!(code.k(), 1);
+        assert!(code.hx().is_orthogonal_to(code.hz()));
+    }
+}
@@ -0,0 +1,172 @@
+use crate::code::css_code::CssCode;
+use crate::code::error_vector::{ErrorVector, Syndrome};
+use crate::decoder::traits::Decoder;
+use crate::math::sparse_matrix::BinarySparseMatrix;
+
+/// Gallagerのビット反転（bit-flipping）による硬判定復号器
+/// 満たされていないチェックに最も多く含まれるビットを1反復につき1つ反転させ、
+/// シンドロームが一致するか反復回数の上限に達するまで繰り返す
+/// 同数のビットが複数あるときは、満たされているチェックが少ないもの、さらにインデックスが小さいものを選ぶ
+#[derive(Clone)]
+pub struct BitFlipDecoder {
+    pcm: BinarySparseMatrix,
+    maximum_iterations: usize,
+    converge: bool,
+    iterations: usize,
+}
+
+impl BitFlipDecoder {
+    pub fn new(pcm: BinarySparseMatrix, max_iterations: usize) -> Self {
+        Self {
+            pcm,
+            maximum_iterations: max_iterations,
+            converge: false,
+            iterations: 0,
+        }
+    }
+
+    pub fn parity_check_matrix(&self) -> &BinarySparseMatrix {
+        &self.pcm
+    }
+
+    pub fn converge(&self) -> bool {
+        self.converge
+    }
+
+    pub fn iterations(&self) -> usize {
+        self.iterations
+    }
+
+    pub fn decode(&mut self, syndrome: &[u8]) -> Vec<u8> {
+        assert_eq!(
+            syndrome.len(),
+            self.pcm.rows(),
+            "シンドロームの長さ({})が検査行列の行数({})と一致しません",
+            syndrome.len(),
+            self.pcm.rows()
+        );
+
+        let mut decoding = vec![0u8; self.pcm.cols()];
+        let mut unsatisfied: Vec<bool> = syndrome.iter().map(|&bit| bit != 0).collect();
+        self.converge = false;
+        self.iterations = 0;
+
+        while unsatisfied.iter().any(|&u| u) {
+            if self.iterations == self.maximum_iterations {
+                return decoding;
+            }
+            self.iterations += 1;
+
+            let Some(bit_idx) = self.most_unsatisfied_bit(&unsatisfied) else {
+                return decoding;
+            };
+            decoding[bit_idx] ^= 1;
+            for &check_idx in self.pcm.nonzero_rows(bit_idx) {
+                unsatisfied[check_idx] = !unsatisfied[check_idx];
+            }
+        }
+
+        self.converge = true;
+        decoding
+    }
+
+    /// 満たされていないチェックに最も多く含まれるビットを返す
+    fn most_unsatisfied_bit(&self, unsatisfied: &[bool]) -> Option<usize> {
+        (0..self.pcm.cols())
+            .map(|bit_idx| {
+                let checks = self.pcm.nonzero_rows(bit_idx);
+                let num_unsatisfied = checks.iter().filter(|&&c| unsatisfied[c]).count();
+                (bit_idx, num_unsatisfied, checks.len() - num_unsatisfied)
+            })
+            .filter(|&(_, num_unsatisfied, _)| num_unsatisfied > 0)
+            .min_by_key(|&(bit_idx, num_unsatisfied, num_satisfied)| {
+                (std::cmp::Reverse(num_unsatisfied), num_satisfied, bit_idx)
+            })
+            .map(|(bit_idx, _, _)| bit_idx)
+    }
+}
+
+/// CSS符号に対するビット反転復号器
+/// X誤りとZ誤りを独立に、2つの古典ビット反転復号器で復号する
+#[derive(Clone)]
+pub struct BitFlipDecoderCss {
+    /// H_X を検査行列に持ち、X型スタビライザーのシンドロームからZ誤りを推定する
+    decoder_x: BitFlipDecoder,
+    /// H_Z を検査行列に持ち、Z型スタビライザーのシンドロームからX誤りを推定する
+    decoder_z: BitFlipDecoder,
+}
+
+impl BitFlipDecoderCss {
+    pub fn new(code: &CssCode, max_iterations: usize) -> Self {
+        Self {
+            decoder_x: BitFlipDecoder::new(code.hx().clone(), max_iterations),
+            decoder_z: BitFlipDecoder::new(code.hz().clone(), max_iterations),
+        }
+    }
+}
+
+impl Decoder for BitFlipDecoderCss {
+    fn name(&self) -> &str {
+        "Bit-flipping Decoder for CSS Codes"
+    }
+
+    fn decode(&mut self, syndrome: &Syndrome) -> ErrorVector {
+        let error_z = self.decoder_x.decode(&syndrome.x_syndrome_u8());
+        let error_x = self.decoder_z.decode(&syndrome.z_syndrome_u8());
+        ErrorVector::from_u8vec(error_x, error_z)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::code::named_codes::shor_code;
+
+    fn repetition_pcm(n: usize) -> BinarySparseMatrix {
+        let row_adj = (0..n - 1).map(|i| vec![i, i + 1]).collect();
+        BinarySparseMatrix::from_row_adj(n - 1, n, row_adj)
+    }
+
+    #[test]
+    fn test_bit_flip_single_error_one_pass() {
+        let pcm = repetition_pcm(5);
+        let mut decoder = BitFlipDecoder::new(pcm.clone(), 10);
+        for i in 0..5 {
+            let mut error = vec![0; 5];
+            error[i] = 1;
+            let syndrome = &pcm * &error;
+            assert_eq!(decoder.decode(&syndrome), error);
+            assert!(decoder.converge());
+            assert_eq!(decoder.iterations(), 1);
+        }
+    }
+
+    #[test]
+    fn test_bit_flip_iteration_cap() {
+        let pcm = repetition_pcm(5);
+        let mut decoder = BitFlipDecoder::new(pcm.clone(), 1);
+        let syndrome = &pcm * &vec![1, 0, 0, 0, 1];
+        decoder.decode(&syndrome);
+        assert!(!decoder.converge());
+        assert_eq!(decoder.iterations(), 1);
+    }
+
+    #[test]
+    fn test_bit_flip_css_shor_code() {
+        let code = shor_code();
+        let mut decoder = BitFlipDecoderCss::new(&code, 9);
+        let lx = code.lx();
+        let lz = code.lz();
+
+        for error in ErrorVector::enumerate_up_to_weight(9, 1) {
+            let syndrome = code.syndrome(&error);
+            let decoded = decoder.decode(&syndrome);
+
+            // 残差がスタビライザーに含まれれば訂正成功
+            let residual = &decoded ^ &error;
+            assert!(code.syndrome(&residual).is_trivial());
+            assert!((&lz * residual.x_part()).not_any());
+            assert!((&lx * residual.z_part()).not_any());
+        }
+    }
+}
@@ -1,10 +1,32 @@
+use crate::channel::detector_error_model::DetectorErrorModel;
+use crate::channel::traits::ErrorChannel;
+use crate::code::classical::ClassicalCode;
 use crate::math::sparse_matrix::BinarySparseMatrix;
 
 use rand::rng;
+use rand::rngs::StdRng;
 use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
 use std::cmp::Ordering;
 use std::collections::HashMap;
 
+/// 固定したビットに与えるLLR
+/// チェックノードからのメッセージの和で符号が反転しない程度に大きく、tanhやmin-sumの計算で
+/// infやNaNを生じない有限の値にする
+const FROZEN_BIT_LLR: f64 = 1e6;
+
+/// 事前確率をクランプする範囲 (ε, 1-ε)
+/// p = 0 や p = 1 をそのままLLRにすると±infになり、メッセージ更新でNaNが発生するため
+const PROBABILITY_EPSILON: f64 = 1e-15;
+
+/// `soft_decision`で出力するLLRの絶対値の上限
+/// 固定したビットや確率0のビットのLLRは非常に大きくなるため、外側の復号器で扱いやすい範囲に収める
+const SOFT_DECISION_LLR_LIMIT: f64 = 50.0;
+
+/// LLRとメッセージをクランプする範囲の既定値
+/// 無限大にしておくと、`set_llr_clamp`を呼ばない限り従来と同じ結果になる
+const DEFAULT_LLR_CLAMP: f64 = f64::INFINITY;
+
 #[derive(PartialEq, Clone, Copy)]
 pub enum BpMethod {
     ProductSum = 0,
@@ -18,6 +40,7 @@ pub enum BpSchedule {
     SerialRelative = 2,
 }
 // 行列のエントリー（エッジ）を表す構造体の想定
+#[derive(Clone)]
 pub struct BpEntry {
     pub row_index: usize,
     pub col_index: usize,
@@ -26,6 +49,7 @@ pub struct BpEntry {
 }
 
 // 疎行列構造体の想定（C++のBpSparse相当）
+#[derive(Clone)]
 pub struct BpSparse {
     parity_check_matrix: BinarySparseMatrix,
     entries: HashMap<(usize, usize), BpEntry>, // (row, col) -> BpEntry
@@ -144,6 +168,7 @@ impl BpSparse {
     }
 }
 
+#[derive(Clone)]
 pub struct BpDecoder {
     pcm: BpSparse,
     bit_count: usize,
@@ -160,6 +185,16 @@ pub struct BpDecoder {
     converge: bool,
     iterations: usize,
     serial_schedule_order: Vec<usize>,
+    perturbation_epsilon: f64,
+    perturbation_restarts: usize,
+    /// 事前確率の摂動に使う乱数生成器。`set_random_perturbation`で指定したシードで初期化する
+    perturbation_rng: StdRng,
+    record_history: bool,
+    history: Vec<(usize, f64)>,
+    /// 誤りがないことが分かっているため、復号結果を0に固定するビット
+    frozen_bits: Vec<bool>,
+    /// 反復ごとにLLRとメッセージをクランプする範囲 [-llr_clamp, llr_clamp]
+    llr_clamp: f64,
     // rng_list_shuffle: rand::seq::SliceRandom, // 乱数シャッフル用
 }
 
@@ -201,15 +236,190 @@ impl BpDecoder {
             converge: false,
             iterations: 0,
             serial_schedule_order,
+            perturbation_epsilon: 0.0,
+            perturbation_restarts: 0,
+            perturbation_rng: StdRng::seed_from_u64(0),
+            record_history: false,
+            history: Vec::new(),
+            frozen_bits: vec![false; bit_count],
+            llr_clamp: DEFAULT_LLR_CLAMP,
         }
     }
 
+    /// 古典符号とビット反転チャネルからBP復号器を構成する
+    /// 各ビットの事前確率にはチャネルのX誤り率とY誤り率の和（ビット反転の確率）を使う
+    pub fn from_classical_code<C: ErrorChannel>(
+        code: &ClassicalCode,
+        error_channel: &C,
+        bp_method: BpMethod,
+        schedule: BpSchedule,
+        max_iterations: usize,
+        ms_scaling_factor: f64,
+        random_serial_schedule: bool,
+    ) -> Self {
+        let error_rate = error_channel.x_error_rate() + error_channel.y_error_rate();
+        Self::from_pcm(
+            code.pcm().clone(),
+            bp_method,
+            schedule,
+            max_iterations,
+            ms_scaling_factor,
+            random_serial_schedule,
+            vec![error_rate; code.n()],
+        )
+    }
+
+    /// 検出器誤りモデルからBP復号器を構成する
+    /// 検出器を行、誤りを列とする検査行列を使い、各列の事前確率にはモデルの誤りの発生確率をそのまま使う
+    /// 復号結果のj番目のビットは、j番目の誤りが起こったと推定したかどうかを表す
+    pub fn from_detector_error_model(
+        dem: &DetectorErrorModel,
+        bp_method: BpMethod,
+        schedule: BpSchedule,
+        max_iterations: usize,
+        ms_scaling_factor: f64,
+        random_serial_schedule: bool,
+    ) -> Self {
+        Self::from_pcm(
+            dem.check_matrix(),
+            bp_method,
+            schedule,
+            max_iterations,
+            ms_scaling_factor,
+            random_serial_schedule,
+            dem.probabilities(),
+        )
+    }
+
+    /// 各ビットの事前誤り確率を置き換える
+    /// 検査行列のデータ構造はそのまま再利用するので、誤り率を変えながら復号器を使い回せる
+    pub fn set_channel_probabilities(&mut self, channel_probabilities: Vec<f64>) {
+        assert_eq!(
+            channel_probabilities.len(),
+            self.bit_count,
+            "channel_probabilitiesの長さ({})がビット数({})と一致しません",
+            channel_probabilities.len(),
+            self.bit_count
+        );
+        self.channel_probabilities = channel_probabilities;
+    }
+
+    /// 直前の復号で推定結果がシンドロームと一致したかどうかを返す
+    pub fn converge(&self) -> bool {
+        self.converge
+    }
+
+    /// 直前の復号で実行した反復回数を返す
+    pub fn iterations(&self) -> usize {
+        self.iterations
+    }
+
+    /// 反復ごとの診断情報の記録を有効にする
+    pub fn enable_history(&mut self) {
+        self.record_history = true;
+    }
+
+    /// 直前の復号における反復ごとの(満たされていないチェックの数, LLRの絶対値の最小値)を返す
+    /// `enable_history`を呼んでいない場合は空になる
+    pub fn history(&self) -> &[(usize, f64)] {
+        &self.history
+    }
+
+    /// 直前の復号における各ビットの事後対数尤度比(LLR)を返す
+    /// LLR = ln(P(e_i = 0) / P(e_i = 1)) なので、負の値はそのビットに誤りがある可能性が高いことを表す
+    pub fn log_prob_ratios(&self) -> &[f64] {
+        &self.log_prob_ratios
+    }
+
+    /// 直前の復号における各ビットの事後LLRを±`SOFT_DECISION_LLR_LIMIT`の範囲にクランプして返す
+    /// 連接符号の内側の復号器として使い、外側の復号器に硬判定ではなく確率の情報を渡すのに使う
+    /// 符号は`log_prob_ratios`と同じで、負の値はそのビットに誤りがある可能性が高いことを表す
+    pub fn soft_decision(&self) -> Vec<f64> {
+        self.log_prob_ratios
+            .iter()
+            .map(|llr| llr.clamp(-SOFT_DECISION_LLR_LIMIT, SOFT_DECISION_LLR_LIMIT))
+            .collect()
+    }
+
+    fn record_iteration(&mut self, syndrome: &[u8]) {
+        if !self.record_history {
+            return;
+        }
+        let unsatisfied_checks = self
+            .candidate_syndrome
+            .iter()
+            .zip(syndrome)
+            .filter(|(a, b)| a != b)
+            .count();
+        let min_abs_llr = self
+            .log_prob_ratios
+            .iter()
+            .map(|llr| llr.abs())
+            .fold(f64::INFINITY, f64::min);
+        self.history.push((unsatisfied_checks, min_abs_llr));
+    }
+
+    /// BPが収束しなかったときに、事前確率をランダムに摂動させて再実行する設定を行う
+    /// 各量子ビットの事前確率 p を p * (1 + epsilon * u) (u は [-1, 1] の一様乱数) に置き換え、
+    /// 最大 max_restarts 回まで再実行して最初に収束した結果を返す
+    /// 確率が0または1のビットは摂動させず、どの再実行も収束しなければ摂動前の結果を返す
+    /// 摂動後の確率は (ε, 1-ε) にクランプし、乱数は`seed`で初期化した生成器から引くので結果は再現できる
+    /// 縮退した対称なスタビライザー構成でBPが振動するのを崩すための簡易的な手法
+    pub fn set_random_perturbation(&mut self, epsilon: f64, max_restarts: usize, seed: u64) {
+        assert!(
+            (0.0..1.0).contains(&epsilon),
+            "epsilonは0以上1未満である必要があります: {}",
+            epsilon
+        );
+        self.perturbation_epsilon = epsilon;
+        self.perturbation_restarts = max_restarts;
+        self.perturbation_rng = StdRng::seed_from_u64(seed);
+    }
+
+    /// 誤りがないことが分かっているビット（準備直後のアンシラなど）の復号結果を0に固定する
+    /// 固定したビットには毎反復で非常に大きな正のLLRを与えるので、事前確率やチェックからの
+    /// メッセージに関わらず反転しない。既に固定されているビットはそのまま固定される
+    pub fn freeze_bits(&mut self, bits: &[usize]) {
+        for &bit in bits {
+            assert!(
+                bit < self.bit_count,
+                "ビットのインデックス({})がビット数({})を超えています",
+                bit,
+                self.bit_count
+            );
+            self.frozen_bits[bit] = true;
+        }
+    }
+
+    /// 事前LLRと、反復ごとの各ビットのLLRおよびエッジ上のメッセージを[-max_abs, max_abs]にクランプする
+    /// 誤り率が非常に小さいとLLRが大きくなり、tanhが飽和して精度が落ちるのを防ぐ
+    /// クランプしないのは固定したビットの事前LLR(`FROZEN_BIT_LLR`)だけで、反復中のLLRとメッセージは
+    /// 固定したビットのものも含めて全てクランプする。既定では無限大で、クランプは行わない
+    pub fn set_llr_clamp(&mut self, max_abs: f64) {
+        assert!(
+            max_abs > 0.0,
+            "LLRのクランプ範囲は正である必要があります: {}",
+            max_abs
+        );
+        self.llr_clamp = max_abs;
+    }
+
+    pub fn parity_check_matrix(&self) -> &BinarySparseMatrix {
+        self.pcm.parity_check_matrix()
+    }
+
     /// チャネル確率から初期対数尤度比(LLR)を計算し、変数ノードからのメッセージを初期化します。
     pub fn initialise_log_domain_bp(&mut self) {
         for i in 0..self.bit_count {
             // LLR = ln((1-p)/p)
-            let p = self.channel_probabilities[i];
-            self.initial_log_prob_ratios[i] = ((1.0 - p) / p).ln();
+            // p = 0 や p = 1 のビットも有限のLLRで扱えるよう、事前確率を (ε, 1-ε) にクランプする
+            let p =
+                self.channel_probabilities[i].clamp(PROBABILITY_EPSILON, 1.0 - PROBABILITY_EPSILON);
+            self.initial_log_prob_ratios[i] = if self.frozen_bits[i] {
+                FROZEN_BIT_LLR
+            } else {
+                ((1.0 - p) / p).ln().clamp(-self.llr_clamp, self.llr_clamp)
+            };
 
             // 変数ノードからチェックノードへの初期メッセージを設定
             for entry in self.pcm.iterate_column_mut(i) {
@@ -219,6 +429,88 @@ impl BpDecoder {
     }
 
     pub fn decode(&mut self, syndrome: &Vec<u8>) -> Vec<u8> {
+        let check_count = self.pcm.parity_check_matrix().rows();
+        assert_eq!(
+            syndrome.len(),
+            check_count,
+            "シンドロームの長さ({})が検査行列の行数({})と一致しません",
+            syndrome.len(),
+            check_count
+        );
+        let decoding = self.decode_once(syndrome);
+        if self.converge || self.perturbation_epsilon == 0.0 {
+            return decoding;
+        }
+
+        let original_probabilities = self.channel_probabilities.clone();
+        let original_log_prob_ratios = self.log_prob_ratios.clone();
+        let original_iterations = self.iterations;
+        for _ in 0..self.perturbation_restarts {
+            for (p, &p0) in self
+                .channel_probabilities
+                .iter_mut()
+                .zip(&original_probabilities)
+            {
+                // 確率0や1のビットは確定しているので摂動させない
+                if p0 == 0.0 || p0 == 1.0 {
+                    continue;
+                }
+                let u: f64 = self.perturbation_rng.random_range(-1.0..=1.0);
+                *p = (p0 * (1.0 + self.perturbation_epsilon * u))
+                    .clamp(PROBABILITY_EPSILON, 1.0 - PROBABILITY_EPSILON);
+            }
+            self.decode_once(syndrome);
+            if self.converge {
+                break;
+            }
+        }
+        self.channel_probabilities = original_probabilities;
+
+        // どの再実行も収束しなかった場合は、摂動前の最初の結果に戻す
+        if !self.converge {
+            self.decoding = decoding;
+            self.log_prob_ratios = original_log_prob_ratios;
+            self.iterations = original_iterations;
+        }
+
+        self.decoding.clone()
+    }
+
+    /// BP復号が収束しなかった場合に、事後LLRから確率的に硬判定を行う
+    /// 各ビットを確率 1 / (1 + exp(LLR)) で1とするサンプリングを最大`attempts`回繰り返し、
+    /// シンドロームと一致するものが見つかればそれを返す
+    /// 縮退のため事後確率が対称になり、決定的な硬判定では収束しない場合に使う
+    /// 見つからなかった場合は通常の硬判定の結果を返す
+    /// サンプリングには`rng`を使うので、シード付きの生成器を渡せば結果を再現できる
+    pub fn decode_stochastic(
+        &mut self,
+        syndrome: &[u8],
+        attempts: usize,
+        rng: &mut impl Rng,
+    ) -> Vec<u8> {
+        let syndrome = syndrome.to_vec();
+        let decoding = self.decode(&syndrome);
+        if self.converge {
+            return decoding;
+        }
+
+        for _ in 0..attempts {
+            let sample: Vec<u8> = self
+                .log_prob_ratios
+                .iter()
+                .map(|&llr| rng.random_bool(1.0 / (1.0 + llr.exp())) as u8)
+                .collect();
+            if self.pcm.parity_check_matrix().mul_u8(&sample) == syndrome {
+                self.decoding = sample;
+                self.converge = true;
+                break;
+            }
+        }
+
+        self.decoding.clone()
+    }
+
+    fn decode_once(&mut self, syndrome: &Vec<u8>) -> Vec<u8> {
         if self.schedule == BpSchedule::Parallel {
             self.bp_decode_parallel(syndrome)
         } else {
@@ -226,19 +518,139 @@ impl BpDecoder {
         }
     }
 
+    /// 消失した量子ビットの事前確率を0.5(LLR = 0)に置き換えてBP復号を行う
+    /// 消失していない量子ビットの事前確率は設定されたものをそのまま使う
+    pub fn decode_with_erasure(&mut self, syndrome: &Vec<u8>, erased: &[bool]) -> Vec<u8> {
+        assert_eq!(
+            erased.len(),
+            self.bit_count,
+            "消失マスクの長さ({})がビット数({})と一致しません",
+            erased.len(),
+            self.bit_count
+        );
+
+        let original_probabilities = self.channel_probabilities.clone();
+        for (p, &is_erased) in self.channel_probabilities.iter_mut().zip(erased) {
+            if is_erased {
+                *p = 0.5;
+            }
+        }
+
+        let decoding = self.decode(syndrome);
+        self.channel_probabilities = original_probabilities;
+        decoding
+    }
+
+    /// 複数ラウンドのシンドローム測定から、測定誤りを含む時空間グラフ上でBP復号を行う
+    /// 連続するラウンドのシンドロームのXORを検出イベントとし、各ラウンドのデータ誤りと
+    /// 最終ラウンド以外の測定誤りを変数に持つ時空間検査行列を組み立てて復号する
+    /// 最終ラウンドの測定は誤りがないものとし、測定誤りの事前確率にはデータ誤りの事前確率の平均を使う
+    /// 戻り値は各ラウンドで推定したデータ誤りを全て足し合わせたもの
+    pub fn decode_rounds(&mut self, syndromes: &[Vec<u8>]) -> Vec<u8> {
+        assert!(!syndromes.is_empty(), "シンドロームが1ラウンドもありません");
+        let check_count = self.pcm.parity_check_matrix().rows();
+        for syndrome in syndromes {
+            assert_eq!(
+                syndrome.len(),
+                check_count,
+                "シンドロームの長さ({})が検査行列の行数({})と一致しません",
+                syndrome.len(),
+                check_count
+            );
+        }
+        if syndromes.len() == 1 {
+            return self.decode(&syndromes[0]);
+        }
+
+        let rounds = syndromes.len();
+        let bit_count = self.bit_count;
+        let data_cols = rounds * bit_count;
+        let pcm = self.pcm.parity_check_matrix();
+
+        let mut row_adj = Vec::with_capacity(rounds * check_count);
+        for round in 0..rounds {
+            for check in 0..check_count {
+                let mut cols: Vec<usize> = pcm
+                    .nonzero_cols(check)
+                    .iter()
+                    .map(|&col| round * bit_count + col)
+                    .collect();
+                if round > 0 {
+                    cols.push(data_cols + (round - 1) * check_count + check);
+                }
+                if round + 1 < rounds {
+                    cols.push(data_cols + round * check_count + check);
+                }
+                row_adj.push(cols);
+            }
+        }
+        let space_time_pcm = BinarySparseMatrix::from_row_adj(
+            rounds * check_count,
+            data_cols + (rounds - 1) * check_count,
+            row_adj,
+        );
+
+        let measurement_probability = if bit_count == 0 {
+            0.0
+        } else {
+            self.channel_probabilities.iter().sum::<f64>() / bit_count as f64
+        };
+        let mut channel_probabilities = Vec::with_capacity(space_time_pcm.cols());
+        for _ in 0..rounds {
+            channel_probabilities.extend_from_slice(&self.channel_probabilities);
+        }
+        channel_probabilities.resize(space_time_pcm.cols(), measurement_probability);
+
+        let mut space_time_decoder = BpDecoder::from_pcm(
+            space_time_pcm,
+            self.bp_method,
+            self.schedule,
+            self.maximum_iterations,
+            self.ms_scaling_factor,
+            self.random_serial_schedule,
+            channel_probabilities,
+        );
+        space_time_decoder.llr_clamp = self.llr_clamp;
+        for round in 0..rounds {
+            space_time_decoder.frozen_bits[round * bit_count..(round + 1) * bit_count]
+                .copy_from_slice(&self.frozen_bits);
+        }
+
+        let mut detection_events = Vec::with_capacity(rounds * check_count);
+        let mut previous = vec![0u8; check_count];
+        for syndrome in syndromes {
+            detection_events.extend(syndrome.iter().zip(&previous).map(|(&s, &p)| s ^ p));
+            previous.clone_from(syndrome);
+        }
+
+        let space_time_decoding = space_time_decoder.decode(&detection_events);
+        self.converge = space_time_decoder.converge;
+        self.iterations = space_time_decoder.iterations;
+
+        let mut decoding = vec![0u8; bit_count];
+        for chunk in space_time_decoding[..data_cols].chunks(bit_count) {
+            for (bit, &value) in decoding.iter_mut().zip(chunk) {
+                *bit ^= value;
+            }
+        }
+        self.decoding.clone_from(&decoding);
+        decoding
+    }
+
     /// C++: bp_decode_parallel
     /// 並列スケジュールでのBP復号（積和法または最小和法）
     fn bp_decode_parallel(&mut self, syndrome: &Vec<u8>) -> Vec<u8> {
-        let check_count = self.pcm.parity_check_matrix().rows();
         self.converge = false;
+        self.history.clear();
         self.initialise_log_domain_bp();
+        let llr_clamp = self.llr_clamp;
 
         for it in 1..=self.maximum_iterations {
             // --- チェックノード更新 (Check Node Update) ---
             if self.bp_method == BpMethod::ProductSum {
                 // Product Sum (Tanh rule)
                 // Forward-Backward アルゴリズムを使って、自分自身以外の積を計算
-                for i in 0..check_count {
+                for (i, &syndrome_bit) in syndrome.iter().enumerate() {
                     self.candidate_syndrome[i] = 0;
 
                     // Forward pass: 左からの積を計算して check_to_bit_msg に一時保存
@@ -254,11 +666,13 @@ impl BpDecoder {
                         // 逆順イテレータ
                         entry.check_to_bit_msg *= temp; // Left * Right
 
-                        let message_sign = if syndrome[i] != 0 { -1.0 } else { 1.0 };
+                        let message_sign = if syndrome_bit != 0 { -1.0 } else { 1.0 };
+                        // 数値安定性のためのクリッピング
+                        let clamped_term = entry.check_to_bit_msg.clamp(-0.9999999, 0.9999999);
                         // 2 * atanh(x) = ln((1+x)/(1-x))
-                        entry.check_to_bit_msg = message_sign
-                            * ((1.0 + entry.check_to_bit_msg) / (1.0 - entry.check_to_bit_msg))
-                                .ln();
+                        entry.check_to_bit_msg = (message_sign
+                            * ((1.0 + clamped_term) / (1.0 - clamped_term)).ln())
+                        .clamp(-llr_clamp, llr_clamp);
 
                         // 次のイテレーション用にRight積を更新
                         temp *= (entry.bit_to_check_msg / 2.0).tanh();
@@ -268,14 +682,14 @@ impl BpDecoder {
                 // Minimum Sum
                 // アルファスケーリング係数の決定
                 let alpha = if self.ms_scaling_factor == 0.0 {
-                    1.0 - 2.0_f64.powf(-1.0 * it as f64)
+                    1.0 - 2.0_f64.powf(-(it as f64))
                 } else {
                     self.ms_scaling_factor
                 };
 
-                for i in 0..check_count {
+                for (i, &syndrome_bit) in syndrome.iter().enumerate() {
                     self.candidate_syndrome[i] = 0;
-                    let mut total_sgn = syndrome[i] as i32;
+                    let mut total_sgn = syndrome_bit as i32;
 
                     // Forward pass: グローバルな最小値を探索しつつ、符号をカウント
                     // 注: bp.hppの実装ではForward-Backwardで自分以外の最小値を厳密に求めている
@@ -310,7 +724,8 @@ impl BpDecoder {
                         }
 
                         let message_sign = if sgn % 2 == 0 { 1.0 } else { -1.0 };
-                        entry.check_to_bit_msg *= message_sign * alpha;
+                        entry.check_to_bit_msg = (entry.check_to_bit_msg * message_sign * alpha)
+                            .clamp(-llr_clamp, llr_clamp);
 
                         // 次のイテレーション用にRight最小値を更新
                         let abs_msg = entry.bit_to_check_msg.abs();
@@ -332,6 +747,7 @@ impl BpDecoder {
                     temp += entry.check_to_bit_msg;
                 }
 
+                let temp = temp.clamp(-llr_clamp, llr_clamp);
                 self.log_prob_ratios[i] = temp;
 
                 // 硬判定
@@ -351,6 +767,7 @@ impl BpDecoder {
                 self.converge = true;
             }
             self.iterations = it;
+            self.record_iteration(syndrome);
 
             if self.converge {
                 return self.decoding.clone();
@@ -372,7 +789,8 @@ impl BpDecoder {
                 // ここで right sum を足せば「自分以外」の和になる。
 
                 for entry in self.pcm.reverse_iterate_column_mut(i) {
-                    entry.bit_to_check_msg += temp;
+                    entry.bit_to_check_msg =
+                        (entry.bit_to_check_msg + temp).clamp(-llr_clamp, llr_clamp);
                     temp += entry.check_to_bit_msg;
                 }
             }
@@ -384,14 +802,16 @@ impl BpDecoder {
     /// 逐次スケジュールでのBP復号
     fn bp_decode_serial(&mut self, syndrome: &Vec<u8>) -> Vec<u8> {
         self.converge = false;
+        self.history.clear();
         // BPの初期化（LLRの計算とメッセージの初期化）
         self.initialise_log_domain_bp();
+        let llr_clamp = self.llr_clamp;
 
         // メイン反復ループ
         for it in 1..=self.maximum_iterations {
             // 1. Minimum Sum用のスケーリング係数(alpha)の計算
             let alpha = if self.ms_scaling_factor == 0.0 {
-                1.0 - 2.0_f64.powf(-1.0 * it as f64)
+                1.0 - 2.0_f64.powf(-(it as f64))
             } else {
                 self.ms_scaling_factor
             };
@@ -402,23 +822,21 @@ impl BpDecoder {
                 self.serial_schedule_order.shuffle(&mut rng);
             } else if self.schedule == BpSchedule::SerialRelative {
                 // LLRの絶対値（信頼度）に基づいてソート
-                let channel_probs = &self.channel_probabilities;
+                let initial_llrs = &self.initial_log_prob_ratios;
                 let llrs = &self.log_prob_ratios;
 
                 self.serial_schedule_order.sort_by(|&a, &b| {
-                    let idx_a = a as usize;
-                    let idx_b = b as usize;
+                    let idx_a = a;
+                    let idx_b = b;
 
                     let val_a = if it == 1 {
-                        let p = channel_probs[idx_a];
-                        ((1.0 - p) / p).ln().abs()
+                        initial_llrs[idx_a].abs()
                     } else {
                         llrs[idx_a].abs()
                     };
 
                     let val_b = if it == 1 {
-                        let p = channel_probs[idx_b];
-                        ((1.0 - p) / p).ln().abs()
+                        initial_llrs[idx_b].abs()
                     } else {
                         llrs[idx_b].abs()
                     };
@@ -429,12 +847,9 @@ impl BpDecoder {
             }
 
             // 3. ビットごとの逐次更新ループ
-            for &bit_index_i32 in &self.serial_schedule_order {
-                let bit_index = bit_index_i32 as usize;
-
+            for &bit_index in &self.serial_schedule_order {
                 // チャネル値でLLRをリセット
-                let p = self.channel_probabilities[bit_index];
-                self.log_prob_ratios[bit_index] = ((1.0 - p) / p).ln();
+                self.log_prob_ratios[bit_index] = self.initial_log_prob_ratios[bit_index];
 
                 // ---------------------------------------------------------
                 // Step A: チェックノードからのメッセージを計算し、LLRを更新
@@ -470,7 +885,7 @@ impl BpDecoder {
                         // 2 * atanh(x) = ln((1+x)/(1-x))
                         let term = sgn_val * prod;
                         // 数値安定性のためのクリッピング
-                        let clamped_term = term.max(-0.9999999).min(0.9999999);
+                        let clamped_term = term.clamp(-0.9999999, 0.9999999);
                         check_to_bit_msg = ((1.0 + clamped_term) / (1.0 - clamped_term)).ln();
                     } else if self.bp_method == BpMethod::MinimumSum {
                         // --- Minimum Sum Logic ---
@@ -500,6 +915,8 @@ impl BpDecoder {
                     // self.pcm.get_entry_mut(check_idx, bit_index).check_to_bit_msg = check_to_bit_msg;
                     // self.pcm.get_entry_mut(check_idx, bit_index).bit_to_check_msg = self.log_prob_ratios[bit_index]; // ここは一時的
 
+                    let check_to_bit_msg = check_to_bit_msg.clamp(-llr_clamp, llr_clamp);
+
                     // 実際にはRustではアクセサ経由で更新
                     self.pcm.update_edge_msg(check_idx, bit_index, |e| {
                         e.check_to_bit_msg = check_to_bit_msg;
@@ -515,6 +932,9 @@ impl BpDecoder {
                 // Step B: 硬判定と Bit-to-Check メッセージの更新 (Outgoing)
                 // ---------------------------------------------------------
 
+                self.log_prob_ratios[bit_index] =
+                    self.log_prob_ratios[bit_index].clamp(-llr_clamp, llr_clamp);
+
                 // 硬判定
                 if self.log_prob_ratios[bit_index] <= 0.0 {
                     self.decoding[bit_index] = 1;
@@ -530,14 +950,17 @@ impl BpDecoder {
 
                     self.pcm.update_edge_msg(check_idx, bit_index, |e| {
                         // Extrinsic情報の計算
-                        e.bit_to_check_msg = total_llr - e.check_to_bit_msg;
+                        e.bit_to_check_msg =
+                            (total_llr - e.check_to_bit_msg).clamp(-llr_clamp, llr_clamp);
                     });
                 }
             }
 
             // 4. シンドローム計算と収束判定
-            self.candidate_syndrome = self.pcm.parity_check_matrix() * &self.decoding;
+            // decodingはVec<u8>のまま、BitVecに変換せずに候補シンドロームを計算する
+            self.candidate_syndrome = self.pcm.parity_check_matrix().mul_u8(&self.decoding);
             self.iterations = it;
+            self.record_iteration(syndrome);
 
             if self.candidate_syndrome == *syndrome {
                 self.converge = true;
@@ -552,6 +975,8 @@ impl BpDecoder {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::Word;
+    use crate::code::named_codes::shor_code;
 
     #[test]
     fn test_from_pcm() {
@@ -593,6 +1018,13 @@ mod tests {
             converge: false,
             iterations: 0,
             serial_schedule_order: vec![0, 1, 2],
+            perturbation_epsilon: 0.0,
+            perturbation_restarts: 0,
+            perturbation_rng: StdRng::seed_from_u64(0),
+            record_history: false,
+            history: Vec::new(),
+            frozen_bits: vec![false; 3],
+            llr_clamp: DEFAULT_LLR_CLAMP,
         };
         let syndrome = vec![0, 0];
         let result = decoder.decode(&syndrome);
@@ -619,6 +1051,13 @@ mod tests {
             converge: false,
             iterations: 0,
             serial_schedule_order: vec![0, 1, 2],
+            perturbation_epsilon: 0.0,
+            perturbation_restarts: 0,
+            perturbation_rng: StdRng::seed_from_u64(0),
+            record_history: false,
+            history: Vec::new(),
+            frozen_bits: vec![false; 3],
+            llr_clamp: DEFAULT_LLR_CLAMP,
         };
         for i in 0..3 {
             let mut error_vector = vec![0; 3];
@@ -629,4 +1068,428 @@ mod tests {
             assert!(decoder.converge);
         }
     }
+
+    #[test]
+    fn test_bp_decode_with_erasure() {
+        let pcm = shor_code().hz().clone();
+        let mut decoder = BpDecoder::from_pcm(
+            pcm,
+            BpMethod::ProductSum,
+            BpSchedule::Parallel,
+            20,
+            0.0,
+            false,
+            vec![0.01; 9],
+        );
+
+        // 消失した量子ビットの半分以上にXエラーが起きている
+        let error_vector = vec![1, 1, 0, 0, 1, 1, 0, 0, 0];
+        let erased = vec![true, true, false, false, true, true, true, false, false];
+        let syndrome = decoder.pcm.parity_check_matrix() * &error_vector;
+
+        // 消失情報がなければ最小重みの誤った推定になる
+        let result = decoder.decode(&syndrome);
+        assert_ne!(result, error_vector);
+
+        let result = decoder.decode_with_erasure(&syndrome, &erased);
+        assert_eq!(result, error_vector);
+        assert!(decoder.converge);
+
+        // 事前確率は元に戻っている
+        assert_eq!(decoder.channel_probabilities, vec![0.01; 9]);
+    }
+
+    #[test]
+    fn test_bp_serial_minimum_sum_single_error() {
+        let pcm = BinarySparseMatrix::from_row_adj(
+            4,
+            5,
+            vec![vec![0, 1], vec![1, 2], vec![2, 3], vec![3, 4]],
+        );
+        let mut decoder = BpDecoder::from_pcm(
+            pcm,
+            BpMethod::MinimumSum,
+            BpSchedule::Serial,
+            10,
+            0.0,
+            false,
+            vec![0.1; 5],
+        );
+        for i in 0..5 {
+            let mut error_vector = vec![0; 5];
+            error_vector[i] = 1;
+            let syndrome = decoder.pcm.parity_check_matrix() * &error_vector;
+            let result = decoder.decode(&syndrome);
+            assert_eq!(result, error_vector);
+            assert_eq!(decoder.candidate_syndrome, syndrome);
+            assert!(decoder.converge);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "シンドロームの長さ(3)が検査行列の行数(2)と一致しません")]
+    fn test_bp_decode_syndrome_length_mismatch() {
+        let pcm = BinarySparseMatrix::from_row_adj(2, 3, vec![vec![0, 1], vec![1, 2]]);
+        let mut decoder = BpDecoder::from_pcm(
+            pcm,
+            BpMethod::ProductSum,
+            BpSchedule::Parallel,
+            10,
+            0.0,
+            false,
+            vec![0.1; 3],
+        );
+        decoder.decode(&vec![0, 1, 1]);
+    }
+
+    #[test]
+    fn test_bp_random_perturbation() {
+        // 巡回型の繰り返し符号: 誤り{0, 1}と{2, 3}が同じシンドロームを与え、
+        // 事前確率が一様だと対称性が崩れずにBPが収束しない
+        let pcm = BinarySparseMatrix::from_row_adj(
+            4,
+            4,
+            vec![vec![0, 1], vec![1, 2], vec![2, 3], vec![3, 0]],
+        );
+        let mut decoder = BpDecoder::from_pcm(
+            pcm,
+            BpMethod::ProductSum,
+            BpSchedule::Parallel,
+            20,
+            0.0,
+            false,
+            vec![0.1; 4],
+        );
+        let syndrome = vec![0, 1, 0, 1];
+
+        decoder.decode(&syndrome);
+        assert!(!decoder.converge);
+
+        let mut reference = decoder.clone();
+        decoder.set_random_perturbation(0.2, 20, 7);
+        let result = decoder.decode(&syndrome);
+        assert!(decoder.converge);
+        assert_eq!(decoder.pcm.parity_check_matrix() * &result, syndrome);
+        assert_eq!(decoder.channel_probabilities, vec![0.1; 4]);
+
+        // 同じシードなら同じ摂動が引かれ、同じ結果になる
+        reference.set_random_perturbation(0.2, 20, 7);
+        assert_eq!(reference.decode(&syndrome), result);
+    }
+
+    #[test]
+    fn test_bp_random_perturbation_without_convergence() {
+        let pcm = BinarySparseMatrix::from_row_adj(
+            4,
+            4,
+            vec![vec![0, 1], vec![1, 2], vec![2, 3], vec![3, 0]],
+        );
+        let syndrome = vec![0, 1, 0, 1];
+
+        // 摂動が小さすぎて対称性が崩れない場合でも、摂動前の最初の結果と事後LLRが残る
+        // 確率が0や1のビットは摂動されないので、再実行しても同じく収束しない
+        for probabilities in [vec![0.1; 4], vec![1.0; 4], vec![0.0; 4]] {
+            let mut decoder = BpDecoder::from_pcm(
+                pcm.clone(),
+                BpMethod::ProductSum,
+                BpSchedule::Parallel,
+                20,
+                0.0,
+                false,
+                probabilities.clone(),
+            );
+            let mut reference = decoder.clone();
+            let expected = reference.decode(&syndrome);
+            assert!(!reference.converge);
+
+            decoder.set_random_perturbation(1e-6, 3, 7);
+            assert_eq!(decoder.decode(&syndrome), expected);
+            assert!(!decoder.converge);
+            assert_eq!(decoder.log_prob_ratios, reference.log_prob_ratios);
+            assert_eq!(decoder.iterations, reference.iterations);
+            assert_eq!(decoder.channel_probabilities, probabilities);
+        }
+    }
+
+    #[test]
+    fn test_bp_classical_code_single_error() {
+        use crate::channel::bit_flip::BitFlipChannel;
+        use bitvec::prelude::*;
+
+        // 列重み3、行重み6の(3, 6)正則LDPC符号
+        let pcm = BinarySparseMatrix::circulant(6, &[0, 1, 3])
+            .augment_right(&BinarySparseMatrix::circulant(6, &[0, 2, 3]));
+        let code = ClassicalCode::new("(3,6)-LDPC", pcm);
+        assert!(code.pcm().col_adj().iter().all(|rows| rows.len() == 3));
+        assert!(code.pcm().row_adj().iter().all(|cols| cols.len() == 6));
+
+        let channel = BitFlipChannel::new(code.n(), 0.05);
+        let mut decoder = BpDecoder::from_classical_code(
+            &code,
+            &channel,
+            BpMethod::ProductSum,
+            BpSchedule::Parallel,
+            20,
+            0.0,
+            false,
+        );
+
+        for i in 0..code.n() {
+            let mut error = bitvec![Word, Lsb0; 0; code.n()];
+            error.set(i, true);
+            let syndrome: Vec<u8> = code.syndrome(&error).iter().map(|b| *b as u8).collect();
+            let result = decoder.decode(&syndrome);
+            let expected: Vec<u8> = error.iter().map(|b| *b as u8).collect();
+            assert_eq!(result, expected);
+        }
+    }
+
+    #[test]
+    fn test_bp_history() {
+        let pcm = BinarySparseMatrix::from_row_adj(
+            4,
+            4,
+            vec![vec![0, 1], vec![1, 2], vec![2, 3], vec![3, 0]],
+        );
+        for schedule in [BpSchedule::Parallel, BpSchedule::Serial] {
+            let mut decoder = BpDecoder::from_pcm(
+                pcm.clone(),
+                BpMethod::ProductSum,
+                schedule,
+                15,
+                0.0,
+                false,
+                vec![0.1; 4],
+            );
+
+            // 記録を有効にしなければ空のまま
+            decoder.decode(&vec![1, 1, 0, 0]);
+            assert!(decoder.history().is_empty());
+
+            decoder.enable_history();
+            decoder.decode(&vec![1, 1, 0, 0]);
+            assert!(decoder.converge);
+            assert_eq!(decoder.history().len(), decoder.iterations);
+            assert_eq!(decoder.history().last().unwrap().0, 0);
+
+            // 収束しない場合は最大反復回数分記録される
+            decoder.decode(&vec![0, 1, 0, 1]);
+            assert!(!decoder.converge);
+            assert_eq!(decoder.history().len(), 15);
+            assert_eq!(decoder.history().len(), decoder.iterations);
+        }
+    }
+
+    #[test]
+    fn test_bp_parallel_product_sum_high_reliability() {
+        // 事前確率が非常に小さいとtanhの積が±1に丸められ、クランプしないとLLRが発散してNaNになる
+        let pcm = BinarySparseMatrix::circulant(5, &[0, 1]);
+        for p in [1e-7, 1e-20] {
+            let mut decoder = BpDecoder::from_pcm(
+                pcm.clone(),
+                BpMethod::ProductSum,
+                BpSchedule::Parallel,
+                10,
+                0.0,
+                false,
+                vec![p; 5],
+            );
+            for i in 0..5 {
+                let mut error_vector = vec![0; 5];
+                error_vector[i] = 1;
+                let syndrome = decoder.pcm.parity_check_matrix() * &error_vector;
+                let result = decoder.decode(&syndrome);
+                assert!(decoder.log_prob_ratios.iter().all(|llr| llr.is_finite()));
+                if p == 1e-7 {
+                    assert_eq!(result, error_vector);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_bp_zero_and_one_probability_bits() {
+        // 事前確率0のビットは誤りがないことが確実なビット、1のビットは誤りが確実なビットとして扱う
+        let pcm = BinarySparseMatrix::from_row_adj(
+            4,
+            5,
+            vec![vec![0, 1], vec![1, 2], vec![2, 3], vec![3, 4]],
+        );
+        for (bp_method, schedule) in [
+            (BpMethod::ProductSum, BpSchedule::Parallel),
+            (BpMethod::MinimumSum, BpSchedule::Parallel),
+            (BpMethod::ProductSum, BpSchedule::Serial),
+        ] {
+            let mut decoder = BpDecoder::from_pcm(
+                pcm.clone(),
+                bp_method,
+                schedule,
+                5,
+                0.75,
+                false,
+                vec![0.1, 0.1, 0.0, 0.1, 1.0],
+            );
+
+            let error_vector = vec![0, 1, 0, 0, 1];
+            let syndrome = decoder.pcm.parity_check_matrix() * &error_vector;
+            let result = decoder.decode(&syndrome);
+            assert!(decoder.log_prob_ratios.iter().all(|llr| llr.is_finite()));
+            assert_eq!(result, error_vector);
+        }
+    }
+
+    #[test]
+    fn test_bp_freeze_bits() {
+        // 事前確率ではビット1の反転が最も尤もらしいが、固定するとビット0と2の反転で説明される
+        let pcm = BinarySparseMatrix::from_row_adj(2, 3, vec![vec![0, 1], vec![1, 2]]);
+        for (bp_method, schedule) in [
+            (BpMethod::ProductSum, BpSchedule::Parallel),
+            (BpMethod::MinimumSum, BpSchedule::Parallel),
+            (BpMethod::ProductSum, BpSchedule::Serial),
+            (BpMethod::MinimumSum, BpSchedule::Serial),
+        ] {
+            let mut decoder = BpDecoder::from_pcm(
+                pcm.clone(),
+                bp_method,
+                schedule,
+                10,
+                0.75,
+                false,
+                vec![0.1, 0.6, 0.1],
+            );
+            assert_eq!(decoder.decode(&vec![1, 1]), vec![0, 1, 0]);
+
+            decoder.freeze_bits(&[1]);
+            assert_eq!(decoder.decode(&vec![1, 1]), vec![1, 0, 1]);
+            assert!(decoder.converge);
+        }
+    }
+
+    #[test]
+    fn test_bp_soft_decision() {
+        let pcm = BinarySparseMatrix::from_row_adj(2, 3, vec![vec![0, 1], vec![1, 2]]);
+        let mut decoder = BpDecoder::from_pcm(
+            pcm,
+            BpMethod::ProductSum,
+            BpSchedule::Parallel,
+            10,
+            0.75,
+            false,
+            vec![0.1, 0.1, 0.0],
+        );
+        decoder.freeze_bits(&[2]);
+        let decoding = decoder.decode(&vec![1, 0]);
+        assert_eq!(decoding, vec![1, 0, 0]);
+
+        let soft = decoder.soft_decision();
+        for (&llr, &bit) in soft.iter().zip(&decoding) {
+            assert!(llr.abs() <= SOFT_DECISION_LLR_LIMIT);
+            assert_eq!(llr <= 0.0, bit == 1);
+        }
+        // 固定したビットのLLRは上限でクランプされる
+        assert_eq!(soft[2], SOFT_DECISION_LLR_LIMIT);
+    }
+
+    #[test]
+    fn test_bp_decode_stochastic() {
+        // 1つのチェックに2ビットが含まれる符号: 誤り{0}と{1}が同じシンドロームを与え、
+        // 両ビットの事後LLRがちょうど0になるため、決定的な硬判定では両方を反転させてしまう
+        let pcm = BinarySparseMatrix::from_row_adj(1, 2, vec![vec![0, 1]]);
+        let mut decoder = BpDecoder::from_pcm(
+            pcm,
+            BpMethod::ProductSum,
+            BpSchedule::Parallel,
+            10,
+            0.0,
+            false,
+            vec![0.1; 2],
+        );
+        let syndrome = vec![1];
+
+        assert_eq!(decoder.decode(&syndrome), vec![1, 1]);
+        assert!(!decoder.converge);
+
+        let mut rng = StdRng::seed_from_u64(3);
+        let result = decoder.decode_stochastic(&syndrome, 50, &mut rng);
+        assert!(decoder.converge);
+        assert_eq!(decoder.pcm.parity_check_matrix() * &result, syndrome);
+        assert_eq!(result.iter().filter(|&&bit| bit == 1).count(), 1);
+    }
+
+    #[test]
+    fn test_bp_llr_clamp_single_error() {
+        // 誤り率が非常に小さく、事前LLRがクランプ範囲を大きく超える場合
+        // 各ビットが2つのチェックに含まれる環状の繰り返し符号を使う
+        let pcm = BinarySparseMatrix::from_row_adj(
+            5,
+            5,
+            vec![vec![0, 1], vec![1, 2], vec![2, 3], vec![3, 4], vec![4, 0]],
+        );
+        for schedule in [BpSchedule::Serial, BpSchedule::Parallel] {
+            for bp_method in [BpMethod::ProductSum, BpMethod::MinimumSum] {
+                let mut decoder = BpDecoder::from_pcm(
+                    pcm.clone(),
+                    bp_method,
+                    schedule,
+                    10,
+                    0.0,
+                    false,
+                    vec![0.0001; 5],
+                );
+                decoder.set_llr_clamp(3.0);
+                for i in 0..5 {
+                    let mut error_vector = vec![0; 5];
+                    error_vector[i] = 1;
+                    let syndrome = &pcm * &error_vector;
+                    assert_eq!(decoder.decode(&syndrome), error_vector);
+                    assert!(decoder.converge());
+                    assert!(decoder.log_prob_ratios().iter().all(|llr| llr.abs() <= 3.0));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_bp_from_detector_error_model_uniform() {
+        let pcm = BinarySparseMatrix::from_row_adj(
+            4,
+            5,
+            vec![vec![0, 1], vec![1, 2], vec![2, 3], vec![3, 4]],
+        );
+        // 各列を1つの誤りとし、全て同じ確率にしたDEMは一様な事前確率のBPと一致する
+        let mut dem = DetectorErrorModel::new(4);
+        for col_idx in 0..5 {
+            dem.add_error(0.1, pcm.nonzero_rows(col_idx));
+        }
+        let mut from_dem = BpDecoder::from_detector_error_model(
+            &dem,
+            BpMethod::ProductSum,
+            BpSchedule::Parallel,
+            10,
+            0.0,
+            false,
+        );
+        let mut uniform = BpDecoder::from_pcm(
+            pcm.clone(),
+            BpMethod::ProductSum,
+            BpSchedule::Parallel,
+            10,
+            0.0,
+            false,
+            vec![0.1; 5],
+        );
+        assert_eq!(
+            from_dem.parity_check_matrix(),
+            uniform.parity_check_matrix()
+        );
+
+        for i in 0..5 {
+            let mut error_vector = vec![0; 5];
+            error_vector[i] = 1;
+            let syndrome = &pcm * &error_vector;
+            assert_eq!(from_dem.decode(&syndrome), uniform.decode(&syndrome));
+            assert_eq!(from_dem.log_prob_ratios(), uniform.log_prob_ratios());
+            assert_eq!(from_dem.iterations(), uniform.iterations());
+        }
+    }
 }
@@ -3,11 +3,17 @@ use crate::code::error_vector::{ErrorVector, Syndrome};
 use crate::decoder::bp::BpDecoder;
 use crate::decoder::bp::BpMethod;
 use crate::decoder::bp::BpSchedule;
-use crate::decoder::traits::Decoder;
+use crate::decoder::traits::{DecodeResult, Decoder, SyndromeReporting};
 use crate::prelude::ErrorChannel;
+use rayon::prelude::*;
 
+/// CSS符号に対するBP復号器
+/// X誤りとZ誤りを独立に、2つの古典BP復号器で復号する
+#[derive(Clone)]
 pub struct BpDecoderCss {
+    /// H_X を検査行列に持ち、X型スタビライザーのシンドロームからZ誤りを推定する
     decoder_x: BpDecoder,
+    /// H_Z を検査行列に持ち、Z型スタビライザーのシンドロームからX誤りを推定する
     decoder_z: BpDecoder,
 }
 
@@ -24,12 +30,14 @@ impl BpDecoderCss {
         let hz = code.hz().clone();
         let hx = code.hx().clone();
 
+        // Y誤りはX成分とZ成分の両方を持つので、どちらの周辺確率にも加える
         let error_rate_x = error_channel.x_error_rate() + error_channel.y_error_rate();
         let error_rate_z = error_channel.z_error_rate() + error_channel.y_error_rate();
 
         let channel_probabilities_x = vec![error_rate_x; code.num_qubits()];
         let channel_probabilities_z = vec![error_rate_z; code.num_qubits()];
 
+        // H_X はZ誤りを検出するので、事前確率にはZ成分の誤り率を使う
         let decoder_x = BpDecoder::from_pcm(
             hx,
             bp_method,
@@ -40,6 +48,7 @@ impl BpDecoderCss {
             channel_probabilities_z,
         );
 
+        // H_Z はX誤りを検出するので、事前確率にはX成分の誤り率を使う
         let decoder_z = BpDecoder::from_pcm(
             hz,
             bp_method,
@@ -55,6 +64,211 @@ impl BpDecoderCss {
             decoder_z,
         }
     }
+
+    /// 設定を名前付きで指定して復号器を構成するビルダーを返す
+    ///
+    /// # Examples
+    /// ```rust
+    /// use qldpc_sim::code::named_codes::steane_code;
+    /// use qldpc_sim::prelude::*;
+    ///
+    /// let code = steane_code();
+    /// let channel = DepolarizingChannel::new(7, 0.01);
+    /// let decoder = BpDecoderCss::builder(&code, &channel)
+    ///     .method(BpMethod::MinimumSum)
+    ///     .schedule(BpSchedule::Serial)
+    ///     .max_iterations(20)
+    ///     .build();
+    /// ```
+    pub fn builder<'a, C: ErrorChannel>(
+        code: &'a CssCode,
+        error_channel: &'a C,
+    ) -> BpDecoderCssBuilder<'a, C> {
+        BpDecoderCssBuilder {
+            code,
+            error_channel,
+            bp_method: BpMethod::ProductSum,
+            schedule: BpSchedule::Parallel,
+            max_iterations: code.num_qubits(),
+            ms_scaling_factor: 0.75,
+            random_serial_schedule: false,
+        }
+    }
+
+    /// 複数のシンドロームをrayonで並列に復号する
+    /// 復号器の状態はワーカーごとに複製して使い回すので、サンプルごとの初期化は発生しない
+    pub fn decode_batch(&self, syndromes: &[Syndrome]) -> Vec<ErrorVector> {
+        syndromes
+            .par_iter()
+            .map_init(
+                || self.clone(),
+                |decoder, syndrome| decoder.decode(syndrome),
+            )
+            .collect()
+    }
+
+    /// X, Y, Z誤りの確率を設定し直し、2つの復号器の事前確率を更新する
+    pub fn set_error_rates(&mut self, px: f64, py: f64, pz: f64) {
+        let num_qubits = self.decoder_x.parity_check_matrix().cols();
+        self.decoder_x
+            .set_channel_probabilities(vec![pz + py; num_qubits]);
+        self.decoder_z
+            .set_channel_probabilities(vec![px + py; num_qubits]);
+    }
+
+    /// 消失した量子ビットの位置を使って復号する
+    /// 消失した量子ビットの事前確率を0.5とし、それ以外は設定済みの事前確率のまま2つの復号器で復号する
+    /// `ErasurePauliChannel`の誤りを復号する場合は、`set_error_rates`で消失以外のPauli誤りの確率を
+    /// 設定しておくとよい
+    pub fn decode_with_erasure(&mut self, syndrome: &Syndrome, erased: &[bool]) -> ErrorVector {
+        self.check_syndrome_len(syndrome);
+        let error_z = self
+            .decoder_x
+            .decode_with_erasure(&syndrome.x_syndrome_u8(), erased);
+        let error_x = self
+            .decoder_z
+            .decode_with_erasure(&syndrome.z_syndrome_u8(), erased);
+
+        ErrorVector::from_u8vec(error_x, error_z)
+    }
+
+    /// 最小和BPのスケーリング係数を候補の中から選ぶ
+    /// 候補ごとに`samples`回のモンテカルロシミュレーションを行い、論理誤り率が最も低いものを返す
+    /// 論理誤り率が同じ候補が複数ある場合は先に現れたものを返す
+    /// 復号器は並列スケジュール・最大反復回数nの最小和BPとして構成する
+    /// 誤りは`seed`で初期化した生成器からサンプリングするので、同じシードなら同じ係数が選ばれる
+    pub fn tune_scaling<C: ErrorChannel>(
+        code: &CssCode,
+        channel: &C,
+        candidates: &[f64],
+        samples: usize,
+        seed: u64,
+    ) -> f64 {
+        assert!(!candidates.is_empty(), "候補が空です");
+
+        let errors = channel.sample_batch_with_seed(samples, seed);
+        let lx = code.lx();
+        let lz = code.lz();
+
+        let mut best_alpha = candidates[0];
+        let mut best_failures = usize::MAX;
+        for &alpha in candidates {
+            let mut decoder = BpDecoderCss::new(
+                code,
+                channel,
+                BpMethod::MinimumSum,
+                BpSchedule::Parallel,
+                code.num_qubits(),
+                alpha,
+                false,
+            );
+
+            let failures = errors
+                .iter()
+                .filter(|error| {
+                    let recovery = decoder.decode(&code.syndrome(error));
+                    code.is_logical_error_with(&lx, &lz, error, &recovery)
+                })
+                .count();
+
+            if failures < best_failures {
+                best_failures = failures;
+                best_alpha = alpha;
+            }
+        }
+
+        best_alpha
+    }
+
+    /// 直前の復号における各量子ビットの事後対数尤度比(LLR)を (X誤り, Z誤り) の組で返す
+    pub fn soft_output(&self) -> (Vec<f64>, Vec<f64>) {
+        (
+            self.decoder_z.log_prob_ratios().to_vec(),
+            self.decoder_x.log_prob_ratios().to_vec(),
+        )
+    }
+
+    /// 直前の復号における各量子ビットの有限の範囲にクランプした事後LLRを (X誤り, Z誤り) の組で返す
+    /// `BpDecoder::soft_decision`を参照
+    pub fn soft_decision(&self) -> (Vec<f64>, Vec<f64>) {
+        (
+            self.decoder_z.soft_decision(),
+            self.decoder_x.soft_decision(),
+        )
+    }
+
+    /// シンドロームの長さがH_XとH_Zの行数と一致することを確認する
+    /// 一致しないまま内部の復号器に渡すと、分かりにくいパニックや誤った復号結果になる
+    fn check_syndrome_len(&self, syndrome: &Syndrome) {
+        let hx_rows = self.decoder_x.parity_check_matrix().rows();
+        let hz_rows = self.decoder_z.parity_check_matrix().rows();
+        assert_eq!(
+            syndrome.x_syndrome().len(),
+            hx_rows,
+            "Xシンドロームの長さ({})がH_Xの行数({})と一致しません",
+            syndrome.x_syndrome().len(),
+            hx_rows
+        );
+        assert_eq!(
+            syndrome.z_syndrome().len(),
+            hz_rows,
+            "Zシンドロームの長さ({})がH_Zの行数({})と一致しません",
+            syndrome.z_syndrome().len(),
+            hz_rows
+        );
+    }
+}
+
+/// `BpDecoderCss`のビルダー
+/// 既定値は積和BP、並列スケジュール、最大反復回数n（量子ビット数）、スケーリング係数0.75、
+/// シリアルスケジュールの順序はランダム化しない
+pub struct BpDecoderCssBuilder<'a, C: ErrorChannel> {
+    code: &'a CssCode,
+    error_channel: &'a C,
+    bp_method: BpMethod,
+    schedule: BpSchedule,
+    max_iterations: usize,
+    ms_scaling_factor: f64,
+    random_serial_schedule: bool,
+}
+
+impl<C: ErrorChannel> BpDecoderCssBuilder<'_, C> {
+    pub fn method(mut self, bp_method: BpMethod) -> Self {
+        self.bp_method = bp_method;
+        self
+    }
+
+    pub fn schedule(mut self, schedule: BpSchedule) -> Self {
+        self.schedule = schedule;
+        self
+    }
+
+    pub fn max_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_iterations = max_iterations;
+        self
+    }
+
+    pub fn ms_scaling_factor(mut self, ms_scaling_factor: f64) -> Self {
+        self.ms_scaling_factor = ms_scaling_factor;
+        self
+    }
+
+    pub fn random_serial_schedule(mut self, random_serial_schedule: bool) -> Self {
+        self.random_serial_schedule = random_serial_schedule;
+        self
+    }
+
+    pub fn build(self) -> BpDecoderCss {
+        BpDecoderCss::new(
+            self.code,
+            self.error_channel,
+            self.bp_method,
+            self.schedule,
+            self.max_iterations,
+            self.ms_scaling_factor,
+            self.random_serial_schedule,
+        )
+    }
 }
 
 impl Decoder for BpDecoderCss {
@@ -63,46 +277,93 @@ impl Decoder for BpDecoderCss {
     }
 
     fn decode(&mut self, syndrome: &Syndrome) -> ErrorVector {
-        let syndrome_x = syndrome
-            .x_syndrome()
-            .as_bitslice()
-            .iter()
-            .map(|bit| if *bit { 1 } else { 0 })
-            .collect::<Vec<u8>>();
-        let syndrome_z = syndrome
-            .z_syndrome()
-            .as_bitslice()
-            .iter()
-            .map(|bit| if *bit { 1 } else { 0 })
-            .collect::<Vec<u8>>();
+        self.check_syndrome_len(syndrome);
+        let syndrome_x = syndrome.x_syndrome_u8();
+        let syndrome_z = syndrome.z_syndrome_u8();
 
         let error_z = self.decoder_x.decode(&syndrome_x);
         let error_x = self.decoder_z.decode(&syndrome_z);
 
         ErrorVector::from_u8vec(error_x, error_z)
     }
+
+    /// 2つの復号器が両方とも収束した場合に`converged = true`とし、反復回数は大きい方を返す
+    fn decode_detailed(&mut self, syndrome: &Syndrome) -> DecodeResult {
+        let recovery = self.decode(syndrome);
+        DecodeResult {
+            recovery,
+            converged: self.decoder_x.converge() && self.decoder_z.converge(),
+            iterations: self.decoder_x.iterations().max(self.decoder_z.iterations()),
+        }
+    }
+
+    /// X誤りとZ誤りそれぞれについて、測定誤りを含む時空間グラフ上でBP復号する
+    fn decode_rounds(&mut self, syndromes: &[Syndrome]) -> ErrorVector {
+        for syndrome in syndromes {
+            self.check_syndrome_len(syndrome);
+        }
+        let syndromes_x: Vec<Vec<u8>> = syndromes.iter().map(|s| s.x_syndrome_u8()).collect();
+        let syndromes_z: Vec<Vec<u8>> = syndromes.iter().map(|s| s.z_syndrome_u8()).collect();
+
+        let error_z = self.decoder_x.decode_rounds(&syndromes_x);
+        let error_x = self.decoder_z.decode_rounds(&syndromes_z);
+
+        ErrorVector::from_u8vec(error_x, error_z)
+    }
+}
+
+impl SyndromeReporting for BpDecoderCss {
+    fn residual_syndrome(&self, original: &Syndrome, recovery: &ErrorVector) -> Syndrome {
+        let recovery_z_syndrome = self.decoder_z.parity_check_matrix() * recovery.x_part();
+        let recovery_x_syndrome = self.decoder_x.parity_check_matrix() * recovery.z_part();
+        Syndrome::new(
+            original.z_syndrome().clone() ^ recovery_z_syndrome,
+            original.x_syndrome().clone() ^ recovery_x_syndrome,
+        )
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::math::sparse_matrix::BinarySparseMatrix;
+    use crate::Word;
+    use crate::code::named_codes::shor_code;
+    use crate::util::u8_to_bitvec;
     use bitvec::prelude::*;
+    use rand::RngCore;
+
+    /// X誤りとZ誤りの確率が大きく異なるテスト用のチャネル
+    struct AsymmetricChannel {
+        num_qubits: usize,
+        px: f64,
+        pz: f64,
+    }
+
+    impl ErrorChannel for AsymmetricChannel {
+        fn sample_with_rng(&self, _rng: &mut dyn RngCore) -> ErrorVector {
+            ErrorVector::from_u8vec(vec![0; self.num_qubits], vec![0; self.num_qubits])
+        }
+
+        fn num_qubits(&self) -> usize {
+            self.num_qubits
+        }
+
+        fn x_error_rate(&self) -> f64 {
+            self.px
+        }
+
+        fn y_error_rate(&self) -> f64 {
+            0.0
+        }
+
+        fn z_error_rate(&self) -> f64 {
+            self.pz
+        }
+    }
 
     #[test]
     fn test_bp_decoder_css_no_error() {
-        let hz_row_adj = vec![
-            vec![0, 1],
-            vec![1, 2],
-            vec![3, 4],
-            vec![4, 5],
-            vec![6, 7],
-            vec![7, 8],
-        ];
-        let hx_row_adj = vec![vec![0, 1, 2, 3, 4, 5], vec![3, 4, 5, 6, 7, 8]];
-        let hz = BinarySparseMatrix::from_row_adj(6, 9, hz_row_adj);
-        let hx = BinarySparseMatrix::from_row_adj(2, 9, hx_row_adj);
-        let css_code = CssCode::from_parity_check_matrices("TestCSS", hz, hx);
+        let css_code = shor_code();
 
         let channel = crate::channel::bit_flip::BitFlipChannel::new(9, 0.1);
         let mut decoder = BpDecoderCss::new(
@@ -115,8 +376,8 @@ mod tests {
             false,
         );
         let zero_syndrome = Syndrome::new(
-            bitvec![u64, Lsb0; 0; css_code.num_stabilizers()],
-            bitvec![u64, Lsb0; 0; css_code.num_stabilizers()],
+            bitvec![Word, Lsb0; 0; css_code.hz().rows()],
+            bitvec![Word, Lsb0; 0; css_code.hx().rows()],
         );
 
         let decoded_error = decoder.decode(&zero_syndrome);
@@ -125,18 +386,7 @@ mod tests {
 
     #[test]
     fn test_bp_decoder_css_one_error() {
-        let hz_row_adj = vec![
-            vec![0, 1],
-            vec![1, 2],
-            vec![3, 4],
-            vec![4, 5],
-            vec![6, 7],
-            vec![7, 8],
-        ];
-        let hx_row_adj = vec![vec![0, 1, 2, 3, 4, 5], vec![3, 4, 5, 6, 7, 8]];
-        let hz = BinarySparseMatrix::from_row_adj(6, 9, hz_row_adj);
-        let hx = BinarySparseMatrix::from_row_adj(2, 9, hx_row_adj);
-        let css_code = CssCode::from_parity_check_matrices("TestCSS", hz, hx);
+        let css_code = shor_code();
 
         let channel = crate::channel::bit_flip::BitFlipChannel::new(9, 0.1);
         let mut decoder = BpDecoderCss::new(
@@ -156,8 +406,349 @@ mod tests {
         let decoded_error = decoder.decode(&syndrome);
         assert_eq!(
             decoded_error.x_part(),
-            &bitvec![u64, Lsb0; 1, 0, 0, 0, 0, 0, 0, 0, 0]
+            &u8_to_bitvec(&[1, 0, 0, 0, 0, 0, 0, 0, 0])
+        );
+        assert_eq!(decoded_error.z_part(), &bitvec![Word, Lsb0; 0; 9]);
+    }
+
+    #[test]
+    fn test_bp_decoder_css_residual_syndrome() {
+        let css_code = shor_code();
+
+        let channel = crate::channel::depolarizing::DepolarizingChannel::new(9, 0.1);
+        let mut decoder = BpDecoderCss::new(
+            &css_code,
+            &channel,
+            BpMethod::ProductSum,
+            BpSchedule::Parallel,
+            10,
+            0.75,
+            false,
+        );
+
+        // qubit 4 に X エラー
+        let error_vector = ErrorVector::from_string("IIIIXIIII");
+        let syndrome = css_code.syndrome(&error_vector);
+        let decoded_error = decoder.decode(&syndrome);
+
+        let residual = decoder.residual_syndrome(&syndrome, &decoded_error);
+        assert_eq!(residual.z_syndrome(), &bitvec![Word, Lsb0; 0; 6]);
+        assert_eq!(residual.x_syndrome(), &bitvec![Word, Lsb0; 0; 2]);
+
+        // 何も訂正しなければ元のシンドロームがそのまま残る
+        let no_recovery = ErrorVector::from_string("IIIIIIIII");
+        let residual = decoder.residual_syndrome(&syndrome, &no_recovery);
+        assert_eq!(residual.z_syndrome(), syndrome.z_syndrome());
+        assert_eq!(residual.x_syndrome(), syndrome.x_syndrome());
+    }
+
+    #[test]
+    fn test_bp_decoder_css_decode_batch() {
+        let css_code = shor_code();
+
+        let channel = crate::channel::depolarizing::DepolarizingChannel::new(9, 0.1);
+        let mut decoder = BpDecoderCss::new(
+            &css_code,
+            &channel,
+            BpMethod::ProductSum,
+            BpSchedule::Parallel,
+            10,
+            0.75,
+            false,
+        );
+
+        let syndromes: Vec<Syndrome> = channel
+            .sample_batch(50)
+            .iter()
+            .map(|error| css_code.syndrome(error))
+            .collect();
+
+        let batch_result = decoder.decode_batch(&syndromes);
+        let sequential_result: Vec<ErrorVector> = syndromes
+            .iter()
+            .map(|syndrome| decoder.decode(syndrome))
+            .collect();
+        assert_eq!(batch_result, sequential_result);
+    }
+
+    #[test]
+    fn test_bp_decoder_css_soft_output() {
+        let css_code = shor_code();
+
+        let channel = crate::channel::bit_flip::BitFlipChannel::new(9, 0.1);
+        let mut decoder = BpDecoderCss::new(
+            &css_code,
+            &channel,
+            BpMethod::ProductSum,
+            BpSchedule::Parallel,
+            10,
+            0.75,
+            false,
+        );
+
+        // 両隣のチェックが反応する qubit 1 に X エラー
+        let error_vector = ErrorVector::from_string("IXIIIIIII");
+        let syndrome = css_code.syndrome(&error_vector);
+        decoder.decode(&syndrome);
+
+        let (x_llrs, z_llrs) = decoder.soft_output();
+        assert_eq!(x_llrs.len(), 9);
+        assert_eq!(z_llrs.len(), 9);
+        assert!(x_llrs[1] < 0.0);
+        assert!(
+            x_llrs
+                .iter()
+                .enumerate()
+                .filter(|&(qubit_idx, _)| qubit_idx != 1)
+                .all(|(_, &llr)| llr > 0.0)
+        );
+        assert!(z_llrs.iter().all(|&llr| llr > 0.0));
+    }
+
+    #[test]
+    fn test_bp_decoder_css_asymmetric_priors() {
+        let css_code = shor_code();
+
+        let channel = AsymmetricChannel {
+            num_qubits: 9,
+            px: 0.2,
+            pz: 0.001,
+        };
+        let mut decoder = BpDecoderCss::new(
+            &css_code,
+            &channel,
+            BpMethod::ProductSum,
+            BpSchedule::Parallel,
+            10,
+            0.75,
+            false,
+        );
+
+        let error_vector = ErrorVector::from_string("IIIIIIIII");
+        let syndrome = css_code.syndrome(&error_vector);
+        decoder.decode(&syndrome);
+
+        // X誤りの方が起こりやすいので、X誤りの事後LLRの方が小さくなる
+        let (x_llrs, z_llrs) = decoder.soft_output();
+        for (x_llr, z_llr) in x_llrs.iter().zip(&z_llrs) {
+            assert!(x_llr < z_llr);
+        }
+    }
+
+    #[test]
+    fn test_bp_decoder_css_tune_scaling_zero_noise() {
+        let css_code = shor_code();
+
+        // 誤りが起こらないので全候補の論理誤り率は0で並び、最初の候補が選ばれる
+        let channel = crate::channel::depolarizing::DepolarizingChannel::new(9, 0.0);
+        let candidates = [0.625, 0.75, 1.0];
+        let alpha = BpDecoderCss::tune_scaling(&css_code, &channel, &candidates, 20, 0);
+        assert_eq!(alpha, candidates[0]);
+    }
+
+    #[test]
+    fn test_bp_decoder_css_tune_scaling_reproducible() {
+        let css_code = shor_code();
+
+        // 同じシードなら同じ誤りで評価されるので、同じ係数が選ばれる
+        let channel = crate::channel::depolarizing::DepolarizingChannel::new(9, 0.1);
+        let candidates = [0.5, 0.625, 0.75, 0.875, 1.0];
+        let alpha = BpDecoderCss::tune_scaling(&css_code, &channel, &candidates, 200, 42);
+        assert!(candidates.contains(&alpha));
+        assert_eq!(
+            BpDecoderCss::tune_scaling(&css_code, &channel, &candidates, 200, 42),
+            alpha
+        );
+    }
+
+    #[test]
+    fn test_bp_decoder_css_set_error_rates() {
+        let css_code = shor_code();
+
+        let initial_channel = crate::channel::depolarizing::DepolarizingChannel::new(9, 0.2);
+        let target_channel = crate::channel::depolarizing::DepolarizingChannel::new(9, 0.03);
+        let mut reused = BpDecoderCss::new(
+            &css_code,
+            &initial_channel,
+            BpMethod::ProductSum,
+            BpSchedule::Parallel,
+            10,
+            0.75,
+            false,
+        );
+        reused.set_error_rates(
+            target_channel.x_error_rate(),
+            target_channel.y_error_rate(),
+            target_channel.z_error_rate(),
+        );
+        let mut fresh = BpDecoderCss::new(
+            &css_code,
+            &target_channel,
+            BpMethod::ProductSum,
+            BpSchedule::Parallel,
+            10,
+            0.75,
+            false,
+        );
+
+        for error in target_channel.sample_batch_with_seed(50, 3) {
+            let syndrome = css_code.syndrome(&error);
+            assert_eq!(reused.decode(&syndrome), fresh.decode(&syndrome));
+            assert_eq!(reused.soft_output(), fresh.soft_output());
+        }
+    }
+
+    #[test]
+    fn test_bp_decoder_css_builder_defaults() {
+        let css_code = shor_code();
+
+        let channel = crate::channel::depolarizing::DepolarizingChannel::new(9, 0.1);
+        let mut built = BpDecoderCss::builder(&css_code, &channel).build();
+        let mut constructed = BpDecoderCss::new(
+            &css_code,
+            &channel,
+            BpMethod::ProductSum,
+            BpSchedule::Parallel,
+            9,
+            0.75,
+            false,
+        );
+
+        for error in channel.sample_batch_with_seed(50, 5) {
+            let syndrome = css_code.syndrome(&error);
+            assert_eq!(built.decode(&syndrome), constructed.decode(&syndrome));
+            assert_eq!(built.soft_output(), constructed.soft_output());
+        }
+    }
+
+    #[test]
+    fn test_bp_decoder_css_decode_rounds_repeated_syndrome() {
+        let css_code = shor_code();
+
+        let channel = crate::channel::depolarizing::DepolarizingChannel::new(9, 0.05);
+        let mut decoder = BpDecoderCss::new(
+            &css_code,
+            &channel,
+            BpMethod::ProductSum,
+            BpSchedule::Parallel,
+            9,
+            0.75,
+            false,
+        );
+
+        // 測定誤りがなければ、同じシンドロームを繰り返し測定しても単発の復号と一致する
+        for qubit in 0..9 {
+            let mut x_errors = vec![0; 9];
+            x_errors[qubit] = 1;
+            let error_vector = ErrorVector::from_u8vec(x_errors, vec![0; 9]);
+            let syndrome = css_code.syndrome(&error_vector);
+
+            let single_shot = decoder.decode(&syndrome);
+            let rounds: Vec<Syndrome> = (0..3).map(|_| css_code.syndrome(&error_vector)).collect();
+            assert_eq!(decoder.decode_rounds(&rounds), single_shot);
+        }
+    }
+
+    #[test]
+    fn test_bp_decoder_css_decode_rounds_measurement_error() {
+        let css_code = shor_code();
+
+        let channel = crate::channel::depolarizing::DepolarizingChannel::new(9, 0.05);
+        let mut decoder = BpDecoderCss::new(
+            &css_code,
+            &channel,
+            BpMethod::ProductSum,
+            BpSchedule::Parallel,
+            9,
+            0.75,
+            false,
+        );
+
+        // 1ラウンド目だけZスタビライザー0が誤って反転した場合は測定誤りとみなし、何も訂正しない
+        let flipped = Syndrome::from_u8vec(vec![1, 0, 0, 0, 0, 0], vec![0, 0]);
+        let rounds = vec![
+            flipped,
+            Syndrome::from_u8vec(vec![0; 6], vec![0; 2]),
+            Syndrome::from_u8vec(vec![0; 6], vec![0; 2]),
+        ];
+        let decoded = decoder.decode_rounds(&rounds);
+        assert_eq!(decoded.num_errors(), 0);
+    }
+
+    #[test]
+    fn test_bp_decoder_css_decode_with_erasure() {
+        let css_code = shor_code();
+
+        let channel =
+            crate::channel::erasure_pauli::ErasurePauliChannel::new(9, 0.1, 0.01, 0.01, 0.01);
+        let mut decoder = BpDecoderCss::builder(&css_code, &channel).build();
+        let (px, py, pz) = channel.pauli_error_rates();
+        decoder.set_error_rates(px, py, pz);
+
+        // 重み2の誤りだが、全て消失した量子ビット上にある
+        let error = ErrorVector::from_string("IIIXXIIII");
+        let mut erased = vec![false; 9];
+        erased[3] = true;
+        erased[4] = true;
+
+        let syndrome = css_code.syndrome(&error);
+        let decoded = decoder.decode_with_erasure(&syndrome, &erased);
+        let residual = &decoded ^ &error;
+        assert!(css_code.syndrome(&residual).is_trivial());
+        assert!((&css_code.lz() * residual.x_part()).not_any());
+        assert!((&css_code.lx() * residual.z_part()).not_any());
+
+        // 消失の情報がなければ X5 と推定してしまい、論理誤りになる
+        let decoded_without_erasure = decoder.decode(&css_code.syndrome(&error));
+        let residual = &decoded_without_erasure ^ &error;
+        assert!((&css_code.lz() * residual.x_part()).any());
+    }
+
+    #[test]
+    fn test_bp_decoder_css_decode_detailed() {
+        let css_code = shor_code();
+
+        let channel = crate::channel::depolarizing::DepolarizingChannel::new(9, 0.05);
+        let mut decoder = BpDecoderCss::builder(&css_code, &channel).build();
+
+        let error = ErrorVector::from_string("IIIIXIIII");
+        let result = decoder.decode_detailed(&css_code.syndrome(&error));
+        assert_eq!(result.recovery, error);
+        assert!(result.converged);
+        assert!(result.iterations >= 1);
+    }
+
+    #[test]
+    fn test_bp_decoder_css_soft_decision_matches_hard_decision() {
+        let css_code = shor_code();
+
+        let channel = crate::channel::depolarizing::DepolarizingChannel::new(9, 0.05);
+        let mut decoder = BpDecoderCss::builder(&css_code, &channel).build();
+
+        let error = ErrorVector::from_string("IYIIIIXII");
+        let decoded = decoder.decode(&css_code.syndrome(&error));
+        let (x_llrs, z_llrs) = decoder.soft_decision();
+        for (llrs, hard) in [(x_llrs, decoded.x_part()), (z_llrs, decoded.z_part())] {
+            assert_eq!(llrs.len(), 9);
+            for (llr, bit) in llrs.iter().zip(hard.iter()) {
+                assert!(llr.is_finite());
+                assert_eq!(*llr <= 0.0, *bit);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Xシンドロームの長さ(8)がH_Xの行数(2)と一致しません")]
+    fn test_bp_decoder_css_syndrome_length_mismatch() {
+        let css_code = shor_code();
+
+        let channel = crate::channel::bit_flip::BitFlipChannel::new(9, 0.1);
+        let mut decoder = BpDecoderCss::builder(&css_code, &channel).build();
+        // 2つのシンドロームの長さをどちらも生成子の総数にしてしまった場合
+        let syndrome = Syndrome::new(
+            bitvec![Word, Lsb0; 0; css_code.num_stabilizers()],
+            bitvec![Word, Lsb0; 0; css_code.num_stabilizers()],
         );
-        assert_eq!(decoded_error.z_part(), &bitvec![u64, Lsb0; 0; 9]);
+        decoder.decode(&syndrome);
     }
 }
@@ -0,0 +1,233 @@
+use crate::Word;
+use crate::decoder::bp::BpDecoder;
+use crate::util::u8_to_bitvec;
+use bitvec::prelude::*;
+
+/// BPの後に順序統計量復号(OSD)による後処理を行う復号器
+/// BPが収束しなかった場合、事後LLRの小さい（誤りの可能性が高い）順に列を並べ、
+/// 線形独立な列を情報集合として選んでシンドロームと整合する復元操作を求める(OSD-0)
+/// `osd_order`が1以上なら、情報集合に含まれない列のうち重みが`osd_order`以下の組み合わせを
+/// 全て反転させて解き直し、重みが最小の復元操作を残す
+/// 試す組み合わせの数は C(n - rank, osd_order) 程度で増えるため、大きな符号では小さな値にする
+#[derive(Clone)]
+pub struct BpOsdDecoder {
+    bp_decoder: BpDecoder,
+    osd_order: usize,
+}
+
+/// 列を並べ替えてガウスの消去法を行った結果
+struct OsdSystem {
+    /// 簡約後の検査行列の各行（列は元のインデックスのまま）
+    rows: Vec<BitVec<Word, Lsb0>>,
+    /// 簡約後のシンドローム
+    syndrome: BitVec<Word, Lsb0>,
+    /// i行目のピボットになった列
+    pivot_cols: Vec<usize>,
+    /// ピボットにならなかった列（信頼度の低い順）
+    free_cols: Vec<usize>,
+}
+
+impl BpOsdDecoder {
+    pub fn new(bp_decoder: BpDecoder, osd_order: usize) -> Self {
+        Self {
+            bp_decoder,
+            osd_order,
+        }
+    }
+
+    pub fn bp_decoder(&self) -> &BpDecoder {
+        &self.bp_decoder
+    }
+
+    pub fn osd_order(&self) -> usize {
+        self.osd_order
+    }
+
+    /// BPで復号し、収束しなければOSDで後処理した結果を返す
+    /// シンドロームが検査行列の列空間に含まれない場合はBPの出力をそのまま返す
+    pub fn decode(&mut self, syndrome: &Vec<u8>) -> Vec<u8> {
+        let decoding = self.bp_decoder.decode(syndrome);
+        if self.bp_decoder.converge() {
+            return decoding;
+        }
+
+        let Some(system) = self.eliminate(syndrome) else {
+            return decoding;
+        };
+
+        let mut best = system.solve(&[]);
+        let mut best_weight = best.iter().filter(|&&bit| bit != 0).count();
+        for weight in 1..=self.osd_order.min(system.free_cols.len()) {
+            let mut combination: Vec<usize> = (0..weight).collect();
+            loop {
+                let flipped: Vec<usize> =
+                    combination.iter().map(|&i| system.free_cols[i]).collect();
+                let candidate = system.solve(&flipped);
+                let candidate_weight = candidate.iter().filter(|&&bit| bit != 0).count();
+                if candidate_weight < best_weight {
+                    best = candidate;
+                    best_weight = candidate_weight;
+                }
+                if !next_combination(&mut combination, system.free_cols.len()) {
+                    break;
+                }
+            }
+        }
+        best
+    }
+
+    /// 事後LLRの小さい列から順にピボットを選んで検査行列を簡約する
+    /// シンドロームと整合する解が存在しなければ`None`を返す
+    fn eliminate(&self, syndrome: &[u8]) -> Option<OsdSystem> {
+        let pcm = self.bp_decoder.parity_check_matrix();
+        let (n_rows, n_cols) = pcm.shape();
+
+        let llrs = self.bp_decoder.log_prob_ratios();
+        let mut order: Vec<usize> = (0..n_cols).collect();
+        order.sort_by(|&a, &b| llrs[a].total_cmp(&llrs[b]));
+
+        let mut rows: Vec<BitVec<Word, Lsb0>> = (0..n_rows).map(|i| pcm.row(i)).collect();
+        let mut reduced_syndrome = u8_to_bitvec(syndrome);
+        let mut pivot_cols = Vec::new();
+        let mut free_cols = Vec::new();
+
+        for col in order {
+            let rank = pivot_cols.len();
+            let Some(pivot) = (rank..n_rows).find(|&row| rows[row][col]) else {
+                free_cols.push(col);
+                continue;
+            };
+            rows.swap(rank, pivot);
+            reduced_syndrome.swap(rank, pivot);
+
+            let pivot_row = rows[rank].clone();
+            let pivot_bit = reduced_syndrome[rank];
+            for row in 0..n_rows {
+                if row != rank && rows[row][col] {
+                    rows[row] ^= &pivot_row;
+                    let bit = reduced_syndrome[row] ^ pivot_bit;
+                    reduced_syndrome.set(row, bit);
+                }
+            }
+            pivot_cols.push(col);
+        }
+
+        if reduced_syndrome[pivot_cols.len()..].any() {
+            return None;
+        }
+        Some(OsdSystem {
+            rows,
+            syndrome: reduced_syndrome,
+            pivot_cols,
+            free_cols,
+        })
+    }
+}
+
+impl OsdSystem {
+    /// 情報集合に含まれない列のうち`flipped`だけを1とし、残りのビットを解いた復元操作を返す
+    fn solve(&self, flipped: &[usize]) -> Vec<u8> {
+        let n_cols = self.pivot_cols.len() + self.free_cols.len();
+        let mut decoding = vec![0u8; n_cols];
+        for &col in flipped {
+            decoding[col] = 1;
+        }
+        for (row, &pivot_col) in self.pivot_cols.iter().enumerate() {
+            let mut bit = self.syndrome[row];
+            for &col in flipped {
+                bit ^= self.rows[row][col];
+            }
+            decoding[pivot_col] = bit as u8;
+        }
+        decoding
+    }
+}
+
+/// 0..nから選んだ昇順のインデックスの組み合わせを辞書順で次に進める
+/// 最後の組み合わせだった場合は`false`を返す
+fn next_combination(combination: &mut [usize], n: usize) -> bool {
+    let k = combination.len();
+    let Some(i) = (0..k).rev().find(|&i| combination[i] < n - k + i) else {
+        return false;
+    };
+    combination[i] += 1;
+    for j in i + 1..k {
+        combination[j] = combination[j - 1] + 1;
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoder::bp::{BpMethod, BpSchedule};
+    use crate::math::sparse_matrix::BinarySparseMatrix;
+
+    /// 先頭4列が単位行列で、列4と列5がそれぞれ2つのチェックに含まれる検査行列
+    /// 列4と列5の誤りは、先頭4列の誤りと同じシンドロームを持つ
+    fn pcm() -> BinarySparseMatrix {
+        BinarySparseMatrix::from_row_adj(4, 6, vec![vec![0, 4], vec![1, 4], vec![2, 5], vec![3, 5]])
+    }
+
+    fn bp_decoder(max_iterations: usize) -> BpDecoder {
+        BpDecoder::from_pcm(
+            pcm(),
+            BpMethod::ProductSum,
+            BpSchedule::Parallel,
+            max_iterations,
+            0.0,
+            false,
+            vec![0.1; 6],
+        )
+    }
+
+    #[test]
+    fn test_bp_osd_higher_order_finds_lower_weight() {
+        let error = vec![0, 0, 0, 0, 1, 1];
+        let syndrome = &pcm() * &error;
+
+        // 反復回数0のBPは収束せず、LLRが全て等しいので列はインデックス順に並ぶ
+        // OSD-0は先頭4列を情報集合に選び、重み4の復元操作を返す
+        let mut osd0 = BpOsdDecoder::new(bp_decoder(0), 0);
+        let decoding0 = osd0.decode(&syndrome);
+        assert_eq!(&pcm() * &decoding0, syndrome);
+        assert_eq!(decoding0, vec![1, 1, 1, 1, 0, 0]);
+
+        // 1列だけの反転では重み3までしか下がらない
+        let mut osd1 = BpOsdDecoder::new(bp_decoder(0), 1);
+        let decoding1 = osd1.decode(&syndrome);
+        assert_eq!(decoding1.iter().filter(|&&bit| bit != 0).count(), 3);
+
+        let mut osd2 = BpOsdDecoder::new(bp_decoder(0), 2);
+        assert_eq!(osd2.decode(&syndrome), error);
+    }
+
+    #[test]
+    fn test_bp_osd_returns_converged_bp_result() {
+        let error = vec![0, 0, 1, 0, 0, 0];
+        let syndrome = &pcm() * &error;
+        let mut decoder = BpOsdDecoder::new(bp_decoder(10), 2);
+        assert_eq!(decoder.decode(&syndrome), error);
+        assert!(decoder.bp_decoder().converge());
+    }
+
+    #[test]
+    fn test_next_combination() {
+        let mut combination = vec![0, 1];
+        let mut all = vec![combination.clone()];
+        while next_combination(&mut combination, 4) {
+            all.push(combination.clone());
+        }
+        assert_eq!(
+            all,
+            vec![
+                vec![0, 1],
+                vec![0, 2],
+                vec![0, 3],
+                vec![1, 2],
+                vec![1, 3],
+                vec![2, 3]
+            ]
+        );
+    }
+}
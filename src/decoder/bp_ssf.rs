@@ -0,0 +1,141 @@
+use crate::decoder::bp::BpDecoder;
+use crate::math::sparse_matrix::BinarySparseMatrix;
+
+/// BPの後にsmall-set-flip(SSF)による後処理を行う復号器
+/// BPの出力で満たされないチェックが残った場合、満たされないチェックの数を最も減らすビットを
+/// 貪欲に1つずつ反転させる。どのビットを反転させても減らなくなるか、反転回数の上限に達したら終了する
+#[derive(Clone)]
+pub struct BpSsfDecoder {
+    bp_decoder: BpDecoder,
+    max_flips: usize,
+}
+
+impl BpSsfDecoder {
+    pub fn new(bp_decoder: BpDecoder, max_flips: usize) -> Self {
+        Self {
+            bp_decoder,
+            max_flips,
+        }
+    }
+
+    pub fn bp_decoder(&self) -> &BpDecoder {
+        &self.bp_decoder
+    }
+
+    pub fn max_flips(&self) -> usize {
+        self.max_flips
+    }
+
+    pub fn decode(&mut self, syndrome: &Vec<u8>) -> Vec<u8> {
+        let mut decoding = self.bp_decoder.decode(syndrome);
+        let pcm = self.bp_decoder.parity_check_matrix();
+
+        // 各チェックが満たされていないかどうか
+        let mut unsatisfied: Vec<bool> = (pcm * &decoding)
+            .iter()
+            .zip(syndrome)
+            .map(|(candidate, target)| candidate != target)
+            .collect();
+
+        for _ in 0..self.max_flips {
+            let Some(bit_idx) = Self::best_flip(pcm, &unsatisfied) else {
+                break;
+            };
+            decoding[bit_idx] ^= 1;
+            for &check_idx in pcm.nonzero_rows(bit_idx) {
+                unsatisfied[check_idx] = !unsatisfied[check_idx];
+            }
+        }
+
+        decoding
+    }
+
+    /// 満たされていないチェックに含まれるビットのうち、反転させたときに
+    /// 満たされていないチェックの数を最も減らすものを返す
+    /// 減らせるビットがなければ`None`を返す
+    fn best_flip(pcm: &BinarySparseMatrix, unsatisfied: &[bool]) -> Option<usize> {
+        let mut best: Option<(usize, usize)> = None;
+        for (check_idx, _) in unsatisfied.iter().enumerate().filter(|(_, u)| **u) {
+            for &bit_idx in pcm.nonzero_cols(check_idx) {
+                let checks = pcm.nonzero_rows(bit_idx);
+                let num_unsatisfied = checks.iter().filter(|&&c| unsatisfied[c]).count();
+                let num_satisfied = checks.len() - num_unsatisfied;
+                if num_unsatisfied <= num_satisfied {
+                    continue;
+                }
+                let gain = num_unsatisfied - num_satisfied;
+                let is_better = match best {
+                    Some((best_gain, best_bit)) => {
+                        gain > best_gain || (gain == best_gain && bit_idx < best_bit)
+                    }
+                    None => true,
+                };
+                if is_better {
+                    best = Some((gain, bit_idx));
+                }
+            }
+        }
+        best.map(|(_, bit_idx)| bit_idx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::code::named_codes::shor_code;
+    use crate::decoder::bp::{BpMethod, BpSchedule};
+
+    fn shor_hz() -> BinarySparseMatrix {
+        shor_code().hz().clone()
+    }
+
+    #[test]
+    fn test_bp_ssf_resolves_unsatisfied_check() {
+        let hz = shor_hz();
+        let bp_decoder = BpDecoder::from_pcm(
+            hz.clone(),
+            BpMethod::MinimumSum,
+            BpSchedule::Parallel,
+            1,
+            0.75,
+            false,
+            vec![0.1; 9],
+        );
+
+        // qubit 2 の X エラー。最小和BPを1回だけ反復すると何も反転せず、チェック1が残る
+        let syndrome = vec![0, 1, 0, 0, 0, 0];
+        let mut bp_only = bp_decoder.clone();
+        let bp_decoding = bp_only.decode(&syndrome);
+        let bp_residual = &hz * &bp_decoding;
+        let num_unsatisfied = bp_residual
+            .iter()
+            .zip(&syndrome)
+            .filter(|(a, b)| a != b)
+            .count();
+        assert_eq!(num_unsatisfied, 1);
+
+        let mut decoder = BpSsfDecoder::new(bp_decoder, 10);
+        let decoding = decoder.decode(&syndrome);
+        assert_eq!(&hz * &decoding, syndrome);
+        assert_eq!(decoding, vec![0, 0, 1, 0, 0, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_bp_ssf_no_flips() {
+        let hz = shor_hz();
+        let bp_decoder = BpDecoder::from_pcm(
+            hz,
+            BpMethod::MinimumSum,
+            BpSchedule::Parallel,
+            1,
+            0.75,
+            false,
+            vec![0.1; 9],
+        );
+
+        // 反転回数の上限が0ならBPの出力がそのまま返る
+        let mut decoder = BpSsfDecoder::new(bp_decoder, 0);
+        let decoding = decoder.decode(&vec![0, 1, 0, 0, 0, 0]);
+        assert_eq!(decoding, vec![0; 9]);
+    }
+}
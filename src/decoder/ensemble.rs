@@ -0,0 +1,114 @@
+use crate::code::css_code::CssCode;
+use crate::code::error_vector::{ErrorVector, Syndrome};
+use crate::decoder::traits::Decoder;
+use crate::math::sparse_matrix::BinarySparseMatrix;
+
+/// 複数の復号器を順に実行し、シンドロームと整合する復元操作のうち重みが最小のものを返す復号器
+/// スケジュールやスケーリング係数の異なるBPを組み合わせ、1つの設定では収束しない難しい
+/// シンドロームを拾うために使う
+/// どの復号器もシンドロームと整合する復元操作を返さなかった場合は、最初の復号器の結果を返す
+pub struct EnsembleDecoder {
+    hz: BinarySparseMatrix,
+    hx: BinarySparseMatrix,
+    decoders: Vec<Box<dyn Decoder>>,
+}
+
+impl EnsembleDecoder {
+    /// 整合性の判定に使う符号と、実行する復号器のリストから構成する
+    pub fn new(code: &CssCode, decoders: Vec<Box<dyn Decoder>>) -> Self {
+        assert!(!decoders.is_empty(), "復号器が1つもありません");
+        Self {
+            hz: code.hz().clone(),
+            hx: code.hx().clone(),
+            decoders,
+        }
+    }
+
+    pub fn decoders(&self) -> &[Box<dyn Decoder>] {
+        &self.decoders
+    }
+}
+
+impl Decoder for EnsembleDecoder {
+    fn name(&self) -> &str {
+        "Ensemble Decoder"
+    }
+
+    fn decode(&mut self, syndrome: &Syndrome) -> ErrorVector {
+        let mut fallback = None;
+        let mut best: Option<ErrorVector> = None;
+
+        for decoder in self.decoders.iter_mut() {
+            let recovery = decoder.decode(syndrome);
+            let consistent = &self.hz * recovery.x_part() == *syndrome.z_syndrome()
+                && &self.hx * recovery.z_part() == *syndrome.x_syndrome();
+
+            if consistent {
+                let is_better = best
+                    .as_ref()
+                    .is_none_or(|current| recovery.num_errors() < current.num_errors());
+                if is_better {
+                    best = Some(recovery);
+                }
+            } else if fallback.is_none() {
+                fallback = Some(recovery);
+            }
+        }
+
+        best.or(fallback).expect("復号器が1つもありません")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::channel::depolarizing::DepolarizingChannel;
+    use crate::code::named_codes::shor_code;
+    use crate::decoder::bit_flip::BitFlipDecoderCss;
+    use crate::decoder::bp_css::BpDecoderCss;
+
+    /// 常に同じ復元操作を返すテスト用の復号器
+    struct FixedDecoder(ErrorVector);
+
+    impl Decoder for FixedDecoder {
+        fn name(&self) -> &str {
+            "Fixed Decoder"
+        }
+
+        fn decode(&mut self, _syndrome: &Syndrome) -> ErrorVector {
+            self.0.clone()
+        }
+    }
+
+    #[test]
+    fn test_ensemble_returns_successful_decoder() {
+        let code = shor_code();
+        let channel = DepolarizingChannel::new(9, 0.05);
+        // 反復回数0のビット反転復号器は何も訂正できない
+        let failing = BitFlipDecoderCss::new(&code, 0);
+        let succeeding = BpDecoderCss::builder(&code, &channel).build();
+        let mut ensemble =
+            EnsembleDecoder::new(&code, vec![Box::new(failing), Box::new(succeeding)]);
+
+        let error = ErrorVector::from_string("IIIIXIIII");
+        let decoded = ensemble.decode(&code.syndrome(&error));
+        assert_eq!(decoded, error);
+    }
+
+    #[test]
+    fn test_ensemble_prefers_minimum_weight() {
+        let code = shor_code();
+        let error = ErrorVector::from_string("IIIIXIIII");
+        // X3 X5 は X4 と同じシンドロームを持つが、重みが大きい
+        let heavy = ErrorVector::from_string("IIIXIXIII");
+        let mut ensemble = EnsembleDecoder::new(
+            &code,
+            vec![
+                Box::new(FixedDecoder(ErrorVector::from_string("IIIIIIIII"))),
+                Box::new(FixedDecoder(heavy)),
+                Box::new(FixedDecoder(error.clone())),
+            ],
+        );
+        assert_eq!(ensemble.decode(&code.syndrome(&error)), error);
+    }
+}
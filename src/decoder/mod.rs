@@ -1,3 +1,9 @@
 pub mod traits;
 pub mod bp;
-pub mod bp_css;
\ No newline at end of file
+pub mod bp_css;
+pub mod bp_ssf;
+pub mod bp_osd;
+pub mod single_shot;
+pub mod stabilizer_bp;
+pub mod bit_flip;
+pub mod ensemble;
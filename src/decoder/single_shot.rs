@@ -0,0 +1,137 @@
+use crate::code::css_code::CssCode;
+use crate::code::error_vector::{ErrorVector, Syndrome};
+use crate::decoder::bp::{BpDecoder, BpMethod, BpSchedule};
+use crate::decoder::bp_css::BpDecoderCss;
+use crate::decoder::traits::Decoder;
+use crate::math::sparse_matrix::BinarySparseMatrix;
+
+/// メタチェックを用いたシングルショット復号器
+/// まずメタチェック行列を検査行列とするBPでシンドロームの測定誤りを推定して取り除き、
+/// 整合したシンドロームをデータ量子ビットのBP復号器に渡す
+#[derive(Clone)]
+pub struct SingleShotBpDecoder {
+    /// M_X を検査行列に持ち、Xシンドロームの測定誤りを推定する
+    metacheck_x: BpDecoder,
+    /// M_Z を検査行列に持ち、Zシンドロームの測定誤りを推定する
+    metacheck_z: BpDecoder,
+    data_decoder: BpDecoderCss,
+}
+
+impl SingleShotBpDecoder {
+    /// `code`のメタチェックと、各シンドロームビットの測定誤り率から復号器を構成する
+    /// `code`は`CssCode::with_metachecks`でメタチェックが設定されている必要がある
+    pub fn new(code: &CssCode, data_decoder: BpDecoderCss, measurement_error_rate: f64) -> Self {
+        let mx = code.mx().expect("メタチェックが設定されていません").clone();
+        let mz = code.mz().expect("メタチェックが設定されていません").clone();
+        Self {
+            metacheck_x: Self::metacheck_decoder(mx, measurement_error_rate),
+            metacheck_z: Self::metacheck_decoder(mz, measurement_error_rate),
+            data_decoder,
+        }
+    }
+
+    fn metacheck_decoder(metacheck: BinarySparseMatrix, measurement_error_rate: f64) -> BpDecoder {
+        let syndrome_len = metacheck.cols();
+        BpDecoder::from_pcm(
+            metacheck,
+            BpMethod::ProductSum,
+            BpSchedule::Parallel,
+            syndrome_len.max(1),
+            0.75,
+            false,
+            vec![measurement_error_rate; syndrome_len],
+        )
+    }
+
+    pub fn data_decoder(&self) -> &BpDecoderCss {
+        &self.data_decoder
+    }
+
+    /// メタチェックの段階だけを実行し、測定誤りを取り除いたシンドロームを返す
+    pub fn clean_syndrome(&mut self, syndrome: &Syndrome) -> Syndrome {
+        let syndrome_z = Self::correct(&mut self.metacheck_z, syndrome.z_syndrome_u8());
+        let syndrome_x = Self::correct(&mut self.metacheck_x, syndrome.x_syndrome_u8());
+        Syndrome::from_u8vec(syndrome_z, syndrome_x)
+    }
+
+    fn correct(metacheck_decoder: &mut BpDecoder, mut syndrome: Vec<u8>) -> Vec<u8> {
+        let metasyndrome = metacheck_decoder.parity_check_matrix().mul_u8(&syndrome);
+        let measurement_error = metacheck_decoder.decode(&metasyndrome);
+        for (bit, flip) in syndrome.iter_mut().zip(measurement_error) {
+            *bit ^= flip;
+        }
+        syndrome
+    }
+}
+
+impl Decoder for SingleShotBpDecoder {
+    fn name(&self) -> &str {
+        "Single-shot BP Decoder"
+    }
+
+    fn decode(&mut self, syndrome: &Syndrome) -> ErrorVector {
+        let cleaned = self.clean_syndrome(syndrome);
+        self.data_decoder.decode(&cleaned)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::channel::bit_flip::BitFlipChannel;
+
+    /// 4ビットの繰り返し符号に全ての2ビット検査を入れ、三角形ごとのメタチェックを付けた符号
+    fn k4_code() -> CssCode {
+        let hz = BinarySparseMatrix::from_row_adj(
+            6,
+            4,
+            vec![
+                vec![0, 1],
+                vec![0, 2],
+                vec![0, 3],
+                vec![1, 2],
+                vec![1, 3],
+                vec![2, 3],
+            ],
+        );
+        let hx = BinarySparseMatrix::zeros(0, 4);
+        let mz = BinarySparseMatrix::from_row_adj(
+            4,
+            6,
+            vec![vec![0, 1, 3], vec![0, 2, 4], vec![1, 2, 5], vec![3, 4, 5]],
+        );
+        let mx = BinarySparseMatrix::zeros(0, 0);
+        CssCode::from_parity_check_matrices("K4", hz, hx).with_metachecks(mx, mz)
+    }
+
+    #[test]
+    fn test_single_shot_corrects_flipped_syndrome_bit() {
+        let code = k4_code();
+        let channel = BitFlipChannel::new(4, 0.05);
+        let data_decoder = BpDecoderCss::builder(&code, &channel).build();
+        let mut decoder = SingleShotBpDecoder::new(&code, data_decoder, 0.05);
+
+        // 量子ビット0のX誤りに加えて、検査(1,2)の測定結果が反転している
+        let error = ErrorVector::from_u8vec(vec![1, 0, 0, 0], vec![0; 4]);
+        let mut syndrome_z = code.syndrome(&error).z_syndrome_u8();
+        syndrome_z[3] ^= 1;
+        let noisy = Syndrome::from_u8vec(syndrome_z, vec![]);
+
+        let cleaned = decoder.clean_syndrome(&noisy);
+        assert_eq!(cleaned.z_syndrome_u8(), vec![1, 1, 1, 0, 0, 0]);
+
+        let decoded = decoder.decode(&noisy);
+        assert_eq!(decoded, error);
+    }
+
+    #[test]
+    #[should_panic(expected = "メタチェックが設定されていません")]
+    fn test_single_shot_requires_metachecks() {
+        let hz = BinarySparseMatrix::from_row_adj(2, 3, vec![vec![0, 1], vec![1, 2]]);
+        let hx = BinarySparseMatrix::zeros(0, 3);
+        let code = CssCode::from_parity_check_matrices("BitFlip", hz, hx);
+        let channel = BitFlipChannel::new(3, 0.05);
+        let data_decoder = BpDecoderCss::builder(&code, &channel).build();
+        let _ = SingleShotBpDecoder::new(&code, data_decoder, 0.05);
+    }
+}
@@ -0,0 +1,260 @@
+use crate::channel::traits::ErrorChannel;
+use crate::code::error_vector::{ErrorVector, Syndrome};
+use crate::code::stabilizer_code::StabilizerCode;
+use crate::code::traits::QuantumCode;
+use crate::decoder::traits::Decoder;
+use crate::util::bitvec_to_bool;
+
+/// 各量子ビットのPauli誤り I, X, Y, Z を (x, z) のビットの組で表したもの
+const PAULIS: [(bool, bool); 4] = [(false, false), (true, false), (true, true), (false, true)];
+
+/// 量子ビットから生成子へのメッセージを更新するときに、前回の値を残す割合
+/// 5量子ビット符号のように短いループだらけの小さな符号ではメッセージが振動しやすいため、減衰させる
+const MESSAGE_DAMPING: f64 = 0.5;
+
+/// 生成子と量子ビットの間のエッジ
+/// `pauli`はその生成子が量子ビットに作用するPauli演算子の (x, z) 成分
+#[derive(Clone)]
+struct StabilizerEdge {
+    check: usize,
+    qubit: usize,
+    pauli: (bool, bool),
+}
+
+/// 一般の（CSSとは限らない）スタビライザー符号に対する4元BP復号器
+/// 各量子ビットの誤りを {I, X, Y, Z} の4値の変数として扱い、シンプレクティックな検査行列の上で
+/// 確率領域のBPを行う。エッジ上のメッセージは「誤りがその生成子と反可換である確率」で表す
+///
+/// `Decoder`として使う場合、シンドロームは`Syndrome::to_bitvec`で連結したビット列を
+/// 生成子の順番に並べたものとして解釈する。非CSS符号では`Syndrome::new(syndrome, BitVec::new())`
+/// のように全体をZシンドローム側に入れて渡せばよい
+#[derive(Clone)]
+pub struct StabilizerBpDecoder {
+    num_qubits: usize,
+    num_checks: usize,
+    edges: Vec<StabilizerEdge>,
+    check_edges: Vec<Vec<usize>>,
+    qubit_edges: Vec<Vec<usize>>,
+    /// 各量子ビットの誤りが I, X, Y, Z である事前確率
+    priors: [f64; 4],
+    maximum_iterations: usize,
+    /// 量子ビットから生成子へのメッセージ（反可換である確率）
+    qubit_to_check_msg: Vec<f64>,
+    /// 生成子から量子ビットへのメッセージ（[可換, 反可換] の確率）
+    check_to_qubit_msg: Vec<[f64; 2]>,
+    converge: bool,
+    iterations: usize,
+}
+
+impl StabilizerBpDecoder {
+    pub fn new<C: ErrorChannel>(
+        code: &StabilizerCode,
+        error_channel: &C,
+        max_iterations: usize,
+    ) -> Self {
+        let num_qubits = code.n();
+        let generators = code.stabilizer_group().generators();
+        let num_checks = generators.len();
+
+        let mut edges = Vec::new();
+        let mut check_edges = vec![Vec::new(); num_checks];
+        let mut qubit_edges = vec![Vec::new(); num_qubits];
+        for (check, generator) in generators.iter().enumerate() {
+            for (qubit, edges_of_qubit) in qubit_edges.iter_mut().enumerate() {
+                let pauli = (generator.x_part()[qubit], generator.z_part()[qubit]);
+                if pauli == (false, false) {
+                    continue;
+                }
+                check_edges[check].push(edges.len());
+                edges_of_qubit.push(edges.len());
+                edges.push(StabilizerEdge {
+                    check,
+                    qubit,
+                    pauli,
+                });
+            }
+        }
+
+        let px = error_channel.x_error_rate();
+        let py = error_channel.y_error_rate();
+        let pz = error_channel.z_error_rate();
+        let priors = [1.0 - px - py - pz, px, py, pz];
+        let num_edges = edges.len();
+
+        Self {
+            num_qubits,
+            num_checks,
+            edges,
+            check_edges,
+            qubit_edges,
+            priors,
+            maximum_iterations: max_iterations,
+            qubit_to_check_msg: vec![0.0; num_edges],
+            check_to_qubit_msg: vec![[0.5, 0.5]; num_edges],
+            converge: false,
+            iterations: 0,
+        }
+    }
+
+    pub fn converge(&self) -> bool {
+        self.converge
+    }
+
+    pub fn iterations(&self) -> usize {
+        self.iterations
+    }
+
+    /// 生成子の順番に並べたシンドロームから誤りを推定する
+    pub fn decode_bits(&mut self, syndrome: &[bool]) -> ErrorVector {
+        assert_eq!(
+            syndrome.len(),
+            self.num_checks,
+            "シンドロームの長さ({})が生成子の数({})と一致しません",
+            syndrome.len(),
+            self.num_checks
+        );
+
+        self.converge = false;
+        self.iterations = 0;
+        for (edge_idx, edge) in self.edges.iter().enumerate() {
+            self.qubit_to_check_msg[edge_idx] = PAULIS
+                .iter()
+                .zip(&self.priors)
+                .filter(|(error, _)| anticommutes(**error, edge.pauli))
+                .map(|(_, prior)| prior)
+                .sum();
+        }
+
+        let mut decoding = vec![0usize; self.num_qubits];
+        for it in 1..=self.maximum_iterations {
+            self.iterations = it;
+
+            // --- チェックノード更新 ---
+            // 他のエッジの反可換性のパリティがシンドロームと一致する確率を計算する
+            for (check, edge_indices) in self.check_edges.iter().enumerate() {
+                for &edge_idx in edge_indices {
+                    let delta: f64 = edge_indices
+                        .iter()
+                        .filter(|&&other| other != edge_idx)
+                        .map(|&other| 1.0 - 2.0 * self.qubit_to_check_msg[other])
+                        .product();
+                    let sign = if syndrome[check] { -1.0 } else { 1.0 };
+                    self.check_to_qubit_msg[edge_idx] =
+                        [(1.0 + sign * delta) / 2.0, (1.0 - sign * delta) / 2.0];
+                }
+            }
+
+            // --- 変数ノード更新と硬判定 ---
+            for (decision, edge_indices) in decoding.iter_mut().zip(&self.qubit_edges) {
+                let mut beliefs = self.priors;
+                for (belief, &error) in beliefs.iter_mut().zip(&PAULIS) {
+                    for &edge_idx in edge_indices {
+                        let anti = anticommutes(error, self.edges[edge_idx].pauli);
+                        *belief *= self.check_to_qubit_msg[edge_idx][anti as usize];
+                    }
+                }
+
+                for &edge_idx in edge_indices {
+                    let edge_pauli = self.edges[edge_idx].pauli;
+                    let mut commuting = 0.0;
+                    let mut anticommuting = 0.0;
+                    for (prior, &error) in self.priors.iter().zip(&PAULIS) {
+                        let mut extrinsic = *prior;
+                        for &other in edge_indices.iter().filter(|&&other| other != edge_idx) {
+                            let anti = anticommutes(error, self.edges[other].pauli);
+                            extrinsic *= self.check_to_qubit_msg[other][anti as usize];
+                        }
+                        if anticommutes(error, edge_pauli) {
+                            anticommuting += extrinsic;
+                        } else {
+                            commuting += extrinsic;
+                        }
+                    }
+                    let total = commuting + anticommuting;
+                    let updated = if total > 0.0 {
+                        anticommuting / total
+                    } else {
+                        0.5
+                    };
+                    self.qubit_to_check_msg[edge_idx] = MESSAGE_DAMPING
+                        * self.qubit_to_check_msg[edge_idx]
+                        + (1.0 - MESSAGE_DAMPING) * updated;
+                }
+
+                *decision = (0..4)
+                    .max_by(|&a, &b| beliefs[a].total_cmp(&beliefs[b]).then(b.cmp(&a)))
+                    .unwrap();
+            }
+
+            if self.candidate_syndrome(&decoding) == syndrome {
+                self.converge = true;
+                break;
+            }
+        }
+
+        let x_errors = decoding.iter().map(|&e| PAULIS[e].0 as u8).collect();
+        let z_errors = decoding.iter().map(|&e| PAULIS[e].1 as u8).collect();
+        ErrorVector::from_u8vec(x_errors, z_errors)
+    }
+
+    fn candidate_syndrome(&self, decoding: &[usize]) -> Vec<bool> {
+        let mut syndrome = vec![false; self.num_checks];
+        for edge in &self.edges {
+            syndrome[edge.check] ^= anticommutes(PAULIS[decoding[edge.qubit]], edge.pauli);
+        }
+        syndrome
+    }
+}
+
+/// 1量子ビットのPauli演算子 (x, z) 同士が反可換かどうかを返す
+fn anticommutes(a: (bool, bool), b: (bool, bool)) -> bool {
+    (a.0 & b.1) ^ (a.1 & b.0)
+}
+
+impl Decoder for StabilizerBpDecoder {
+    fn name(&self) -> &str {
+        "Quaternary BP Decoder for Stabilizer Codes"
+    }
+
+    fn decode(&mut self, syndrome: &Syndrome) -> ErrorVector {
+        self.decode_bits(&bitvec_to_bool(&syndrome.to_bitvec()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::channel::depolarizing::DepolarizingChannel;
+    use crate::code::named_codes::five_qubit_code;
+    use crate::util::bool_to_bitvec;
+    use bitvec::prelude::*;
+
+    #[test]
+    fn test_stabilizer_bp_no_error() {
+        let code = five_qubit_code();
+        let channel = DepolarizingChannel::new(5, 0.05);
+        let mut decoder = StabilizerBpDecoder::new(&code, &channel, 10);
+
+        let decoded = decoder.decode_bits(&[false; 4]);
+        assert_eq!(decoded.num_errors(), 0);
+        assert!(decoder.converge());
+    }
+
+    #[test]
+    fn test_stabilizer_bp_five_qubit_code_single_errors() {
+        let code = five_qubit_code();
+        let channel = DepolarizingChannel::new(5, 0.05);
+        let mut decoder = StabilizerBpDecoder::new(&code, &channel, 10);
+
+        for error in ErrorVector::enumerate_up_to_weight(5, 1).skip(1) {
+            let syndrome = code.syndrome(&error);
+            let decoded = decoder.decode(&Syndrome::new(bool_to_bitvec(&syndrome), BitVec::new()));
+            assert!(decoder.converge());
+
+            // 残差がスタビライザーに含まれれば（シンドロームも論理演算子も自明なら）訂正成功
+            let residual = &decoded ^ &error;
+            assert!(code.syndrome(&residual).iter().all(|&bit| !bit));
+            assert!(code.measure_logicals(&residual).iter().all(|&bit| !bit));
+        }
+    }
+}
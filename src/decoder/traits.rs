@@ -1,7 +1,46 @@
 use crate::code::error_vector::ErrorVector;
 use crate::code::error_vector::Syndrome;
 
+/// 復元操作と、復号器が内部で判定した収束の情報をまとめた復号結果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodeResult {
+    pub recovery: ErrorVector,
+    /// 復元操作のシンドロームが入力のシンドロームと一致したかどうか
+    pub converged: bool,
+    /// 復号に要した反復回数
+    pub iterations: usize,
+}
+
 pub trait Decoder {
     fn name(&self) -> &str;
     fn decode(&mut self, syndrome: &Syndrome) -> ErrorVector;
+
+    /// 復元操作に加えて、収束したかどうかと反復回数を返す
+    /// デフォルトでは`decode`を呼び、収束の情報を持たないものとして`converged = false, iterations = 0`を返す
+    fn decode_detailed(&mut self, syndrome: &Syndrome) -> DecodeResult {
+        DecodeResult {
+            recovery: self.decode(syndrome),
+            converged: false,
+            iterations: 0,
+        }
+    }
+
+    /// 複数ラウンドのシンドローム測定をまとめて復号する
+    /// デフォルトでは最終ラウンドのシンドロームだけを単発で復号し、それより前のラウンドと測定誤りは無視する
+    /// 時空間グラフ上で測定誤りも含めて復号するのは、`BpDecoderCss`のようにこれを上書きした復号器だけである
+    fn decode_rounds(&mut self, syndromes: &[Syndrome]) -> ErrorVector {
+        self.decode(
+            syndromes
+                .last()
+                .expect("シンドロームが1ラウンドもありません"),
+        )
+    }
+}
+
+/// 復号結果で説明しきれなかったシンドロームを報告するための拡張Trait
+/// BPが収束しなかった場合などに、どのスタビライザーが残っているかを調べるのに使う
+pub trait SyndromeReporting {
+    /// 元のシンドロームと復元操作のシンドロームのXOR(残差シンドローム)を計算する
+    /// 復号が成功していれば全て0になる
+    fn residual_syndrome(&self, original: &Syndrome, recovery: &ErrorVector) -> Syndrome;
 }
@@ -1,39 +1,94 @@
+/// ビットベクトル`BitVec<Word, Lsb0>`の格納に使うワードの型
+/// 既定は`u64`で、`bits32`フィーチャーを有効にすると`u32`になる
+#[cfg(not(feature = "bits32"))]
+pub type Word = u64;
+#[cfg(feature = "bits32")]
+pub type Word = u32;
+
 pub mod math {
     pub mod bit_linear_algebra;
+    pub mod quasi_cyclic;
     pub mod sparse_matrix;
 }
 
 pub mod code {
     pub mod binary_symplectic;
+    pub mod classical;
+    pub mod color_code;
+    pub mod concatenated;
     pub mod css_code;
     pub mod error_vector;
+    pub mod generalized_bicycle;
+    pub mod lifted_product;
+    pub mod named_codes;
     pub mod paulis;
+    pub mod random_ldpc;
     pub mod stabilizer;
     pub mod stabilizer_code;
+    pub mod surface_code;
     pub mod traits;
 }
 
 pub mod channel {
     pub mod bit_flip;
+    pub mod composed;
+    pub mod correlated;
     pub mod depolarizing;
+    pub mod detector_error_model;
+    pub mod erasure;
+    pub mod erasure_pauli;
+    pub mod single_pauli;
     pub mod traits;
 }
 
 pub mod decoder {
+    pub mod bit_flip;
     pub mod bp;
     pub mod bp_css;
+    pub mod bp_osd;
+    pub mod bp_ssf;
+    pub mod ensemble;
+    pub mod single_shot;
+    pub mod stabilizer_bp;
     pub mod traits;
 }
 
+pub mod util;
+
+pub mod sim {
+    pub mod fuzz;
+    pub mod simulate;
+    pub mod stats;
+    pub mod threshold;
+
+    pub use fuzz::{FuzzReport, fuzz_decoder};
+    pub use simulate::{SimResult, simulate};
+    pub use threshold::pseudo_threshold;
+}
+
 pub mod prelude {
     pub use crate::channel::bit_flip::BitFlipChannel;
+    pub use crate::channel::composed::ComposedChannel;
+    pub use crate::channel::correlated::CorrelatedChannel;
     pub use crate::channel::depolarizing::DepolarizingChannel;
-    pub use crate::channel::traits::ErrorChannel;
-    pub use crate::code::css_code::CssCode;
+    pub use crate::channel::detector_error_model::DetectorErrorModel;
+    pub use crate::channel::erasure::ErasureChannel;
+    pub use crate::channel::erasure_pauli::ErasurePauliChannel;
+    pub use crate::channel::single_pauli::SinglePauliChannel;
+    pub use crate::channel::traits::{ErasureErrorChannel, ErrorChannel};
+    pub use crate::code::classical::ClassicalCode;
+    pub use crate::code::css_code::{CssCode, CssCodeError};
+    pub use crate::code::paulis::PauliType;
     pub use crate::code::stabilizer_code::StabilizerCode;
     pub use crate::code::traits::QuantumCode;
+    pub use crate::decoder::bit_flip::{BitFlipDecoder, BitFlipDecoderCss};
     pub use crate::decoder::bp::*;
     pub use crate::decoder::bp_css::*;
-    pub use crate::decoder::traits::Decoder;
+    pub use crate::decoder::bp_osd::BpOsdDecoder;
+    pub use crate::decoder::bp_ssf::BpSsfDecoder;
+    pub use crate::decoder::ensemble::EnsembleDecoder;
+    pub use crate::decoder::single_shot::SingleShotBpDecoder;
+    pub use crate::decoder::stabilizer_bp::StabilizerBpDecoder;
+    pub use crate::decoder::traits::{DecodeResult, Decoder, SyndromeReporting};
     pub use crate::math::sparse_matrix::BinarySparseMatrix;
 }
@@ -19,7 +19,7 @@ fn main() {
         hz.into_sparse_matrix(),
         hx.into_sparse_matrix(),
     );
-    let channel = DepolarizingChannel::new(9, 0.0001);
+    let channel = DepolarizingChannel::seeded(9, 0.0001, 2024);
     let num_samples = 1000000;
     let error_batch = channel.sample_batch(num_samples);
 
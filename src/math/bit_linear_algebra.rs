@@ -1,14 +1,24 @@
+use crate::Word;
 use bitvec::prelude::*;
 use std::ops::Mul;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct BinaryDenseMatrix {
-    data: Vec<BitVec<u64, Lsb0>>,
+    data: Vec<BitVec<Word, Lsb0>>,
+    /// 行が0本の場合にも列数を保持するために、列数を別に持つ
+    n_cols: usize,
 }
 
 impl BinaryDenseMatrix {
-    pub fn new(data: Vec<BitVec<u64, Lsb0>>) -> Self {
-        let n_cols = data[0].len();
+    /// 行のリストから行列を構成する
+    /// 空のリストからは0×0行列ができる。行数0で列数を指定したい場合は`zeros`を使う
+    pub fn new(data: Vec<BitVec<Word, Lsb0>>) -> Self {
+        let n_cols = data.first().map_or(0, |row| row.len());
+        Self::from_rows(data, n_cols)
+    }
+
+    /// 列数を指定して行のリストから行列を構成する。行数0の行列も作れる
+    pub(crate) fn from_rows(data: Vec<BitVec<Word, Lsb0>>, n_cols: usize) -> Self {
         for vec in &data {
             assert_eq!(
                 vec.len(),
@@ -18,18 +28,18 @@ impl BinaryDenseMatrix {
                 vec.len()
             );
         }
-        Self { data }
+        Self { data, n_cols }
     }
 
     pub fn zeros(rows: usize, cols: usize) -> Self {
-        let data = vec![bitvec![u64, Lsb0; 0; cols]; rows];
-        Self::new(data)
+        let data = vec![bitvec![Word, Lsb0; 0; cols]; rows];
+        Self::from_rows(data, cols)
     }
 
     pub fn identity(size: usize) -> Self {
-        let mut data: Vec<BitVec<u64, Lsb0>> = Vec::with_capacity(size);
+        let mut data: Vec<BitVec<Word, Lsb0>> = Vec::with_capacity(size);
         for i in 0..size {
-            let mut row: BitVec<u64, Lsb0> = bitvec![u64, Lsb0; 0; size];
+            let mut row: BitVec<Word, Lsb0> = bitvec![Word, Lsb0; 0; size];
             row.set(i, true);
             data.push(row);
         }
@@ -41,43 +51,219 @@ impl BinaryDenseMatrix {
     }
 
     pub fn cols(&self) -> usize {
-        if self.data.is_empty() {
-            0
-        } else {
-            self.data[0].len()
-        }
+        self.n_cols
     }
 
     pub fn shape(&self) -> (usize, usize) {
         (self.rows(), self.cols())
     }
 
-    pub fn get_data(&self) -> &[BitVec<u64, Lsb0>] {
+    pub fn get_data(&self) -> &[BitVec<Word, Lsb0>] {
         &self.data
     }
 
+    /// (r, c) 成分を返す
+    pub fn get(&self, r: usize, c: usize) -> bool {
+        self.check_index(r, c);
+        self.data[r][c]
+    }
+
+    /// (r, c) 成分を`value`に設定する
+    pub fn set(&mut self, r: usize, c: usize, value: bool) {
+        self.check_index(r, c);
+        self.data[r].set(c, value);
+    }
+
+    /// r行目を返す
+    pub fn row(&self, r: usize) -> &BitVec<Word, Lsb0> {
+        assert!(
+            r < self.rows(),
+            "行のインデックス({})が行数({})を超えています",
+            r,
+            self.rows()
+        );
+        &self.data[r]
+    }
+
+    fn check_index(&self, r: usize, c: usize) {
+        assert!(
+            r < self.rows() && c < self.cols(),
+            "インデックス({}, {})が行列のサイズ{:?}を超えています",
+            r,
+            c,
+            self.shape()
+        );
+    }
+
     pub fn rank(&self) -> usize {
         rank(&self.data)
     }
 
     pub fn transpose(&self) -> Self {
-        let mut transposed_data: Vec<BitVec<u64, Lsb0>> = Vec::with_capacity(self.cols());
+        let mut transposed_data: Vec<BitVec<Word, Lsb0>> = Vec::with_capacity(self.cols());
         for col_idx in 0..self.cols() {
-            let mut col_vec: BitVec<u64, Lsb0> = bitvec![u64, Lsb0; 0; self.rows()];
+            let mut col_vec: BitVec<Word, Lsb0> = bitvec![Word, Lsb0; 0; self.rows()];
             for row_idx in 0..self.rows() {
                 col_vec.set(row_idx, self.data[row_idx][col_idx]);
             }
             transposed_data.push(col_vec);
         }
-        Self::new(transposed_data)
+        Self::from_rows(transposed_data, self.rows())
+    }
+
+    /// GF(2)上の逆行列をGauss-Jordan法で計算する
+    /// 正方行列でない場合はパニックし、正則でない場合は`None`を返す
+    ///
+    /// # Examples
+    /// ```rust
+    /// use bitvec::prelude::*;
+    /// use qldpc_sim::math::bit_linear_algebra::BinaryDenseMatrix;
+    /// use qldpc_sim::util::u8_to_bitvec;
+    ///
+    /// let matrix = BinaryDenseMatrix::new(vec![
+    ///     u8_to_bitvec(&[1, 1]),
+    ///     u8_to_bitvec(&[0, 1]),
+    /// ]);
+    /// let inverse = matrix.inverse().unwrap();
+    /// assert_eq!(&matrix * &inverse, BinaryDenseMatrix::identity(2));
+    /// ```
+    pub fn inverse(&self) -> Option<BinaryDenseMatrix> {
+        assert_eq!(
+            self.rows(),
+            self.cols(),
+            "正方行列ではありません: shape = {:?}",
+            self.shape()
+        );
+        let n = self.rows();
+        let mut mat = self.data.clone();
+        let mut inv = BinaryDenseMatrix::identity(n).data;
+
+        for col in 0..n {
+            let pivot = (col..n).find(|&row| mat[row][col])?;
+            mat.swap(col, pivot);
+            inv.swap(col, pivot);
+
+            for row in 0..n {
+                if row != col && mat[row][col] {
+                    let pivot_row = mat[col].clone();
+                    mat[row] ^= pivot_row;
+                    let pivot_inv_row = inv[col].clone();
+                    inv[row] ^= pivot_inv_row;
+                }
+            }
+        }
+
+        Some(BinaryDenseMatrix::new(inv))
+    }
+
+    /// 行既約階段形を計算し、簡約後の行とピボット列を返す
+    fn row_echelon_form(&self) -> (Vec<BitVec<Word, Lsb0>>, Vec<usize>) {
+        let mut mat = self.data.clone();
+        let mut pivot_cols = Vec::new();
+
+        for col in 0..self.cols() {
+            let rank = pivot_cols.len();
+            if let Some(pivot) = (rank..mat.len()).find(|&row| mat[row][col]) {
+                mat.swap(rank, pivot);
+                for row in 0..mat.len() {
+                    if row != rank && mat[row][col] {
+                        let pivot_row = mat[rank].clone();
+                        mat[row] ^= pivot_row;
+                    }
+                }
+                pivot_cols.push(col);
+            }
+        }
+
+        (mat, pivot_cols)
+    }
+
+    /// 連立方程式 self * x = b をGF(2)上で解く
+    /// 解が存在しない場合は`None`を返し、複数存在する場合は自由変数を0とした解を返す
+    ///
+    /// # Examples
+    /// ```rust
+    /// use bitvec::prelude::*;
+    /// use qldpc_sim::math::bit_linear_algebra::BinaryDenseMatrix;
+    /// use qldpc_sim::util::u8_to_bitvec;
+    ///
+    /// let matrix = BinaryDenseMatrix::new(vec![
+    ///     u8_to_bitvec(&[1, 1, 0]),
+    ///     u8_to_bitvec(&[0, 1, 1]),
+    /// ]);
+    /// let b = u8_to_bitvec(&[1, 0]);
+    /// let x = matrix.solve(&b).unwrap();
+    /// assert_eq!(&matrix * &x, b);
+    /// ```
+    pub fn solve(&self, b: &BitVec<Word, Lsb0>) -> Option<BitVec<Word, Lsb0>> {
+        assert_eq!(
+            self.rows(),
+            b.len(),
+            "行列の行数({})とベクトルの長さ({})が一致していません",
+            self.rows(),
+            b.len()
+        );
+        let n_cols = self.cols();
+
+        // 拡大係数行列 [A | b]
+        let augmented_data = self
+            .data
+            .iter()
+            .enumerate()
+            .map(|(i, row)| {
+                let mut augmented_row = row.clone();
+                augmented_row.push(b[i]);
+                augmented_row
+            })
+            .collect();
+        let (mat, pivot_cols) = BinaryDenseMatrix::new(augmented_data).row_echelon_form();
+
+        // 最後の列がピボットになる場合は解なし
+        if pivot_cols.last() == Some(&n_cols) {
+            return None;
+        }
+
+        let mut x = bitvec![Word, Lsb0; 0; n_cols];
+        for (row, &col) in pivot_cols.iter().enumerate() {
+            x.set(col, mat[row][n_cols]);
+        }
+        Some(x)
+    }
+
+    /// self * x = 0 を満たすベクトル全体(零空間)の基底を行に持つ行列を返す
+    /// 零空間が自明な場合は行数0の行列を返す
+    pub fn nullspace(&self) -> BinaryDenseMatrix {
+        let n_cols = self.cols();
+        let (mat, pivot_cols) = self.row_echelon_form();
+
+        let mut is_pivot = bitvec![Word, Lsb0; 0; n_cols];
+        for &col in &pivot_cols {
+            is_pivot.set(col, true);
+        }
+
+        let basis = (0..n_cols)
+            .filter(|&free_col| !is_pivot[free_col])
+            .map(|free_col| {
+                let mut vec = bitvec![Word, Lsb0; 0; n_cols];
+                vec.set(free_col, true);
+                for (row, &pivot_col) in pivot_cols.iter().enumerate() {
+                    if mat[row][free_col] {
+                        vec.set(pivot_col, true);
+                    }
+                }
+                vec
+            })
+            .collect();
+
+        BinaryDenseMatrix::from_rows(basis, n_cols)
     }
 }
 
 /// バイナリ密行列とバイナリベクトルの積を計算する
-impl Mul<&BitVec<u64, Lsb0>> for &BinaryDenseMatrix {
-    type Output = BitVec<u64, Lsb0>;
+impl Mul<&BitVec<Word, Lsb0>> for &BinaryDenseMatrix {
+    type Output = BitVec<Word, Lsb0>;
 
-    fn mul(self, rhs: &BitVec<u64, Lsb0>) -> Self::Output {
+    fn mul(self, rhs: &BitVec<Word, Lsb0>) -> Self::Output {
         assert_eq!(
             self.cols(),
             rhs.len(),
@@ -86,7 +272,7 @@ impl Mul<&BitVec<u64, Lsb0>> for &BinaryDenseMatrix {
             rhs.len()
         );
 
-        let mut result: BitVec<u64, Lsb0> = bitvec![u64, Lsb0; 0; self.rows()];
+        let mut result: BitVec<Word, Lsb0> = bitvec![Word, Lsb0; 0; self.rows()];
 
         for (i, row) in self.data.iter().enumerate() {
             result.set(i, inner_product(row, rhs));
@@ -96,26 +282,26 @@ impl Mul<&BitVec<u64, Lsb0>> for &BinaryDenseMatrix {
     }
 }
 
-impl Mul<BitVec<u64, Lsb0>> for BinaryDenseMatrix {
-    type Output = BitVec<u64, Lsb0>;
+impl Mul<BitVec<Word, Lsb0>> for BinaryDenseMatrix {
+    type Output = BitVec<Word, Lsb0>;
 
-    fn mul(self, rhs: BitVec<u64, Lsb0>) -> Self::Output {
+    fn mul(self, rhs: BitVec<Word, Lsb0>) -> Self::Output {
         &self * &rhs
     }
 }
 
-impl Mul<&BitVec<u64, Lsb0>> for BinaryDenseMatrix {
-    type Output = BitVec<u64, Lsb0>;
+impl Mul<&BitVec<Word, Lsb0>> for BinaryDenseMatrix {
+    type Output = BitVec<Word, Lsb0>;
 
-    fn mul(self, rhs: &BitVec<u64, Lsb0>) -> Self::Output {
+    fn mul(self, rhs: &BitVec<Word, Lsb0>) -> Self::Output {
         &self * rhs
     }
 }
 
-impl Mul<BitVec<u64, Lsb0>> for &BinaryDenseMatrix {
-    type Output = BitVec<u64, Lsb0>;
+impl Mul<BitVec<Word, Lsb0>> for &BinaryDenseMatrix {
+    type Output = BitVec<Word, Lsb0>;
 
-    fn mul(self, rhs: BitVec<u64, Lsb0>) -> Self::Output {
+    fn mul(self, rhs: BitVec<Word, Lsb0>) -> Self::Output {
         self * &rhs
     }
 }
@@ -133,13 +319,13 @@ impl Mul<&BinaryDenseMatrix> for &BinaryDenseMatrix {
             rhs.rows()
         );
 
-        let mut result_data: Vec<BitVec<u64, Lsb0>> = Vec::with_capacity(self.rows());
+        let mut result_data: Vec<BitVec<Word, Lsb0>> = Vec::with_capacity(self.rows());
 
         for row in &self.data {
-            let mut result_row: BitVec<u64, Lsb0> = bitvec![u64, Lsb0; 0; rhs.cols()];
+            let mut result_row: BitVec<Word, Lsb0> = bitvec![Word, Lsb0; 0; rhs.cols()];
 
             for col_idx in 0..rhs.cols() {
-                let mut col_vec: BitVec<u64, Lsb0> = bitvec![u64, Lsb0; 0; rhs.rows()];
+                let mut col_vec: BitVec<Word, Lsb0> = bitvec![Word, Lsb0; 0; rhs.rows()];
                 for row_idx in 0..rhs.rows() {
                     col_vec.set(row_idx, rhs.data[row_idx][col_idx]);
                 }
@@ -149,7 +335,7 @@ impl Mul<&BinaryDenseMatrix> for &BinaryDenseMatrix {
             result_data.push(result_row);
         }
 
-        BinaryDenseMatrix::new(result_data)
+        BinaryDenseMatrix::from_rows(result_data, rhs.cols())
     }
 }
 
@@ -183,13 +369,14 @@ impl Mul<BinaryDenseMatrix> for &BinaryDenseMatrix {
 /// ```rust
 /// use bitvec::prelude::*;
 /// use qldpc_sim::math::bit_linear_algebra::inner_product;
+/// use qldpc_sim::util::u8_to_bitvec;
 ///
-/// let a = bitvec![u64, Lsb0; 1, 0, 1, 1];
-/// let b = bitvec![u64, Lsb0; 1, 1, 0, 1];
+/// let a = u8_to_bitvec(&[1, 0, 1, 1]);
+/// let b = u8_to_bitvec(&[1, 1, 0, 1]);
 /// let result = inner_product(&a, &b);
 /// assert_eq!(result, false);
 /// ```
-pub fn inner_product(a: &BitVec<u64, Lsb0>, b: &BitVec<u64, Lsb0>) -> bool {
+pub fn inner_product(a: &BitVec<Word, Lsb0>, b: &BitVec<Word, Lsb0>) -> bool {
     assert_eq!(
         a.len(),
         b.len(),
@@ -209,6 +396,29 @@ pub fn inner_product(a: &BitVec<u64, Lsb0>, b: &BitVec<u64, Lsb0>) -> bool {
     parity
 }
 
+/// 0/1の`u8`スライスで表されたベクトル同士のGF(2)上の内積を計算する
+///
+/// # Examples
+/// ```rust
+/// use qldpc_sim::math::bit_linear_algebra::inner_product_u8;
+///
+/// assert_eq!(inner_product_u8(&[1, 0, 1, 1], &[1, 1, 0, 1]), 0);
+/// assert_eq!(inner_product_u8(&[1, 0, 1], &[1, 1, 0]), 1);
+/// ```
+pub fn inner_product_u8(a: &[u8], b: &[u8]) -> u8 {
+    assert_eq!(
+        a.len(),
+        b.len(),
+        "ベクトルの長さが一致しません: a.len() = {}, b.len() = {}",
+        a.len(),
+        b.len()
+    );
+
+    a.iter()
+        .zip(b)
+        .fold(0u8, |parity, (&x, &y)| parity ^ (x & y & 1))
+}
+
 /// ビット行列のランクを計算する
 /// ビット行列は、`Vec<BitVec>`で表され、各`BitVec`が行を表す
 ///
@@ -216,16 +426,17 @@ pub fn inner_product(a: &BitVec<u64, Lsb0>, b: &BitVec<u64, Lsb0>) -> bool {
 /// ```rust
 /// use bitvec::prelude::*;
 /// use qldpc_sim::math::bit_linear_algebra::rank;
+/// use qldpc_sim::util::u8_to_bitvec;
 ///
 /// let vectors = vec![
-///     bitvec![u64, Lsb0; 1, 0, 0, 1],
-///     bitvec![u64, Lsb0; 0, 1, 1, 0],
-///     bitvec![u64, Lsb0; 1, 1, 1, 1],
+///     u8_to_bitvec(&[1, 0, 0, 1]),
+///     u8_to_bitvec(&[0, 1, 1, 0]),
+///     u8_to_bitvec(&[1, 1, 1, 1]),
 /// ];
 /// let rank = rank(&vectors);
 /// assert_eq!(rank, 2);
 /// ```
-pub fn rank(bit_matrix: &[BitVec<u64, Lsb0>]) -> usize {
+pub fn rank(bit_matrix: &[BitVec<Word, Lsb0>]) -> usize {
     let n = bit_matrix.len();
     if n == 0 {
         return 0;
@@ -235,7 +446,7 @@ pub fn rank(bit_matrix: &[BitVec<u64, Lsb0>]) -> usize {
         assert_eq!(vec.len(), m, "ベクトルの長さが一致しません");
     }
 
-    let mut mat: Vec<BitVec<u64, Lsb0>> = bit_matrix.to_vec();
+    let mut mat: Vec<BitVec<Word, Lsb0>> = bit_matrix.to_vec();
 
     let mut rank = 0;
 
@@ -271,46 +482,48 @@ pub fn rank(bit_matrix: &[BitVec<u64, Lsb0>]) -> usize {
 /// ```rust
 /// use bitvec::prelude::*;
 /// use qldpc_sim::math::bit_linear_algebra::is_linearly_independent;
+/// use qldpc_sim::util::u8_to_bitvec;
 ///
 /// let vectors = vec![
-///     bitvec![u64, Lsb0; 1, 0, 0, 1],
-///     bitvec![u64, Lsb0; 0, 1, 1, 0],
-///     bitvec![u64, Lsb0; 1, 1, 0, 1],
+///     u8_to_bitvec(&[1, 0, 0, 1]),
+///     u8_to_bitvec(&[0, 1, 1, 0]),
+///     u8_to_bitvec(&[1, 1, 0, 1]),
 /// ];
 /// let is_independent = is_linearly_independent(&vectors);
 /// assert_eq!(is_independent, true);
 /// ```
-pub fn is_linearly_independent(vectors: &[BitVec<u64, Lsb0>]) -> bool {
+pub fn is_linearly_independent(vectors: &[BitVec<Word, Lsb0>]) -> bool {
     rank(vectors) == vectors.len()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::util::u8_to_bitvec;
 
     #[test]
     fn test_inner_product() {
-        let a = bitvec![u64, Lsb0; 1, 0, 1, 1];
-        let b = bitvec![u64, Lsb0; 1, 1, 0, 1];
+        let a = u8_to_bitvec(&[1, 0, 1, 1]);
+        let b = u8_to_bitvec(&[1, 1, 0, 1]);
         assert!(!inner_product(&a, &b));
 
-        let c = bitvec![u64, Lsb0; 1, 1, 0, 0];
+        let c = u8_to_bitvec(&[1, 1, 0, 0]);
         assert!(inner_product(&a, &c));
     }
 
     #[test]
     fn test_rank() {
         let vectors = vec![
-            bitvec![u64, Lsb0; 1, 0, 0, 1],
-            bitvec![u64, Lsb0; 0, 1, 1, 0],
-            bitvec![u64, Lsb0; 1, 1, 1, 1],
+            u8_to_bitvec(&[1, 0, 0, 1]),
+            u8_to_bitvec(&[0, 1, 1, 0]),
+            u8_to_bitvec(&[1, 1, 1, 1]),
         ];
         assert_eq!(rank(&vectors), 2);
         let dependent_vectors = vec![
-            bitvec![u64, Lsb0; 1, 0, 0, 1],
-            bitvec![u64, Lsb0; 0, 1, 1, 0],
-            bitvec![u64, Lsb0; 1, 1, 1, 1],
-            bitvec![u64, Lsb0; 1, 1, 1, 1],
+            u8_to_bitvec(&[1, 0, 0, 1]),
+            u8_to_bitvec(&[0, 1, 1, 0]),
+            u8_to_bitvec(&[1, 1, 1, 1]),
+            u8_to_bitvec(&[1, 1, 1, 1]),
         ];
         assert_eq!(rank(&dependent_vectors), 2);
     }
@@ -318,16 +531,16 @@ mod tests {
     #[test]
     fn test_is_linearly_independent() {
         let independent_vectors = vec![
-            bitvec![u64, Lsb0; 1, 0, 0, 1],
-            bitvec![u64, Lsb0; 0, 1, 1, 0],
-            bitvec![u64, Lsb0; 1, 1, 0, 1],
+            u8_to_bitvec(&[1, 0, 0, 1]),
+            u8_to_bitvec(&[0, 1, 1, 0]),
+            u8_to_bitvec(&[1, 1, 0, 1]),
         ];
         assert!(is_linearly_independent(&independent_vectors));
         let dependent_vectors = vec![
-            bitvec![u64, Lsb0; 1, 0, 0, 1],
-            bitvec![u64, Lsb0; 0, 1, 1, 0],
-            bitvec![u64, Lsb0; 1, 1, 1, 1],
-            bitvec![u64, Lsb0; 1, 1, 1, 1],
+            u8_to_bitvec(&[1, 0, 0, 1]),
+            u8_to_bitvec(&[0, 1, 1, 0]),
+            u8_to_bitvec(&[1, 1, 1, 1]),
+            u8_to_bitvec(&[1, 1, 1, 1]),
         ];
         assert!(!is_linearly_independent(&dependent_vectors));
     }
@@ -335,31 +548,31 @@ mod tests {
     #[test]
     fn test_binary_dense_matrix_mul_bitvec() {
         let data = vec![
-            bitvec![u64, Lsb0; 1, 0, 1],
-            bitvec![u64, Lsb0; 0, 1, 1],
-            bitvec![u64, Lsb0; 1, 1, 0],
+            u8_to_bitvec(&[1, 0, 1]),
+            u8_to_bitvec(&[0, 1, 1]),
+            u8_to_bitvec(&[1, 1, 0]),
         ];
         let matrix = BinaryDenseMatrix::new(data);
-        let vec = bitvec![u64, Lsb0; 1, 0, 1];
+        let vec = u8_to_bitvec(&[1, 0, 1]);
         let result = &matrix * &vec;
-        assert_eq!(result, bitvec![u64, Lsb0; 0, 1, 1]);
+        assert_eq!(result, u8_to_bitvec(&[0, 1, 1]));
     }
 
     #[test]
     fn test_binary_dense_matrix_mul_matrix() {
-        let data_a = vec![bitvec![u64, Lsb0; 1, 0, 1], bitvec![u64, Lsb0; 0, 1, 1]];
+        let data_a = vec![u8_to_bitvec(&[1, 0, 1]), u8_to_bitvec(&[0, 1, 1])];
         let matrix_a = BinaryDenseMatrix::new(data_a);
 
         let data_b = vec![
-            bitvec![u64, Lsb0; 1, 1],
-            bitvec![u64, Lsb0; 0, 1],
-            bitvec![u64, Lsb0; 1, 0],
+            u8_to_bitvec(&[1, 1]),
+            u8_to_bitvec(&[0, 1]),
+            u8_to_bitvec(&[1, 0]),
         ];
         let matrix_b = BinaryDenseMatrix::new(data_b);
 
         let result_matrix = &matrix_a * &matrix_b;
 
-        let expected_data = vec![bitvec![u64, Lsb0; 0, 1], bitvec![u64, Lsb0; 1, 1]];
+        let expected_data = vec![u8_to_bitvec(&[0, 1]), u8_to_bitvec(&[1, 1])];
         let expected_matrix = BinaryDenseMatrix::new(expected_data);
 
         assert_eq!(result_matrix, expected_matrix);
@@ -367,7 +580,7 @@ mod tests {
 
     #[test]
     fn test_binary_dense_matrix_mul_zero_matrix() {
-        let data = vec![bitvec![u64, Lsb0; 1, 0, 1], bitvec![u64, Lsb0; 0, 1, 1]];
+        let data = vec![u8_to_bitvec(&[1, 0, 1]), u8_to_bitvec(&[0, 1, 1])];
         let matrix = BinaryDenseMatrix::new(data);
         let zero_matrix = BinaryDenseMatrix::zeros(3, 4);
         let result = &matrix * &zero_matrix;
@@ -377,11 +590,142 @@ mod tests {
 
     #[test]
     fn test_binary_dense_matrix_mul_identity_matrix() {
-        let data = vec![bitvec![u64, Lsb0; 1, 0, 1], bitvec![u64, Lsb0; 0, 1, 1]];
+        let data = vec![u8_to_bitvec(&[1, 0, 1]), u8_to_bitvec(&[0, 1, 1])];
         let matrix = BinaryDenseMatrix::new(data);
         let identity_matrix = BinaryDenseMatrix::identity(3);
         let result = &matrix * &identity_matrix;
         let expected = matrix.clone();
         assert_eq!(result, expected);
     }
+
+    #[test]
+    fn test_binary_dense_matrix_inverse() {
+        let data = vec![
+            u8_to_bitvec(&[1, 1, 0]),
+            u8_to_bitvec(&[0, 1, 1]),
+            u8_to_bitvec(&[1, 1, 1]),
+        ];
+        let matrix = BinaryDenseMatrix::new(data);
+        let inverse = matrix.inverse().unwrap();
+        assert_eq!(&matrix * &inverse, BinaryDenseMatrix::identity(3));
+        assert_eq!(&inverse * &matrix, BinaryDenseMatrix::identity(3));
+    }
+
+    #[test]
+    fn test_binary_dense_matrix_inverse_singular() {
+        let data = vec![
+            u8_to_bitvec(&[1, 1, 0]),
+            u8_to_bitvec(&[0, 1, 1]),
+            u8_to_bitvec(&[1, 0, 1]),
+        ];
+        let matrix = BinaryDenseMatrix::new(data);
+        assert!(matrix.inverse().is_none());
+    }
+
+    #[test]
+    fn test_binary_dense_matrix_solve() {
+        let data = vec![
+            u8_to_bitvec(&[1, 1, 0, 0]),
+            u8_to_bitvec(&[0, 1, 1, 0]),
+            u8_to_bitvec(&[0, 0, 1, 1]),
+        ];
+        let matrix = BinaryDenseMatrix::new(data);
+        let b = u8_to_bitvec(&[1, 1, 0]);
+        let x = matrix.solve(&b).unwrap();
+        assert_eq!(&matrix * &x, b);
+
+        // 解なし
+        let inconsistent =
+            BinaryDenseMatrix::new(vec![u8_to_bitvec(&[1, 1]), u8_to_bitvec(&[1, 1])]);
+        assert!(inconsistent.solve(&u8_to_bitvec(&[1, 0])).is_none());
+    }
+
+    #[test]
+    fn test_binary_dense_matrix_nullspace() {
+        use crate::math::sparse_matrix::BinarySparseMatrix;
+
+        let row_adj = vec![vec![0, 1], vec![1, 2], vec![3, 4], vec![0, 2]];
+        let sparse = BinarySparseMatrix::from_row_adj(4, 6, row_adj.clone());
+        let dense = BinaryDenseMatrix::new(
+            row_adj
+                .iter()
+                .map(|cols| {
+                    let mut row = bitvec![Word, Lsb0; 0; 6];
+                    for &c in cols {
+                        row.set(c, true);
+                    }
+                    row
+                })
+                .collect(),
+        );
+
+        let nullspace = dense.nullspace();
+        assert_eq!(nullspace.rows(), sparse.cols() - sparse.rank());
+        assert_eq!(nullspace.rank(), nullspace.rows());
+        for vec in nullspace.get_data() {
+            assert!((&dense * vec).not_any());
+        }
+
+        // 零空間が自明な場合
+        assert_eq!(BinaryDenseMatrix::identity(3).nullspace().rows(), 0);
+    }
+
+    #[test]
+    fn test_binary_dense_matrix_empty() {
+        let empty = BinaryDenseMatrix::new(vec![]);
+        assert_eq!(empty.shape(), (0, 0));
+        assert_eq!(empty.rank(), 0);
+        assert_eq!(
+            &empty * &BitVec::<Word, Lsb0>::new(),
+            BitVec::<Word, Lsb0>::new()
+        );
+        assert_eq!(&empty * &empty, empty);
+        assert_eq!(empty.transpose(), empty);
+        assert_eq!(empty.nullspace().shape(), (0, 0));
+    }
+
+    #[test]
+    fn test_binary_dense_matrix_zero_columns() {
+        // 3×0行列
+        let tall = BinaryDenseMatrix::zeros(3, 0);
+        assert_eq!(tall.shape(), (3, 0));
+        assert_eq!(tall.rank(), 0);
+        assert_eq!(
+            &tall * &BitVec::<Word, Lsb0>::new(),
+            bitvec![Word, Lsb0; 0; 3]
+        );
+
+        // 0×3行列は3×0行列の転置で、列数を保持する
+        let wide = tall.transpose();
+        assert_eq!(wide.shape(), (0, 3));
+        assert_eq!(
+            &wide * &u8_to_bitvec(&[1, 0, 1]),
+            BitVec::<Word, Lsb0>::new()
+        );
+        assert_eq!((&tall * &wide).shape(), (3, 3));
+        assert_eq!((&wide * &tall).shape(), (0, 0));
+        assert_eq!(wide.nullspace(), BinaryDenseMatrix::identity(3));
+    }
+
+    #[test]
+    fn test_binary_dense_matrix_get_set_row() {
+        let mut matrix = BinaryDenseMatrix::zeros(2, 3);
+        assert!(!matrix.get(1, 2));
+
+        matrix.set(1, 2, true);
+        matrix.set(0, 0, true);
+        assert!(matrix.get(1, 2));
+        assert!(matrix.get(0, 0));
+        assert_eq!(matrix.row(1), &u8_to_bitvec(&[0, 0, 1]));
+
+        matrix.set(0, 0, false);
+        assert_eq!(matrix.row(0), &u8_to_bitvec(&[0, 0, 0]));
+    }
+
+    #[test]
+    #[should_panic(expected = "インデックス(0, 3)が行列のサイズ(2, 3)を超えています")]
+    fn test_binary_dense_matrix_set_out_of_bounds() {
+        let mut matrix = BinaryDenseMatrix::zeros(2, 3);
+        matrix.set(0, 3, true);
+    }
 }
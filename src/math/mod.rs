@@ -1,2 +1,3 @@
 pub mod bit_linear_algebra;
+pub mod quasi_cyclic;
 pub mod sparse_matrix;
@@ -0,0 +1,194 @@
+use crate::math::sparse_matrix::BinarySparseMatrix;
+
+/// 巡回群Z_l上の多項式を成分に持つ行列（準巡回行列）を表す構造体
+/// 各成分は多項式の非零項の指数のリストで表し、空のリストは0を表す
+/// `expand`で各成分をl×lの巡回行列に置き換えたバイナリ疎行列に展開できる
+///
+/// # Examples
+/// ```rust
+/// use qldpc_sim::math::quasi_cyclic::QuasiCyclicMatrix;
+///
+/// // [[1 + x, 0], [x^2, 1]] over Z_3
+/// let matrix = QuasiCyclicMatrix::new(3, vec![vec![vec![0, 1], vec![]], vec![vec![2], vec![0]]]);
+/// assert_eq!(matrix.shape(), (2, 2));
+/// assert_eq!(matrix.expand().shape(), (6, 6));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuasiCyclicMatrix {
+    l: usize,
+    n_rows: usize,
+    n_cols: usize,
+    /// 各成分の多項式の指数のリスト
+    exponents: Vec<Vec<Vec<usize>>>,
+}
+
+impl QuasiCyclicMatrix {
+    pub fn new(l: usize, exponents: Vec<Vec<Vec<usize>>>) -> Self {
+        assert!(l > 0, "巡回群の位数lは1以上である必要があります");
+        let n_rows = exponents.len();
+        let n_cols = exponents.first().map_or(0, |row| row.len());
+        for row in &exponents {
+            assert_eq!(row.len(), n_cols, "各行の長さが一致しません");
+        }
+
+        let exponents = exponents
+            .into_iter()
+            .map(|row| {
+                row.into_iter()
+                    .map(|entry| entry.into_iter().map(|e| e % l).collect())
+                    .collect()
+            })
+            .collect();
+
+        Self {
+            l,
+            n_rows,
+            n_cols,
+            exponents,
+        }
+    }
+
+    /// 全成分が0の準巡回行列を返す
+    pub fn zeros(l: usize, n_rows: usize, n_cols: usize) -> Self {
+        Self::new(l, vec![vec![vec![]; n_cols]; n_rows])
+    }
+
+    pub fn l(&self) -> usize {
+        self.l
+    }
+
+    pub fn rows(&self) -> usize {
+        self.n_rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.n_cols
+    }
+
+    pub fn shape(&self) -> (usize, usize) {
+        (self.n_rows, self.n_cols)
+    }
+
+    pub fn exponents(&self) -> &[Vec<Vec<usize>>] {
+        &self.exponents
+    }
+
+    /// 共役転置 A* を返す
+    /// 転置した上で各成分の多項式 a(x) を a(x^{-1}) に置き換える
+    /// 展開した行列は元の行列を展開したものの転置になる
+    pub fn conjugate_transpose(&self) -> Self {
+        let exponents = (0..self.n_cols)
+            .map(|col_idx| {
+                (0..self.n_rows)
+                    .map(|row_idx| {
+                        self.exponents[row_idx][col_idx]
+                            .iter()
+                            .map(|&e| (self.l - e) % self.l)
+                            .collect()
+                    })
+                    .collect()
+            })
+            .collect();
+        Self::new(self.l, exponents)
+    }
+
+    /// クロネッカー積 A ⊗ I_m を返す
+    pub fn kron_identity(&self, m: usize) -> Self {
+        let mut result = Self::zeros(self.l, self.n_rows * m, self.n_cols * m);
+        for (row_idx, row) in self.exponents.iter().enumerate() {
+            for (col_idx, entry) in row.iter().enumerate() {
+                for s in 0..m {
+                    result.exponents[row_idx * m + s][col_idx * m + s] = entry.clone();
+                }
+            }
+        }
+        result
+    }
+
+    /// クロネッカー積 I_m ⊗ A を返す
+    pub fn identity_kron(&self, m: usize) -> Self {
+        let mut result = Self::zeros(self.l, self.n_rows * m, self.n_cols * m);
+        for s in 0..m {
+            for (row_idx, row) in self.exponents.iter().enumerate() {
+                for (col_idx, entry) in row.iter().enumerate() {
+                    result.exponents[s * self.n_rows + row_idx][s * self.n_cols + col_idx] =
+                        entry.clone();
+                }
+            }
+        }
+        result
+    }
+
+    /// 行数の等しい準巡回行列を右側に連結した [self | other] を返す
+    pub fn augment_right(&self, other: &QuasiCyclicMatrix) -> Self {
+        assert_eq!(self.l, other.l, "巡回群の位数が一致しません");
+        assert_eq!(
+            self.n_rows, other.n_rows,
+            "行数が一致しません: {} != {}",
+            self.n_rows, other.n_rows
+        );
+        let exponents = self
+            .exponents
+            .iter()
+            .zip(&other.exponents)
+            .map(|(left, right)| left.iter().chain(right).cloned().collect())
+            .collect();
+        Self::new(self.l, exponents)
+    }
+
+    /// 各成分をl×lの巡回行列に置き換えてバイナリ疎行列に展開する
+    pub fn expand(&self) -> BinarySparseMatrix {
+        let l = self.l;
+        let mut row_adj = vec![vec![]; self.n_rows * l];
+        for (block_row, row) in self.exponents.iter().enumerate() {
+            for (block_col, entry) in row.iter().enumerate() {
+                for r in 0..l {
+                    for &e in entry {
+                        row_adj[block_row * l + r].push(block_col * l + (r + e) % l);
+                    }
+                }
+            }
+        }
+        BinarySparseMatrix::from_row_adj(self.n_rows * l, self.n_cols * l, row_adj)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expand_single_entry() {
+        let matrix = QuasiCyclicMatrix::new(5, vec![vec![vec![0, 1, 3]]]);
+        assert_eq!(
+            matrix.expand(),
+            BinarySparseMatrix::circulant(5, &[0, 1, 3])
+        );
+    }
+
+    #[test]
+    fn test_conjugate_transpose() {
+        let matrix = QuasiCyclicMatrix::new(
+            4,
+            vec![
+                vec![vec![0, 1], vec![], vec![3]],
+                vec![vec![2], vec![1], vec![0, 2]],
+            ],
+        );
+        let conjugate = matrix.conjugate_transpose();
+        assert_eq!(conjugate.shape(), (3, 2));
+        assert_eq!(conjugate.expand(), matrix.expand().transpose());
+    }
+
+    #[test]
+    fn test_kron_identity() {
+        let matrix = QuasiCyclicMatrix::new(3, vec![vec![vec![1], vec![0]]]);
+        let left = matrix.kron_identity(2);
+        assert_eq!(left.shape(), (2, 4));
+        assert_eq!(left.exponents()[1], vec![vec![], vec![1], vec![], vec![0]]);
+
+        let right = matrix.identity_kron(2);
+        assert_eq!(right.shape(), (2, 4));
+        assert_eq!(right.exponents()[1], vec![vec![], vec![], vec![1], vec![0]]);
+    }
+}
@@ -1,7 +1,10 @@
+use crate::Word;
 use bitvec::prelude::*;
-use std::ops::Mul;
+use std::collections::VecDeque;
+use std::ops::{Add, Index, Mul};
+use std::sync::OnceLock;
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone)]
 pub struct BinarySparseMatrix {
     n_rows: usize,
     n_cols: usize,
@@ -10,6 +13,18 @@ pub struct BinarySparseMatrix {
     row_adj: Vec<Vec<usize>>,
     /// 各列に含まれる行のインデックス
     col_adj: Vec<Vec<usize>>,
+    /// 初回の`rank`呼び出しで計算したランク
+    /// `append_row`や`remove_row`で行列を変更するとクリアされ、次の`rank`呼び出しで再計算する
+    rank_cache: OnceLock<usize>,
+}
+
+impl PartialEq for BinarySparseMatrix {
+    fn eq(&self, other: &Self) -> bool {
+        self.n_rows == other.n_rows
+            && self.n_cols == other.n_cols
+            && self.row_adj == other.row_adj
+            && self.col_adj == other.col_adj
+    }
 }
 
 /// バイナリ疎行列を表す構造体
@@ -47,6 +62,10 @@ impl BinarySparseMatrix {
             col_adj.len()
         );
 
+        // 隣接リストは昇順かつ重複なしであることを前提に各種演算を行うので、ここで正規化する
+        let row_adj = Self::normalize_adj(row_adj);
+        let col_adj = Self::normalize_adj(col_adj);
+
         for (row_idx, neighbor) in row_adj.iter().enumerate() {
             for &col_idx in neighbor {
                 if col_idx >= n_cols {
@@ -72,9 +91,24 @@ impl BinarySparseMatrix {
             n_cols,
             row_adj,
             col_adj,
+            rank_cache: OnceLock::new(),
         }
     }
 
+    /// 各隣接リストを昇順に並べ替え、GF(2)上で偶数回現れるインデックスを打ち消す
+    fn normalize_adj(adj: Vec<Vec<usize>>) -> Vec<Vec<usize>> {
+        adj.into_iter()
+            .map(|mut neighbors| {
+                neighbors.sort_unstable();
+                neighbors
+                    .chunk_by(|a, b| a == b)
+                    .filter(|chunk| chunk.len() % 2 == 1)
+                    .map(|chunk| chunk[0])
+                    .collect()
+            })
+            .collect()
+    }
+
     pub fn from_row_adj(n_rows: usize, n_cols: usize, row_adj: Vec<Vec<usize>>) -> Self {
         let mut col_adj = vec![vec![]; n_cols];
 
@@ -97,12 +131,71 @@ impl BinarySparseMatrix {
         Self::new(n_rows, n_cols, row_adj, col_adj)
     }
 
+    /// 非零成分の座標 (行, 列) のリスト（COO形式）から行列を構成する
+    /// GF(2)上の行列なので、同じ座標が偶数回現れた場合は打ち消し合って0になる
+    ///
+    /// # Examples
+    /// ```
+    /// use qldpc_sim::math::sparse_matrix::BinarySparseMatrix;
+    ///
+    /// let matrix = BinarySparseMatrix::from_triplets(2, 3, &[(1, 2), (0, 1), (1, 0), (0, 1)]);
+    /// assert_eq!(matrix.row_adj(), &[vec![], vec![0, 2]]);
+    /// ```
+    pub fn from_triplets(n_rows: usize, n_cols: usize, triplets: &[(usize, usize)]) -> Self {
+        let mut row_adj = vec![vec![]; n_rows];
+        for &(row_idx, col_idx) in triplets {
+            assert!(
+                row_idx < n_rows,
+                "行インデックス({})がn_rows({})を超えています",
+                row_idx,
+                n_rows
+            );
+            row_adj[row_idx].push(col_idx);
+        }
+        // 重複の打ち消しと並べ替えはコンストラクタで行われる
+        Self::from_row_adj(n_rows, n_cols, row_adj)
+    }
+
     pub fn zeros(n_rows: usize, n_cols: usize) -> Self {
         let row_adj = vec![vec![]; n_rows];
         let col_adj = vec![vec![]; n_cols];
         Self::new(n_rows, n_cols, row_adj, col_adj)
     }
 
+    pub fn identity(size: usize) -> Self {
+        let row_adj = (0..size).map(|i| vec![i]).collect();
+        Self::from_row_adj(size, size, row_adj)
+    }
+
+    /// 行iの (i + s) mod size 列目が1となる正方巡回行列を構成する
+    /// 同じシフトが複数回現れた場合はGF(2)上で打ち消し合う
+    ///
+    /// # Examples
+    /// ```
+    /// use qldpc_sim::math::sparse_matrix::BinarySparseMatrix;
+    ///
+    /// let matrix = BinarySparseMatrix::circulant(3, &[0, 1]);
+    /// assert_eq!(matrix.row_adj(), &[vec![0, 1], vec![1, 2], vec![0, 2]]);
+    /// ```
+    pub fn circulant(size: usize, shifts: &[usize]) -> Self {
+        let row_adj = (0..size)
+            .map(|row_idx| {
+                let mut neighbors: Vec<usize> = Vec::new();
+                for &shift in shifts {
+                    let col_idx = (row_idx + shift) % size;
+                    if let Some(pos) = neighbors.iter().position(|&c| c == col_idx) {
+                        neighbors.remove(pos);
+                    } else {
+                        neighbors.push(col_idx);
+                    }
+                }
+                neighbors.sort_unstable();
+                neighbors
+            })
+            .collect();
+        Self::from_row_adj(size, size, row_adj)
+    }
+
     pub fn rows(&self) -> usize {
         self.n_rows
     }
@@ -123,15 +216,108 @@ impl BinarySparseMatrix {
         &self.col_adj
     }
 
+    /// 末尾に新しい行を追加する
+    /// `cols`は新しい行の非零要素の列インデックスで、重複したインデックスはGF(2)上で打ち消される
+    /// 行列が変わるので、キャッシュされたランクは破棄する
+    pub fn append_row(&mut self, cols: &[usize]) {
+        let neighbors = Self::normalize_adj(vec![cols.to_vec()]).remove(0);
+        if let Some(&col_idx) = neighbors.iter().find(|&&col_idx| col_idx >= self.n_cols) {
+            panic!(
+                "列インデックス({})がn_cols({})を超えています",
+                col_idx, self.n_cols
+            );
+        }
+
+        let row_idx = self.n_rows;
+        for &col_idx in &neighbors {
+            // 新しい行のインデックスは最大なので、末尾に追加しても昇順が保たれる
+            self.col_adj[col_idx].push(row_idx);
+        }
+        self.row_adj.push(neighbors);
+        self.n_rows += 1;
+        self.rank_cache = OnceLock::new();
+    }
+
+    /// idx行目を削除し、それより後ろの行を1つずつ詰める
+    /// 行列が変わるので、キャッシュされたランクは破棄する
+    pub fn remove_row(&mut self, idx: usize) {
+        assert!(
+            idx < self.n_rows,
+            "行インデックス({})がn_rows({})を超えています",
+            idx,
+            self.n_rows
+        );
+
+        self.row_adj.remove(idx);
+        for neighbors in self.col_adj.iter_mut() {
+            neighbors.retain(|&row_idx| row_idx != idx);
+            for row_idx in neighbors.iter_mut() {
+                if *row_idx > idx {
+                    *row_idx -= 1;
+                }
+            }
+        }
+        self.n_rows -= 1;
+        self.rank_cache = OnceLock::new();
+    }
+
+    /// 非零要素の個数を返す
+    pub fn nnz(&self) -> usize {
+        self.row_adj.iter().map(|neighbors| neighbors.len()).sum()
+    }
+
+    /// 全要素に占める非零要素の割合 nnz / (rows * cols) を返す
+    /// 要素のない行列では0を返す
+    pub fn density(&self) -> f64 {
+        let num_entries = self.n_rows * self.n_cols;
+        if num_entries == 0 {
+            return 0.0;
+        }
+        self.nnz() as f64 / num_entries as f64
+    }
+
+    /// 列`col_idx`で1となっている行のインデックスを昇順で返す
     pub fn nonzero_rows(&self, col_idx: usize) -> &[usize] {
         &self.col_adj[col_idx]
     }
 
+    /// 行`row_idx`で1となっている列のインデックスを昇順で返す
+    /// 検査行列では、そのチェックに含まれるビットの集合にあたる
     pub fn nonzero_cols(&self, row_idx: usize) -> &[usize] {
         &self.row_adj[row_idx]
     }
 
+    /// 行`row_idx`を長さ`cols()`のビットベクトルとして返す
+    ///
+    /// # Examples
+    /// ```
+    /// use bitvec::prelude::*;
+    /// use qldpc_sim::math::sparse_matrix::BinarySparseMatrix;
+    /// use qldpc_sim::util::u8_to_bitvec;
+    ///
+    /// let matrix = BinarySparseMatrix::from_row_adj(2, 3, vec![vec![0, 1], vec![1, 2]]);
+    /// assert_eq!(matrix.row(1), u8_to_bitvec(&[0, 1, 1]));
+    /// assert_eq!(matrix.col(1), u8_to_bitvec(&[1, 1]));
+    /// ```
+    pub fn row(&self, row_idx: usize) -> BitVec<Word, Lsb0> {
+        let mut row = bitvec![Word, Lsb0; 0; self.n_cols];
+        for &col_idx in &self.row_adj[row_idx] {
+            row.set(col_idx, true);
+        }
+        row
+    }
+
+    /// 列`col_idx`を長さ`rows()`のビットベクトルとして返す
+    pub fn col(&self, col_idx: usize) -> BitVec<Word, Lsb0> {
+        let mut col = bitvec![Word, Lsb0; 0; self.n_rows];
+        for &row_idx in &self.col_adj[col_idx] {
+            col.set(row_idx, true);
+        }
+        col
+    }
+
     /// 疎行列のままランクを計算する（ガウスの消去法）
+    /// 計算結果はキャッシュされ、2回目以降の呼び出しでは再計算しない
     ///
     /// # Examples
     /// ```
@@ -144,6 +330,45 @@ impl BinarySparseMatrix {
     /// assert_eq!(rank, 3);
     /// ```
     pub fn rank(&self) -> usize {
+        *self.rank_cache.get_or_init(|| self.compute_rank())
+    }
+
+    /// 指定した列だけからなる部分行列のランクを計算する
+    /// 部分行列を組み立てず、選んだ列ベクトルを順に既存の基底で簡約して独立なものを数える
+    /// OSDで候補の列集合ごとにランクを調べるのに使う。重複した列は従属として扱う
+    ///
+    /// # Examples
+    /// ```
+    /// use qldpc_sim::math::sparse_matrix::BinarySparseMatrix;
+    ///
+    /// let matrix = BinarySparseMatrix::from_row_adj(2, 3, vec![vec![0, 1], vec![1, 2]]);
+    /// assert_eq!(matrix.rank_of_columns(&[0, 1]), 2);
+    /// assert_eq!(matrix.rank_of_columns(&[1]), 1);
+    /// ```
+    pub fn rank_of_columns(&self, cols: &[usize]) -> usize {
+        // (ピボット行, 簡約済みの列ベクトル)
+        let mut basis: Vec<(usize, BitVec<Word, Lsb0>)> = Vec::new();
+        for &col_idx in cols {
+            assert!(
+                col_idx < self.n_cols,
+                "列のインデックス({})が列数({})を超えています",
+                col_idx,
+                self.n_cols
+            );
+            let mut column = self.col(col_idx);
+            for (pivot, vector) in &basis {
+                if column[*pivot] {
+                    column ^= vector;
+                }
+            }
+            if let Some(pivot) = column.first_one() {
+                basis.push((pivot, column));
+            }
+        }
+        basis.len()
+    }
+
+    fn compute_rank(&self) -> usize {
         let mut matrix = self.row_adj.clone();
         let mut rank = 0;
 
@@ -213,8 +438,462 @@ impl BinarySparseMatrix {
         self.rank() == self.n_rows
     }
 
+    /// 転置行列を返す
+    /// 行隣接リストと列隣接リストを入れ替えるだけなので、隣接リストの再構築や検証は行わない
+    /// ランクは転置で変わらないので、計算済みであれば引き継ぐ
     pub fn transpose(&self) -> Self {
-        BinarySparseMatrix::from_col_adj(self.n_cols, self.n_rows, self.row_adj.clone())
+        Self {
+            n_rows: self.n_cols,
+            n_cols: self.n_rows,
+            row_adj: self.col_adj.clone(),
+            col_adj: self.row_adj.clone(),
+            rank_cache: self.rank_cache.clone(),
+        }
+    }
+
+    /// 行数の等しい行列を右側に連結した [self | other] を返す
+    ///
+    /// # Examples
+    /// ```
+    /// use qldpc_sim::math::sparse_matrix::BinarySparseMatrix;
+    ///
+    /// let a = BinarySparseMatrix::from_row_adj(2, 3, vec![vec![0, 1], vec![1, 2]]);
+    /// // 標準形 [I | A]
+    /// let standard_form = a.augment_left(&BinarySparseMatrix::identity(2));
+    /// assert_eq!(standard_form.row_adj(), &[vec![0, 2, 3], vec![1, 3, 4]]);
+    /// ```
+    pub fn augment_right(&self, other: &BinarySparseMatrix) -> Self {
+        assert_eq!(
+            self.n_rows, other.n_rows,
+            "連結する行列の行数が一致しません: self = {}, other = {}",
+            self.n_rows, other.n_rows
+        );
+        let offset = self.n_cols;
+        let row_adj = self
+            .row_adj
+            .iter()
+            .zip(&other.row_adj)
+            .map(|(left, right)| {
+                left.iter()
+                    .copied()
+                    .chain(right.iter().map(|&c| c + offset))
+                    .collect()
+            })
+            .collect();
+        Self::from_row_adj(self.n_rows, self.n_cols + other.n_cols, row_adj)
+    }
+
+    /// 行数の等しい行列を左側に連結した [other | self] を返す
+    pub fn augment_left(&self, other: &BinarySparseMatrix) -> Self {
+        other.augment_right(self)
+    }
+
+    /// 各行列を対角線上に並べ、それ以外を0とするブロック対角行列を返す
+    /// 同じ符号を複数個並べた検査行列を作るのに使う
+    ///
+    /// # Examples
+    /// ```
+    /// use qldpc_sim::math::sparse_matrix::BinarySparseMatrix;
+    ///
+    /// let a = BinarySparseMatrix::from_row_adj(1, 2, vec![vec![0, 1]]);
+    /// let b = BinarySparseMatrix::identity(2);
+    /// let diag = BinarySparseMatrix::block_diagonal(&[a, b]);
+    /// assert_eq!(diag.row_adj(), &[vec![0, 1], vec![2], vec![3]]);
+    /// ```
+    pub fn block_diagonal(blocks: &[BinarySparseMatrix]) -> Self {
+        let n_rows = blocks.iter().map(|block| block.n_rows).sum();
+        let n_cols = blocks.iter().map(|block| block.n_cols).sum();
+        let mut row_adj = Vec::with_capacity(n_rows);
+        let mut offset = 0;
+        for block in blocks {
+            row_adj.extend(
+                block
+                    .row_adj
+                    .iter()
+                    .map(|row| row.iter().map(|&c| c + offset).collect()),
+            );
+            offset += block.n_cols;
+        }
+        Self::from_row_adj(n_rows, n_cols, row_adj)
+    }
+
+    /// self * other^T = 0 かどうか（すべての行同士が直交しているか）を判定する
+    /// CSS符号のH_XとH_Zの整合性チェックに使う
+    ///
+    /// # Examples
+    /// ```
+    /// use qldpc_sim::math::sparse_matrix::BinarySparseMatrix;
+    ///
+    /// let hz = BinarySparseMatrix::from_row_adj(2, 3, vec![vec![0, 1], vec![1, 2]]);
+    /// let hx = BinarySparseMatrix::from_row_adj(1, 3, vec![vec![0, 1, 2]]);
+    /// assert!(hx.is_orthogonal_to(&hz));
+    /// ```
+    pub fn is_orthogonal_to(&self, other: &BinarySparseMatrix) -> bool {
+        assert_eq!(
+            self.n_cols, other.n_cols,
+            "列数が一致しません: self = {}, other = {}",
+            self.n_cols, other.n_cols
+        );
+        let product = self * &other.transpose();
+        product.row_adj.iter().all(|neighbors| neighbors.is_empty())
+    }
+
+    /// 0/1の`u8`スライスで表されたベクトルとの積を計算する
+    /// `Vec<u8>`で動作するBP復号器がBitVecへの変換なしにシンドロームを計算するために使う
+    ///
+    /// # Examples
+    /// ```
+    /// use qldpc_sim::math::sparse_matrix::BinarySparseMatrix;
+    ///
+    /// let matrix = BinarySparseMatrix::from_row_adj(2, 3, vec![vec![0, 1], vec![1, 2]]);
+    /// assert_eq!(matrix.mul_u8(&[1, 0, 0]), vec![1, 0]);
+    /// ```
+    pub fn mul_u8(&self, v: &[u8]) -> Vec<u8> {
+        assert_eq!(
+            self.n_cols,
+            v.len(),
+            "行列の列数({})とベクトルの長さ({})が一致していません",
+            self.n_cols,
+            v.len()
+        );
+
+        self.row_adj
+            .iter()
+            .map(|neighbors| {
+                neighbors
+                    .iter()
+                    .fold(0u8, |sum, &col_idx| sum ^ (v[col_idx] & 1))
+            })
+            .collect()
+    }
+
+    /// 2つの行列がGF(2)上で同じ行空間を持つかどうかを判定する
+    /// 行の順序や冗長な行の有無に依存しないので、別の方法で構成した検査行列の比較に使う
+    ///
+    /// # Examples
+    /// ```
+    /// use qldpc_sim::math::sparse_matrix::BinarySparseMatrix;
+    ///
+    /// let a = BinarySparseMatrix::from_row_adj(2, 3, vec![vec![0, 1], vec![1, 2]]);
+    /// let b = BinarySparseMatrix::from_row_adj(2, 3, vec![vec![0, 2], vec![0, 1]]);
+    /// assert!(a.row_space_eq(&b));
+    /// ```
+    pub fn row_space_eq(&self, other: &BinarySparseMatrix) -> bool {
+        if self.n_cols != other.n_cols {
+            return false;
+        }
+
+        let self_rank = self.rank();
+        if self_rank != other.rank() {
+            return false;
+        }
+
+        // 行を縦に並べてもランクが増えなければ、互いの行空間に含まれている
+        let stacked_row_adj: Vec<Vec<usize>> = self
+            .row_adj
+            .iter()
+            .chain(other.row_adj.iter())
+            .cloned()
+            .collect();
+        let stacked = Self::from_row_adj(self.n_rows + other.n_rows, self.n_cols, stacked_row_adj);
+        stacked.rank() == self_rank
+    }
+
+    /// タナーグラフの辺を(チェックノード, ビットノード)の組で列挙する
+    ///
+    /// # Examples
+    /// ```
+    /// use qldpc_sim::math::sparse_matrix::BinarySparseMatrix;
+    ///
+    /// let matrix = BinarySparseMatrix::from_row_adj(2, 3, vec![vec![0, 1], vec![1, 2]]);
+    /// assert_eq!(matrix.edges(), vec![(0, 0), (0, 1), (1, 1), (1, 2)]);
+    /// ```
+    pub fn edges(&self) -> Vec<(usize, usize)> {
+        self.iter_nonzeros().collect()
+    }
+
+    /// 非零成分の位置(行, 列)を行優先の順で列挙する
+    ///
+    /// # Examples
+    /// ```
+    /// use qldpc_sim::math::sparse_matrix::BinarySparseMatrix;
+    ///
+    /// let matrix = BinarySparseMatrix::from_row_adj(2, 3, vec![vec![1, 0], vec![2]]);
+    /// let nonzeros: Vec<(usize, usize)> = matrix.iter_nonzeros().collect();
+    /// assert_eq!(nonzeros, vec![(0, 0), (0, 1), (1, 2)]);
+    /// ```
+    pub fn iter_nonzeros(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.row_adj
+            .iter()
+            .enumerate()
+            .flat_map(|(row_idx, neighbors)| {
+                neighbors.iter().map(move |&col_idx| (row_idx, col_idx))
+            })
+    }
+
+    /// タナーグラフの内周(最短サイクル長)を計算する
+    /// 各ノードを始点とした幅優先探索で最短サイクルを探し、サイクルが存在しなければ`None`を返す
+    /// タナーグラフは二部グラフなので、内周は常に偶数になる
+    ///
+    /// # Examples
+    /// ```
+    /// use qldpc_sim::math::sparse_matrix::BinarySparseMatrix;
+    ///
+    /// // 2つのチェックが2つのビットを共有しているので長さ4のサイクルを持つ
+    /// let matrix = BinarySparseMatrix::from_row_adj(2, 2, vec![vec![0, 1], vec![0, 1]]);
+    /// assert_eq!(matrix.girth(), Some(4));
+    /// ```
+    pub fn girth(&self) -> Option<usize> {
+        // ノード番号: チェックノードは 0..n_rows、ビットノードは n_rows..n_rows + n_cols
+        let n_nodes = self.n_rows + self.n_cols;
+        let neighbors = |node: usize| -> Vec<usize> {
+            if node < self.n_rows {
+                self.row_adj[node]
+                    .iter()
+                    .map(|&c| self.n_rows + c)
+                    .collect()
+            } else {
+                self.col_adj[node - self.n_rows].clone()
+            }
+        };
+
+        let mut girth: Option<usize> = None;
+
+        for source in 0..n_nodes {
+            let mut dist = vec![usize::MAX; n_nodes];
+            let mut parent = vec![usize::MAX; n_nodes];
+            let mut queue = VecDeque::new();
+            dist[source] = 0;
+            queue.push_back(source);
+
+            while let Some(u) = queue.pop_front() {
+                // これ以上探索しても短いサイクルは見つからない
+                if girth.is_some_and(|g| 2 * dist[u] + 1 >= g) {
+                    break;
+                }
+                for v in neighbors(u) {
+                    if dist[v] == usize::MAX {
+                        dist[v] = dist[u] + 1;
+                        parent[v] = u;
+                        queue.push_back(v);
+                    } else if parent[u] != v {
+                        let cycle_len = dist[u] + dist[v] + 1;
+                        girth = Some(girth.map_or(cycle_len, |g| g.min(cycle_len)));
+                    }
+                }
+            }
+        }
+
+        girth
+    }
+
+    /// タナーグラフをGraphvizのDOT形式で出力する
+    /// チェックノードは`c{i}`(四角)、ビットノードは`b{j}`(丸)として出力する
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("graph TannerGraph {\n");
+        for row_idx in 0..self.n_rows {
+            dot.push_str(&format!("    c{} [shape=box];\n", row_idx));
+        }
+        for col_idx in 0..self.n_cols {
+            dot.push_str(&format!("    b{} [shape=circle];\n", col_idx));
+        }
+        for (row_idx, col_idx) in self.edges() {
+            dot.push_str(&format!("    c{} -- b{};\n", row_idx, col_idx));
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// MacKayのalist形式の文字列に変換する
+    /// 1行目に列数と行数、2行目に列と行の重みの最大値、3・4行目に各列・各行の重みを書き、
+    /// 続けて各列の非零要素の行番号、各行の非零要素の列番号を1始まりで並べる
+    /// 重みが最大値に満たない列・行は0で埋める
+    ///
+    /// # Examples
+    /// ```
+    /// use qldpc_sim::math::sparse_matrix::BinarySparseMatrix;
+    ///
+    /// let matrix = BinarySparseMatrix::from_row_adj(2, 3, vec![vec![0, 1], vec![1, 2]]);
+    /// let alist = matrix.to_alist();
+    /// assert!(alist.starts_with("3 2\n2 2\n1 2 1\n2 2\n1 0\n1 2\n2 0\n"));
+    /// assert_eq!(BinarySparseMatrix::from_alist(&alist), matrix);
+    /// ```
+    pub fn to_alist(&self) -> String {
+        let col_weights: Vec<usize> = self.col_adj.iter().map(|col| col.len()).collect();
+        let row_weights: Vec<usize> = self.row_adj.iter().map(|row| row.len()).collect();
+        let max_col_weight = col_weights.iter().copied().max().unwrap_or(0);
+        let max_row_weight = row_weights.iter().copied().max().unwrap_or(0);
+
+        let mut lines = vec![
+            format!("{} {}", self.n_cols, self.n_rows),
+            format!("{} {}", max_col_weight, max_row_weight),
+            join_numbers(col_weights.iter().copied()),
+            join_numbers(row_weights.iter().copied()),
+        ];
+        for (adj, max_weight) in [
+            (&self.col_adj, max_col_weight),
+            (&self.row_adj, max_row_weight),
+        ] {
+            for neighbors in adj {
+                let padding = std::iter::repeat_n(0, max_weight - neighbors.len());
+                lines.push(join_numbers(
+                    neighbors.iter().map(|&idx| idx + 1).chain(padding),
+                ));
+            }
+        }
+
+        let mut alist = lines.join("\n");
+        alist.push('\n');
+        alist
+    }
+
+    /// MacKayのalist形式の文字列から行列を構成する
+    /// 0埋めの有無はどちらでもよい。列ごとの記述と行ごとの記述が食い違う場合はパニックする
+    pub fn from_alist(alist: &str) -> Self {
+        Self::try_from_alist(alist).unwrap_or_else(|message| panic!("{}", message))
+    }
+
+    /// `from_alist`と同じだが、不正なalistに対してパニックせずエラーメッセージを返す
+    /// ファイルなど外部から読み込んだ文字列を解釈するときに使う
+    ///
+    /// # Examples
+    /// ```
+    /// use qldpc_sim::math::sparse_matrix::BinarySparseMatrix;
+    ///
+    /// assert!(BinarySparseMatrix::try_from_alist("3 2\n2 2\n").is_err());
+    /// ```
+    pub fn try_from_alist(alist: &str) -> Result<Self, String> {
+        let mut numbers = alist.split_whitespace().map(|token| {
+            token
+                .parse::<usize>()
+                .map_err(|_| format!("alistに数値でない要素があります: {}", token))
+        });
+        let mut next = || {
+            numbers
+                .next()
+                .unwrap_or_else(|| Err("alistが途中で終わっています".to_string()))
+        };
+
+        let n_cols = next()?;
+        let n_rows = next()?;
+        let _max_weights = (next()?, next()?);
+        let col_weights = (0..n_cols).map(|_| next()).collect::<Result<Vec<_>, _>>()?;
+        let row_weights = (0..n_rows).map(|_| next()).collect::<Result<Vec<_>, _>>()?;
+
+        // 0埋めの要素は有効なインデックスにならないので読み飛ばす
+        let mut indices = numbers
+            .filter(|idx| *idx != Ok(0))
+            .map(|idx| idx.map(|idx| idx - 1));
+        let mut read_adjacency = |weights: &[usize], bound: usize| {
+            weights
+                .iter()
+                .map(|&weight| {
+                    let neighbors = indices
+                        .by_ref()
+                        .take(weight)
+                        .collect::<Result<Vec<usize>, _>>()?;
+                    if neighbors.len() != weight {
+                        return Err("alistが途中で終わっています".to_string());
+                    }
+                    if neighbors.iter().any(|&idx| idx >= bound) {
+                        return Err(format!("alistのインデックスが範囲外です: {:?}", neighbors));
+                    }
+                    Ok(neighbors)
+                })
+                .collect::<Result<Vec<_>, String>>()
+        };
+        let col_adj = read_adjacency(&col_weights, n_rows)?;
+        let row_adj = read_adjacency(&row_weights, n_cols)?;
+
+        let matrix = Self::from_row_adj(n_rows, n_cols, row_adj);
+        if matrix != Self::from_col_adj(n_rows, n_cols, col_adj) {
+            return Err("alistの列ごとの記述と行ごとの記述が一致しません".to_string());
+        }
+        Ok(matrix)
+    }
+}
+
+/// 数値を空白区切りで並べた文字列を返す
+fn join_numbers(numbers: impl Iterator<Item = usize>) -> String {
+    numbers.map(|n| n.to_string()).collect::<Vec<_>>().join(" ")
+}
+
+/// 非零成分を1つずつ追加して疎行列を組み立てるビルダー
+/// 行数と列数を先に決めておき、`push`や`extend`で追加した座標から`build`で行列を構成する
+/// `from_triplets`と同様に、同じ座標を偶数回追加すると打ち消し合って0になる
+///
+/// # Examples
+/// ```
+/// use qldpc_sim::math::sparse_matrix::SparseBuilder;
+///
+/// let mut builder = SparseBuilder::new(2, 3);
+/// builder.push(0, 1);
+/// builder.extend([(1, 0), (1, 2)]);
+/// let matrix = builder.build();
+/// assert_eq!(matrix.row_adj(), &[vec![1], vec![0, 2]]);
+/// assert!(matrix[(1, 2)]);
+/// assert!(!matrix[(0, 0)]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SparseBuilder {
+    rows: usize,
+    cols: usize,
+    triplets: Vec<(usize, usize)>,
+}
+
+impl SparseBuilder {
+    pub fn new(rows: usize, cols: usize) -> Self {
+        Self {
+            rows,
+            cols,
+            triplets: Vec::new(),
+        }
+    }
+
+    /// (r, c) 成分を追加する
+    pub fn push(&mut self, r: usize, c: usize) {
+        assert!(
+            r < self.rows && c < self.cols,
+            "インデックス({}, {})が行列のサイズ({}, {})を超えています",
+            r,
+            c,
+            self.rows,
+            self.cols
+        );
+        self.triplets.push((r, c));
+    }
+
+    pub fn build(&self) -> BinarySparseMatrix {
+        BinarySparseMatrix::from_triplets(self.rows, self.cols, &self.triplets)
+    }
+}
+
+impl Extend<(usize, usize)> for SparseBuilder {
+    fn extend<I: IntoIterator<Item = (usize, usize)>>(&mut self, iter: I) {
+        for (r, c) in iter {
+            self.push(r, c);
+        }
+    }
+}
+
+/// (r, c) 成分が1かどうかを返す
+impl Index<(usize, usize)> for BinarySparseMatrix {
+    type Output = bool;
+
+    fn index(&self, (r, c): (usize, usize)) -> &bool {
+        assert!(
+            r < self.n_rows && c < self.n_cols,
+            "インデックス({}, {})が行列のサイズ({}, {})を超えています",
+            r,
+            c,
+            self.n_rows,
+            self.n_cols
+        );
+        if self.row_adj[r].binary_search(&c).is_ok() {
+            &true
+        } else {
+            &false
+        }
     }
 }
 
@@ -225,7 +904,7 @@ pub trait IntoSparseMatrix {
 impl IntoSparseMatrix for Vec<Vec<i32>> {
     fn into_sparse_matrix(self) -> BinarySparseMatrix {
         let n_rows = self.len();
-        let n_cols = self[0].len();
+        let n_cols = self.first().map_or(0, |row| row.len());
 
         let mut row_adj: Vec<Vec<usize>> = vec![vec![]; n_rows];
 
@@ -246,10 +925,10 @@ impl IntoSparseMatrix for Vec<Vec<i32>> {
 }
 
 /// バイナリ疎行列とバイナリベクトルの積を計算する
-impl Mul<&BitVec<u64, Lsb0>> for &BinarySparseMatrix {
-    type Output = BitVec<u64, Lsb0>;
+impl Mul<&BitVec<Word, Lsb0>> for &BinarySparseMatrix {
+    type Output = BitVec<Word, Lsb0>;
 
-    fn mul(self, rhs: &BitVec<u64, Lsb0>) -> Self::Output {
+    fn mul(self, rhs: &BitVec<Word, Lsb0>) -> Self::Output {
         assert_eq!(
             self.n_cols,
             rhs.len(),
@@ -258,7 +937,7 @@ impl Mul<&BitVec<u64, Lsb0>> for &BinarySparseMatrix {
             rhs.len()
         );
 
-        let mut result = bitvec![u64, Lsb0; 0; self.n_rows];
+        let mut result = bitvec![Word, Lsb0; 0; self.n_rows];
 
         for (row_idx, neighbors) in self.row_adj.iter().enumerate() {
             let mut parity = false;
@@ -276,26 +955,26 @@ impl Mul<&BitVec<u64, Lsb0>> for &BinarySparseMatrix {
     }
 }
 
-impl Mul<BitVec<u64, Lsb0>> for BinarySparseMatrix {
-    type Output = BitVec<u64, Lsb0>;
+impl Mul<BitVec<Word, Lsb0>> for BinarySparseMatrix {
+    type Output = BitVec<Word, Lsb0>;
 
-    fn mul(self, rhs: BitVec<u64, Lsb0>) -> Self::Output {
+    fn mul(self, rhs: BitVec<Word, Lsb0>) -> Self::Output {
         &self * &rhs
     }
 }
 
-impl Mul<&BitVec<u64, Lsb0>> for BinarySparseMatrix {
-    type Output = BitVec<u64, Lsb0>;
+impl Mul<&BitVec<Word, Lsb0>> for BinarySparseMatrix {
+    type Output = BitVec<Word, Lsb0>;
 
-    fn mul(self, rhs: &BitVec<u64, Lsb0>) -> Self::Output {
+    fn mul(self, rhs: &BitVec<Word, Lsb0>) -> Self::Output {
         &self * rhs
     }
 }
 
-impl Mul<BitVec<u64, Lsb0>> for &BinarySparseMatrix {
-    type Output = BitVec<u64, Lsb0>;
+impl Mul<BitVec<Word, Lsb0>> for &BinarySparseMatrix {
+    type Output = BitVec<Word, Lsb0>;
 
-    fn mul(self, rhs: BitVec<u64, Lsb0>) -> Self::Output {
+    fn mul(self, rhs: BitVec<Word, Lsb0>) -> Self::Output {
         self * &rhs
     }
 }
@@ -305,26 +984,7 @@ impl Mul<&Vec<u8>> for &BinarySparseMatrix {
     type Output = Vec<u8>;
 
     fn mul(self, rhs: &Vec<u8>) -> Self::Output {
-        assert_eq!(
-            self.n_cols,
-            rhs.len(),
-            "行列の列数({})とベクトルの長さ({})が一致していません",
-            self.n_cols,
-            rhs.len()
-        );
-
-        let mut result = vec![0u8; self.n_rows];
-
-        for (row_idx, neighbors) in self.row_adj.iter().enumerate() {
-            let mut sum = 0u8;
-
-            for &col_idx in neighbors {
-                sum ^= rhs[col_idx];
-            }
-
-            result[row_idx] = sum;
-        }
-        result
+        self.mul_u8(rhs)
     }
 }
 
@@ -413,9 +1073,83 @@ impl Mul<BinarySparseMatrix> for &BinarySparseMatrix {
     }
 }
 
+/// GF(2)上でバイナリ疎行列同士の和（要素ごとのXOR）を計算する
+impl Add<&BinarySparseMatrix> for &BinarySparseMatrix {
+    type Output = BinarySparseMatrix;
+
+    fn add(self, rhs: &BinarySparseMatrix) -> Self::Output {
+        assert_eq!(
+            self.shape(),
+            rhs.shape(),
+            "行列の形が一致しません: self = {:?}, rhs = {:?}",
+            self.shape(),
+            rhs.shape()
+        );
+
+        let result_row_adj: Vec<Vec<usize>> = self
+            .row_adj
+            .iter()
+            .zip(&rhs.row_adj)
+            .map(|(lhs_neighbors, rhs_neighbors)| {
+                // 各行の列インデックスはソート済みなので、マージしながら対称差を取る
+                let mut result_neighbors =
+                    Vec::with_capacity(lhs_neighbors.len() + rhs_neighbors.len());
+                let (mut i, mut j) = (0, 0);
+                while i < lhs_neighbors.len() && j < rhs_neighbors.len() {
+                    match lhs_neighbors[i].cmp(&rhs_neighbors[j]) {
+                        std::cmp::Ordering::Less => {
+                            result_neighbors.push(lhs_neighbors[i]);
+                            i += 1;
+                        }
+                        std::cmp::Ordering::Greater => {
+                            result_neighbors.push(rhs_neighbors[j]);
+                            j += 1;
+                        }
+                        std::cmp::Ordering::Equal => {
+                            i += 1;
+                            j += 1;
+                        }
+                    }
+                }
+                result_neighbors.extend_from_slice(&lhs_neighbors[i..]);
+                result_neighbors.extend_from_slice(&rhs_neighbors[j..]);
+                result_neighbors
+            })
+            .collect();
+
+        BinarySparseMatrix::from_row_adj(self.n_rows, self.n_cols, result_row_adj)
+    }
+}
+
+impl Add<BinarySparseMatrix> for BinarySparseMatrix {
+    type Output = BinarySparseMatrix;
+
+    fn add(self, rhs: BinarySparseMatrix) -> Self::Output {
+        &self + &rhs
+    }
+}
+
+impl Add<&BinarySparseMatrix> for BinarySparseMatrix {
+    type Output = BinarySparseMatrix;
+
+    fn add(self, rhs: &BinarySparseMatrix) -> Self::Output {
+        &self + rhs
+    }
+}
+
+impl Add<BinarySparseMatrix> for &BinarySparseMatrix {
+    type Output = BinarySparseMatrix;
+
+    fn add(self, rhs: BinarySparseMatrix) -> Self::Output {
+        self + &rhs
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::code::named_codes::shor_code;
+    use crate::util::{bitvec_to_u8, u8_to_bitvec};
 
     #[test]
     #[should_panic(expected = "row_adjとcol_adjが整合していません")]
@@ -456,6 +1190,40 @@ mod tests {
         assert_eq!(matrix_from_col_adj, matrix);
     }
 
+    #[test]
+    fn test_identity() {
+        let identity = BinarySparseMatrix::identity(3);
+        assert_eq!(identity.shape(), (3, 3));
+        assert_eq!(identity.row_adj(), &[vec![0], vec![1], vec![2]]);
+        assert_eq!(identity.col_adj(), &[vec![0], vec![1], vec![2]]);
+    }
+
+    #[test]
+    fn test_augment() {
+        let a = BinarySparseMatrix::from_row_adj(2, 3, vec![vec![0, 1], vec![2]]);
+        let identity = BinarySparseMatrix::identity(2);
+
+        let right = a.augment_right(&identity);
+        assert_eq!(right.shape(), (2, 5));
+        assert_eq!(right.row_adj(), &[vec![0, 1, 3], vec![2, 4]]);
+
+        let left = a.augment_left(&identity);
+        assert_eq!(left.shape(), (2, 5));
+        assert_eq!(left.row_adj(), &[vec![0, 2, 3], vec![1, 4]]);
+    }
+
+    #[test]
+    fn test_circulant() {
+        let matrix = BinarySparseMatrix::circulant(4, &[0, 1]);
+        let expected_row_adj = vec![vec![0, 1], vec![1, 2], vec![2, 3], vec![0, 3]];
+        let expected = BinarySparseMatrix::from_row_adj(4, 4, expected_row_adj);
+        assert_eq!(matrix, expected);
+
+        // 重複したシフトは打ち消し合う
+        let cancelled = BinarySparseMatrix::circulant(4, &[1, 1]);
+        assert_eq!(cancelled, BinarySparseMatrix::zeros(4, 4));
+    }
+
     #[test]
     fn test_nonzero_rows() {
         let row_adj = vec![vec![0, 1], vec![1, 2], vec![2, 3]];
@@ -482,16 +1250,89 @@ mod tests {
         assert_eq!(transposed, expected);
     }
 
+    #[test]
+    fn test_is_orthogonal_to() {
+        let hz = BinarySparseMatrix::from_row_adj(2, 4, vec![vec![0, 1], vec![2, 3]]);
+        let hx = BinarySparseMatrix::from_row_adj(1, 4, vec![vec![0, 1, 2, 3]]);
+        assert!(hx.is_orthogonal_to(&hz));
+        assert!(hz.is_orthogonal_to(&hx));
+
+        let non_orthogonal = BinarySparseMatrix::from_row_adj(1, 4, vec![vec![1, 2]]);
+        assert!(!hz.is_orthogonal_to(&non_orthogonal));
+    }
+
+    #[test]
+    fn test_edges() {
+        let row_adj = vec![vec![0, 1], vec![1, 2], vec![2, 3]];
+        let matrix = BinarySparseMatrix::from_row_adj(3, 4, row_adj);
+        let nnz: usize = matrix
+            .row_adj()
+            .iter()
+            .map(|neighbors| neighbors.len())
+            .sum();
+        assert_eq!(matrix.edges().len(), nnz);
+        assert_eq!(
+            matrix.edges(),
+            vec![(0, 0), (0, 1), (1, 1), (1, 2), (2, 2), (2, 3)]
+        );
+    }
+
+    #[test]
+    fn test_girth() {
+        // 巡回型の繰り返し符号
+        let cyclic_repetition = |n: usize| {
+            let row_adj = (0..n).map(|i| vec![i, (i + 1) % n]).collect();
+            BinarySparseMatrix::from_row_adj(n, n, row_adj)
+        };
+        assert_eq!(cyclic_repetition(2).girth(), Some(4));
+        assert_eq!(cyclic_repetition(3).girth(), Some(6));
+        assert_eq!(cyclic_repetition(5).girth(), Some(10));
+
+        // 木構造のタナーグラフ
+        let tree = BinarySparseMatrix::from_row_adj(3, 4, vec![vec![0, 1], vec![1, 2], vec![2, 3]]);
+        assert_eq!(tree.girth(), None);
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let matrix = BinarySparseMatrix::from_row_adj(1, 2, vec![vec![0, 1]]);
+        let dot = matrix.to_dot();
+        assert!(dot.starts_with("graph TannerGraph {"));
+        assert!(dot.contains("c0 [shape=box];"));
+        assert!(dot.contains("b1 [shape=circle];"));
+        assert!(dot.contains("c0 -- b0;"));
+        assert!(dot.contains("c0 -- b1;"));
+        assert_eq!(dot.matches("--").count(), 2);
+    }
+
     #[test]
     fn test_mul_binary_vec() {
         let row_adj = vec![vec![0, 1], vec![1, 2], vec![2, 3]];
         let matrix = BinarySparseMatrix::from_row_adj(3, 4, row_adj);
-        let vec = bitvec![u64, Lsb0; 1, 0, 1, 0];
+        let vec = u8_to_bitvec(&[1, 0, 1, 0]);
         let result = &matrix * &vec;
-        let expected = bitvec![u64, Lsb0; 1, 1, 1];
+        let expected = u8_to_bitvec(&[1, 1, 1]);
         assert_eq!(result, expected);
     }
 
+    #[test]
+    fn test_mul_u8_vec() {
+        let row_adj = vec![vec![0, 1], vec![1, 2], vec![2, 3]];
+        let matrix = BinarySparseMatrix::from_row_adj(3, 4, row_adj);
+        let vec: Vec<u8> = vec![1, 0, 1, 0];
+        let result = &matrix * &vec;
+        assert_eq!(result, vec![1, 1, 1]);
+        assert_eq!(matrix.mul_u8(&vec), result);
+
+        // Shor符号のhzでも、mul_u8とu8の積はBitVecとの積と一致する
+        let code = shor_code();
+        let hz = code.hz();
+        let error = vec![1, 0, 0, 0, 1, 0, 1, 1, 0];
+        let expected = bitvec_to_u8(&(hz * &u8_to_bitvec(&error)));
+        assert_eq!(hz.mul_u8(&error), expected);
+        assert_eq!(hz * &error, expected);
+    }
+
     #[test]
     fn test_mul_binary_sparse_matrix() {
         let row_adj_a = vec![vec![0, 1], vec![1, 2], vec![2, 3]];
@@ -530,4 +1371,295 @@ mod tests {
         let converted_matrix = vec.into_sparse_matrix();
         assert_eq!(converted_matrix, matrix);
     }
+
+    #[test]
+    fn test_iter_nonzeros() {
+        let hx = shor_code().hx().clone();
+        let nonzeros: Vec<(usize, usize)> = hx.iter_nonzeros().collect();
+        let expected: Vec<(usize, usize)> = (0..6)
+            .map(|col_idx| (0, col_idx))
+            .chain((3..9).map(|col_idx| (1, col_idx)))
+            .collect();
+        assert_eq!(nonzeros, expected);
+        assert_eq!(hx.iter_nonzeros().count(), hx.edges().len());
+    }
+
+    #[test]
+    fn test_row_space_eq() {
+        let hz = BinarySparseMatrix::from_row_adj(3, 4, vec![vec![0, 1], vec![1, 2], vec![2, 3]]);
+        let permuted =
+            BinarySparseMatrix::from_row_adj(3, 4, vec![vec![2, 3], vec![0, 1], vec![1, 2]]);
+        assert_ne!(hz, permuted);
+        assert!(hz.row_space_eq(&permuted));
+
+        // 行の和で置き換えても行空間は変わらない
+        let combined =
+            BinarySparseMatrix::from_row_adj(3, 4, vec![vec![0, 2], vec![1, 2], vec![2, 3]]);
+        assert!(hz.row_space_eq(&combined));
+
+        let different =
+            BinarySparseMatrix::from_row_adj(3, 4, vec![vec![0, 1], vec![1, 2], vec![3]]);
+        assert!(!hz.row_space_eq(&different));
+        assert!(!hz.row_space_eq(&BinarySparseMatrix::identity(3)));
+    }
+
+    #[test]
+    fn test_rank_cache() {
+        let matrix = BinarySparseMatrix::circulant(6, &[0, 1, 3]);
+        let cloned = matrix.clone();
+        assert!(matrix.rank_cache.get().is_none());
+
+        let rank = matrix.rank();
+        assert_eq!(matrix.rank_cache.get(), Some(&rank));
+        assert_eq!(matrix.rank(), matrix.compute_rank());
+        assert!(matrix.is_linearly_independent() == (rank == matrix.rows()));
+
+        // 計算前に複製した行列はキャッシュを共有せず、独立に計算する
+        assert!(cloned.rank_cache.get().is_none());
+        assert_eq!(cloned.rank(), rank);
+        assert_eq!(cloned, matrix);
+    }
+
+    #[test]
+    fn test_from_triplets() {
+        let matrix = BinarySparseMatrix::from_triplets(2, 3, &[(0, 1), (0, 2), (0, 1), (1, 0)]);
+        assert_eq!(matrix.row_adj(), &[vec![2], vec![0]]);
+        assert!(matrix.nonzero_rows(1).is_empty());
+
+        // 3回現れた座標は1つだけ残る
+        let matrix = BinarySparseMatrix::from_triplets(1, 2, &[(0, 1), (0, 1), (0, 1)]);
+        assert_eq!(matrix.row_adj(), &[vec![1]]);
+
+        let hx = shor_code().hx().clone();
+        let rebuilt = BinarySparseMatrix::from_triplets(2, 9, &hx.edges());
+        assert_eq!(rebuilt, hx);
+    }
+
+    #[test]
+    fn test_constructor_normalizes_adjacency() {
+        let matrix = BinarySparseMatrix::from_row_adj(1, 3, vec![vec![1, 0, 1]]);
+        assert_eq!(matrix.row_adj(), &[vec![0]]);
+        assert_eq!(matrix.col_adj(), &[vec![0], vec![], vec![]]);
+        assert_eq!(matrix.rank(), 1);
+
+        let unsorted = BinarySparseMatrix::from_row_adj(2, 3, vec![vec![2, 0], vec![1, 0]]);
+        assert_eq!(unsorted.row_adj(), &[vec![0, 2], vec![0, 1]]);
+        assert_eq!(
+            unsorted,
+            BinarySparseMatrix::from_col_adj(2, 3, vec![vec![1, 0], vec![1], vec![0]])
+        );
+        assert_eq!(unsorted.rank(), 2);
+    }
+
+    #[test]
+    fn test_transpose_swaps_adjacency() {
+        let matrix = BinarySparseMatrix::from_row_adj(2, 4, vec![vec![0, 1, 3], vec![1, 2]]);
+        let transposed = matrix.transpose();
+        assert_eq!(transposed.shape(), (4, 2));
+        assert_eq!(transposed.row_adj(), matrix.col_adj());
+        assert_eq!(transposed.col_adj(), matrix.row_adj());
+        assert_eq!(
+            transposed,
+            BinarySparseMatrix::from_col_adj(4, 2, matrix.row_adj().to_vec())
+        );
+        assert_eq!(transposed.transpose(), matrix);
+
+        // 転置後も積は正しく計算できる
+        let v = u8_to_bitvec(&[1, 1]);
+        assert_eq!(&transposed * &v, u8_to_bitvec(&[1, 0, 1, 1]));
+        assert_eq!(transposed.rank(), matrix.rank());
+    }
+
+    #[test]
+    fn test_add_self_is_zero() {
+        let matrix =
+            BinarySparseMatrix::from_row_adj(3, 4, vec![vec![0, 1], vec![1, 2], vec![2, 3]]);
+        assert_eq!(&matrix + &matrix, BinarySparseMatrix::zeros(3, 4));
+    }
+
+    #[test]
+    fn test_add_matches_dense() {
+        let dense_a = vec![vec![1, 1, 0, 0], vec![0, 1, 1, 0], vec![1, 0, 0, 1]];
+        let dense_b = vec![vec![1, 0, 1, 0], vec![0, 0, 0, 0], vec![0, 1, 0, 1]];
+        let dense_sum: Vec<Vec<i32>> = dense_a
+            .iter()
+            .zip(&dense_b)
+            .map(|(row_a, row_b)| row_a.iter().zip(row_b).map(|(a, b)| (a + b) % 2).collect())
+            .collect();
+
+        let sum = dense_a.into_sparse_matrix() + dense_b.into_sparse_matrix();
+        assert_eq!(sum, dense_sum.into_sparse_matrix());
+    }
+
+    #[test]
+    #[should_panic(expected = "行列の形が一致しません")]
+    fn test_add_shape_mismatch() {
+        let _ = BinarySparseMatrix::zeros(2, 3) + BinarySparseMatrix::zeros(3, 2);
+    }
+
+    #[test]
+    fn test_nnz_and_density() {
+        let hz = shor_code().hz().clone();
+        assert_eq!(hz.nnz(), 12);
+        assert_eq!(hz.density(), 12.0 / 54.0);
+
+        assert_eq!(BinarySparseMatrix::zeros(0, 0).density(), 0.0);
+        assert_eq!(BinarySparseMatrix::identity(4).density(), 0.25);
+    }
+
+    #[test]
+    fn test_append_and_remove_row() {
+        let original = BinarySparseMatrix::from_row_adj(2, 4, vec![vec![0, 1], vec![2, 3]]);
+        let mut matrix = original.clone();
+        assert_eq!(matrix.rank(), 2);
+
+        matrix.append_row(&[3, 1, 1, 0]);
+        assert_eq!(matrix.shape(), (3, 4));
+        assert_eq!(matrix.nonzero_cols(2), &[0, 3]);
+        assert_eq!(matrix.nonzero_rows(0), &[0, 2]);
+        assert_eq!(matrix.nonzero_rows(3), &[1, 2]);
+        assert_eq!(matrix.rank(), 3);
+        assert_eq!(
+            matrix,
+            BinarySparseMatrix::from_row_adj(3, 4, matrix.row_adj().to_vec())
+        );
+
+        matrix.remove_row(2);
+        assert_eq!(matrix, original);
+        assert_eq!(matrix.rank(), 2);
+    }
+
+    #[test]
+    fn test_remove_row_shifts_indices() {
+        let mut matrix =
+            BinarySparseMatrix::from_row_adj(3, 3, vec![vec![0, 1], vec![1, 2], vec![0, 2]]);
+        matrix.remove_row(0);
+        let expected = BinarySparseMatrix::from_row_adj(2, 3, vec![vec![1, 2], vec![0, 2]]);
+        assert_eq!(matrix, expected);
+        assert_eq!(matrix.col_adj(), expected.col_adj());
+    }
+
+    #[test]
+    fn test_block_diagonal_shor_hz() {
+        let hz = shor_code().hz().clone();
+        let diag = BinarySparseMatrix::block_diagonal(&[hz.clone(), hz.clone()]);
+        assert_eq!(diag.shape(), (12, 18));
+        assert_eq!(diag.nnz(), 2 * hz.nnz());
+        assert_eq!(diag.rank(), 2 * hz.rank());
+        for row_idx in 0..6 {
+            assert_eq!(diag.nonzero_cols(row_idx), hz.nonzero_cols(row_idx));
+            let shifted: Vec<usize> = hz.nonzero_cols(row_idx).iter().map(|&c| c + 9).collect();
+            assert_eq!(diag.nonzero_cols(row_idx + 6), shifted.as_slice());
+        }
+    }
+
+    #[test]
+    fn test_sparse_matrix_zero_size() {
+        let empty = Vec::<Vec<i32>>::new().into_sparse_matrix();
+        assert_eq!(empty.shape(), (0, 0));
+        assert_eq!(empty.rank(), 0);
+        assert_eq!(
+            &empty * &BitVec::<Word, Lsb0>::new(),
+            BitVec::<Word, Lsb0>::new()
+        );
+        assert_eq!(empty.mul_u8(&[]), Vec::<u8>::new());
+
+        let tall = BinarySparseMatrix::zeros(3, 0);
+        assert_eq!(
+            &tall * &BitVec::<Word, Lsb0>::new(),
+            bitvec![Word, Lsb0; 0; 3]
+        );
+        assert_eq!(tall.mul_u8(&[]), vec![0, 0, 0]);
+        assert_eq!((&tall * &tall.transpose()).shape(), (3, 3));
+        assert_eq!((&tall.transpose() * &tall).shape(), (0, 0));
+    }
+
+    #[test]
+    fn test_alist_round_trip() {
+        let matrix =
+            BinarySparseMatrix::from_row_adj(3, 5, vec![vec![0, 1, 4], vec![1, 2], vec![]]);
+        assert_eq!(BinarySparseMatrix::from_alist(&matrix.to_alist()), matrix);
+
+        // 0埋めのないalistも読める
+        let unpadded = "3 2\n2 2\n1 2 1\n2 2\n1\n1 2\n2\n1 2\n2 3\n";
+        assert_eq!(
+            BinarySparseMatrix::from_alist(unpadded),
+            BinarySparseMatrix::from_row_adj(2, 3, vec![vec![0, 1], vec![1, 2]])
+        );
+    }
+
+    #[test]
+    fn test_try_from_alist_invalid() {
+        // 途中で終わっている
+        assert!(BinarySparseMatrix::try_from_alist("3 2\n2 2\n1 2 1\n2 2\n1\n").is_err());
+        // 数値でない要素がある
+        assert!(BinarySparseMatrix::try_from_alist("3 2\n2 2\n1 x 1\n").is_err());
+        // インデックスが範囲外
+        assert!(BinarySparseMatrix::try_from_alist("1 1\n1 1\n1\n1\n2\n1\n").is_err());
+        // 列ごとの記述と行ごとの記述が一致しない
+        assert!(BinarySparseMatrix::try_from_alist("2 1\n1 1\n1 0\n1\n1\n\n2\n").is_err());
+    }
+
+    #[test]
+    fn test_row_and_col_bitvec() {
+        let matrix =
+            BinarySparseMatrix::from_row_adj(3, 5, vec![vec![0, 2, 4], vec![1, 2], vec![]]);
+        for row_idx in 0..matrix.rows() {
+            let row = matrix.row(row_idx);
+            assert_eq!(row.len(), 5);
+            assert_eq!(
+                row.iter_ones().collect::<Vec<_>>(),
+                matrix.nonzero_cols(row_idx)
+            );
+        }
+        for col_idx in 0..matrix.cols() {
+            let col = matrix.col(col_idx);
+            assert_eq!(col.len(), 3);
+            assert_eq!(
+                col.iter_ones().collect::<Vec<_>>(),
+                matrix.nonzero_rows(col_idx)
+            );
+        }
+        assert_eq!(matrix.row(0), u8_to_bitvec(&[1, 0, 1, 0, 1]));
+        assert_eq!(matrix.col(2), u8_to_bitvec(&[1, 1, 0]));
+        assert!(matrix.row(2).not_any());
+    }
+
+    #[test]
+    fn test_rank_of_columns() {
+        let row_adj = vec![vec![0, 1], vec![1, 2], vec![2, 3], vec![2, 3]];
+        let matrix = BinarySparseMatrix::from_row_adj(4, 4, row_adj);
+        assert_eq!(matrix.rank_of_columns(&[0, 1, 2, 3]), matrix.rank());
+
+        // 列0 + 列1 = 列2 + 列3 のため、4列のうち3列までしか独立にならない
+        assert_eq!(matrix.rank_of_columns(&[0, 1]), 2);
+        assert_eq!(matrix.rank_of_columns(&[0, 1, 2]), 3);
+        let h = BinarySparseMatrix::from_row_adj(2, 3, vec![vec![0, 1], vec![1, 2]]);
+        assert_eq!(h.rank_of_columns(&[0, 1, 2]), 2);
+        assert_eq!(h.rank_of_columns(&[2, 2]), 1);
+        assert_eq!(h.rank_of_columns(&[]), 0);
+    }
+
+    #[test]
+    fn test_sparse_builder_shor_hx() {
+        let mut builder = SparseBuilder::new(2, 9);
+        builder.extend((0..6).map(|col_idx| (0, col_idx)));
+        for col_idx in 3..9 {
+            builder.push(1, col_idx);
+        }
+        let hx = builder.build();
+        assert_eq!(&hx, shor_code().hx());
+
+        assert!(hx[(0, 0)]);
+        assert!(hx[(1, 8)]);
+        assert!(!hx[(0, 6)]);
+        assert!(!hx[(1, 2)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "インデックス(2, 0)が行列のサイズ(2, 9)を超えています")]
+    fn test_sparse_builder_out_of_bounds() {
+        let mut builder = SparseBuilder::new(2, 9);
+        builder.push(2, 0);
+    }
 }
@@ -0,0 +1,106 @@
+use crate::code::css_code::CssCode;
+use crate::code::error_vector::ErrorVector;
+use crate::decoder::traits::Decoder;
+use crate::util::u8_to_bitvec;
+use rand::prelude::*;
+use rand::rngs::StdRng;
+
+/// `fuzz_decoder`の結果
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct FuzzReport {
+    /// 復元操作が誤りと完全に一致した回数
+    pub exact: usize,
+    /// 復元操作が誤りとスタビライザーの分だけ異なり、論理的に等価だった回数
+    pub equivalent: usize,
+    /// 論理誤りになった（またはシンドロームが残った）回数
+    pub failures: usize,
+}
+
+impl FuzzReport {
+    pub fn trials(&self) -> usize {
+        self.exact + self.equivalent + self.failures
+    }
+}
+
+/// 重みが`max_weight`以下のランダムな誤りを`trials`回生成して復号し、結果を分類する
+/// 各試行では重みを 1..=max_weight から一様に選び、その数の量子ビットにX, Y, Zのいずれかを一様に作用させる
+/// 復号器は`decoder_factory`で一度だけ構成し、全ての試行で使い回す
+/// 同じ`seed`からは常に同じ誤りの列が得られるので、復号器の回帰テストに使える
+pub fn fuzz_decoder<D, F>(
+    code: &CssCode,
+    decoder_factory: F,
+    trials: usize,
+    max_weight: usize,
+    seed: u64,
+) -> FuzzReport
+where
+    D: Decoder,
+    F: FnOnce(&CssCode) -> D,
+{
+    let n = code.num_qubits();
+    assert!(
+        (1..=n).contains(&max_weight),
+        "max_weight({})は1以上、量子ビット数({})以下でなければなりません",
+        max_weight,
+        n
+    );
+
+    let mut decoder = decoder_factory(code);
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut report = FuzzReport::default();
+    let (lx, lz) = (code.lx(), code.lz());
+
+    for _ in 0..trials {
+        let weight = rng.random_range(1..=max_weight);
+        let mut x_errors = vec![0u8; n];
+        let mut z_errors = vec![0u8; n];
+        for qubit_idx in rand::seq::index::sample(&mut rng, n, weight) {
+            // 1: X, 2: Y, 3: Z
+            let pauli: u8 = rng.random_range(1..=3);
+            x_errors[qubit_idx] = (pauli <= 2) as u8;
+            z_errors[qubit_idx] = (pauli >= 2) as u8;
+        }
+        let error = ErrorVector::new(u8_to_bitvec(&x_errors), u8_to_bitvec(&z_errors));
+
+        let recovery = decoder.decode(&code.syndrome(&error));
+        if recovery == error {
+            report.exact += 1;
+        } else if !code.is_logical_error_with(&lx, &lz, &error, &recovery) {
+            report.equivalent += 1;
+        } else {
+            report.failures += 1;
+        }
+    }
+
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::channel::depolarizing::DepolarizingChannel;
+    use crate::code::named_codes::shor_code;
+    use crate::decoder::bit_flip::BitFlipDecoderCss;
+    use crate::decoder::bp_css::BpDecoderCss;
+
+    #[test]
+    fn test_fuzz_decoder_shor_code_single_errors() {
+        let code = shor_code();
+        let report = fuzz_decoder(&code, |code| BitFlipDecoderCss::new(code, 9), 200, 1, 11);
+        assert_eq!(report.trials(), 200);
+        assert_eq!(report.failures, 0);
+        // Z誤りは同じブロック内の量子ビットと区別できないので、等価な復元操作になることがある
+        assert!(report.exact > 0);
+    }
+
+    #[test]
+    fn test_fuzz_decoder_reproducible() {
+        let code = shor_code();
+        let channel = DepolarizingChannel::new(9, 0.05);
+        let factory = |code: &CssCode| BpDecoderCss::builder(code, &channel).build();
+        assert_eq!(
+            fuzz_decoder(&code, factory, 50, 3, 4),
+            fuzz_decoder(&code, factory, 50, 3, 4)
+        );
+    }
+}
@@ -0,0 +1,114 @@
+use crate::channel::traits::ErrorChannel;
+use crate::code::css_code::CssCode;
+use crate::decoder::traits::Decoder;
+
+/// `simulate`によるモンテカルロシミュレーションの結果
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SimResult {
+    pub samples: usize,
+    /// 復号に失敗した（残差がスタビライザーでなかった）サンプル数
+    pub failures: usize,
+    /// 残差のX部分がZ型論理演算子と反可換で、論理X誤りが起きたサンプル数
+    pub x_logical_failures: usize,
+    /// 残差のZ部分がX型論理演算子と反可換で、論理Z誤りが起きたサンプル数
+    pub z_logical_failures: usize,
+}
+
+impl SimResult {
+    pub fn logical_error_rate(&self) -> f64 {
+        rate(self.failures, self.samples)
+    }
+
+    pub fn x_logical_error_rate(&self) -> f64 {
+        rate(self.x_logical_failures, self.samples)
+    }
+
+    pub fn z_logical_error_rate(&self) -> f64 {
+        rate(self.z_logical_failures, self.samples)
+    }
+}
+
+fn rate(count: usize, samples: usize) -> f64 {
+    if samples == 0 {
+        return 0.0;
+    }
+    count as f64 / samples as f64
+}
+
+/// チャネルから`num_samples`個の誤りをシードを固定してサンプリングし、復号して論理誤りを数える
+/// 残差のシンドロームが残った場合は`failures`にだけ数え、シンドロームが自明な場合は
+/// X部分とZ部分をそれぞれ論理演算子と照らし合わせて、論理X誤りと論理Z誤りを別々に数える
+/// 両方が同時に起きたサンプル（論理Y誤り）は両方に数える
+pub fn simulate<C: ErrorChannel, D: Decoder>(
+    code: &CssCode,
+    channel: &C,
+    decoder: &mut D,
+    num_samples: usize,
+    seed: u64,
+) -> SimResult {
+    let lx = code.lx();
+    let lz = code.lz();
+    let mut result = SimResult {
+        samples: num_samples,
+        ..SimResult::default()
+    };
+
+    for error in channel.sample_batch_with_seed(num_samples, seed) {
+        let recovery = decoder.decode(&code.syndrome(&error));
+        let Some((x_logical, z_logical)) = code.logical_flips_with(&lx, &lz, &error, &recovery)
+        else {
+            result.failures += 1;
+            continue;
+        };
+        if x_logical || z_logical {
+            result.failures += 1;
+        }
+        result.x_logical_failures += x_logical as usize;
+        result.z_logical_failures += z_logical as usize;
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::channel::composed::ComposedChannel;
+    use crate::channel::depolarizing::DepolarizingChannel;
+    use crate::channel::single_pauli::SinglePauliChannel;
+    use crate::code::named_codes::shor_code;
+    use crate::decoder::bit_flip::BitFlipDecoderCss;
+
+    #[test]
+    fn test_simulate_z_biased_channel() {
+        let code = shor_code();
+        let channel = ComposedChannel::new(vec![
+            Box::new(DepolarizingChannel::new(9, 0.01)),
+            Box::new(SinglePauliChannel::phase_flip(9, 0.1)),
+        ]);
+        let mut decoder = BitFlipDecoderCss::new(&code, 9);
+
+        let result = simulate(&code, &channel, &mut decoder, 2000, 3);
+        assert_eq!(result.samples, 2000);
+        assert!(result.z_logical_failures > 0);
+        assert!(result.z_logical_failures > 5 * result.x_logical_failures);
+        assert!(result.failures >= result.z_logical_failures);
+        assert!(result.z_logical_error_rate() > result.x_logical_error_rate());
+    }
+
+    #[test]
+    fn test_simulate_no_noise() {
+        let code = shor_code();
+        let channel = DepolarizingChannel::new(9, 0.0);
+        let mut decoder = BitFlipDecoderCss::new(&code, 9);
+        let result = simulate(&code, &channel, &mut decoder, 100, 0);
+        assert_eq!(
+            result,
+            SimResult {
+                samples: 100,
+                ..SimResult::default()
+            }
+        );
+        assert_eq!(result.logical_error_rate(), 0.0);
+    }
+}
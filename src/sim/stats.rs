@@ -0,0 +1,50 @@
+use crate::code::error_vector::ErrorVector;
+
+/// 誤りのバッチを重みごとに数えたヒストグラムを返す
+/// 戻り値のw番目の要素は重みwの誤りの個数で、長さはバッチ中の最大重み+1（空のバッチでは0）
+///
+/// # Examples
+/// ```rust
+/// use qldpc_sim::code::error_vector::ErrorVector;
+/// use qldpc_sim::sim::stats::weight_histogram;
+///
+/// let errors = vec![ErrorVector::from_string("XII"), ErrorVector::from_string("XYI")];
+/// assert_eq!(weight_histogram(&errors), vec![0, 1, 1]);
+/// ```
+pub fn weight_histogram(errors: &[ErrorVector]) -> Vec<usize> {
+    let mut histogram = Vec::new();
+    for error in errors {
+        let weight = error.num_errors();
+        if histogram.len() <= weight {
+            histogram.resize(weight + 1, 0);
+        }
+        histogram[weight] += 1;
+    }
+    histogram
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::channel::depolarizing::DepolarizingChannel;
+    use crate::channel::traits::ErrorChannel;
+
+    #[test]
+    fn test_weight_histogram_zero_rate() {
+        let channel = DepolarizingChannel::new(5, 0.0);
+        let errors = channel.sample_batch(100);
+        assert_eq!(weight_histogram(&errors), vec![100]);
+    }
+
+    #[test]
+    fn test_weight_histogram_counts() {
+        let errors = vec![
+            ErrorVector::from_string("IIII"),
+            ErrorVector::from_string("XIZI"),
+            ErrorVector::from_string("YIII"),
+            ErrorVector::from_string("IZIY"),
+        ];
+        assert_eq!(weight_histogram(&errors), vec![1, 1, 2]);
+        assert!(weight_histogram(&[]).is_empty());
+    }
+}
@@ -0,0 +1,67 @@
+/// 物理誤り率と論理誤り率の組 `(p, logical_rate)` のスイープから、擬似しきい値を求める
+/// 擬似しきい値は論理誤り率が物理誤り率と等しくなる物理誤り率で、
+/// `logical_rate - p` の符号が変わる隣り合った2点の間を線形補間して求める
+/// 点は物理誤り率の昇順に並べ替えてから調べ、交点が複数あれば最も小さいものを返す
+/// 交点が見つからなければ`None`を返す
+///
+/// # Examples
+/// ```rust
+/// use qldpc_sim::sim::pseudo_threshold;
+///
+/// let points = vec![(0.02, 0.01), (0.06, 0.09)];
+/// let threshold = pseudo_threshold(&points).unwrap();
+/// assert!((threshold - 0.03).abs() < 1e-12);
+/// ```
+pub fn pseudo_threshold(points: &[(f64, f64)]) -> Option<f64> {
+    let mut sorted = points.to_vec();
+    sorted.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    for window in sorted.windows(2) {
+        let (p0, logical0) = window[0];
+        let (p1, logical1) = window[1];
+        let diff0 = logical0 - p0;
+        let diff1 = logical1 - p1;
+        if diff0 == 0.0 {
+            return Some(p0);
+        }
+        if diff0 * diff1 < 0.0 {
+            return Some(p0 + (p1 - p0) * diff0 / (diff0 - diff1));
+        }
+    }
+
+    sorted
+        .last()
+        .filter(|(p, logical)| logical == p)
+        .map(|(p, _)| *p)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pseudo_threshold_interpolates_crossing() {
+        // p_L = 10 p^2 は p = 0.1 で p_L = p と交わる
+        let points: Vec<(f64, f64)> = [0.02, 0.05, 0.08, 0.12, 0.15]
+            .iter()
+            .map(|&p| (p, 10.0 * p * p))
+            .collect();
+        let threshold = pseudo_threshold(&points).unwrap();
+        assert!(threshold > 0.08 && threshold < 0.12);
+
+        // (0.08, 0.064) と (0.12, 0.144) の間の線形補間
+        let expected = 0.08 + 0.04 * (0.064 - 0.08) / ((0.064 - 0.08) - (0.144 - 0.12));
+        assert!((threshold - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_pseudo_threshold_unsorted_and_missing() {
+        let points = vec![(0.06, 0.09), (0.02, 0.01)];
+        assert!((pseudo_threshold(&points).unwrap() - 0.03).abs() < 1e-12);
+
+        // 常に論理誤り率が物理誤り率を下回る場合は交点がない
+        let below = vec![(0.01, 0.001), (0.1, 0.05)];
+        assert_eq!(pseudo_threshold(&below), None);
+        assert_eq!(pseudo_threshold(&[]), None);
+    }
+}
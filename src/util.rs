@@ -0,0 +1,51 @@
+use crate::Word;
+use bitvec::prelude::*;
+
+/// `BitVec`を0/1の`Vec<u8>`に変換する
+pub fn bitvec_to_u8(bits: &BitVec<Word, Lsb0>) -> Vec<u8> {
+    bits.iter().map(|bit| *bit as u8).collect()
+}
+
+/// `Vec<u8>`を`BitVec`に変換する。0以外の値は1として扱う
+pub fn u8_to_bitvec(values: &[u8]) -> BitVec<Word, Lsb0> {
+    values.iter().map(|&value| value != 0).collect()
+}
+
+/// `BitVec`を`Vec<bool>`に変換する
+pub fn bitvec_to_bool(bits: &BitVec<Word, Lsb0>) -> Vec<bool> {
+    bits.iter().map(|bit| *bit).collect()
+}
+
+/// `Vec<bool>`を`BitVec`に変換する
+pub fn bool_to_bitvec(values: &[bool]) -> BitVec<Word, Lsb0> {
+    values.iter().copied().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_u8_round_trip() {
+        for values in [vec![], vec![1], vec![0], vec![1, 0, 1, 1, 0]] {
+            let bits = u8_to_bitvec(&values);
+            assert_eq!(bits.len(), values.len());
+            assert_eq!(bitvec_to_u8(&bits), values);
+        }
+        assert_eq!(u8_to_bitvec(&[2, 0]), u8_to_bitvec(&[1, 0]));
+    }
+
+    #[test]
+    fn test_bool_round_trip() {
+        for values in [
+            vec![],
+            vec![true],
+            vec![false],
+            vec![true, false, false, true],
+        ] {
+            let bits = bool_to_bitvec(&values);
+            assert_eq!(bits.len(), values.len());
+            assert_eq!(bitvec_to_bool(&bits), values);
+        }
+    }
+}